@@ -159,6 +159,28 @@ impl Action {
     (keyboard_mask:impl Into<KeyboardPattern>, mouse_mask:impl Into<MousePattern>) -> Self {
         Self::new(ActionType::DoublePress,ActionPattern::new(keyboard_mask,mouse_mask))
     }
+
+    /// A human-readable description of the keys triggering the action, like `Control + G`.
+    /// Returns `None` if the action is not a keyboard shortcut, i.e. it matches any keys or
+    /// requires pressing mouse buttons.
+    pub fn keyboard_description(&self) -> Option<String> {
+        let no_mouse_buttons = self.mask.mouse == Pattern::Equal(default());
+        let keys             = match &self.mask.keyboard {
+            Pattern::Equal(mask) if no_mouse_buttons => mask.keys(),
+            _                                        => return None,
+        };
+        let keys = keys.into_iter().map(|key| match key {
+            keyboard::Key::Character(character) if character == " " => "Space".to_string(),
+            keyboard::Key::Character(character)                     => character.to_uppercase(),
+            other                                                    => other.to_string(),
+        });
+        let description = keys.join(" + ");
+        match self.tp {
+            _ if description.is_empty() => None,
+            ActionType::DoublePress     => Some(iformat!("Double {description}")),
+            _                           => Some(description),
+        }
+    }
 }
 
 
@@ -351,6 +373,18 @@ impl RegistryModel {
         Self {logger,keyboard,mouse,command_registry,action_map}
     }
 
+    /// Get the actions triggering the command on the target. Useful for displaying the shortcuts
+    /// to the user.
+    pub fn actions_of(&self, target:&str, command:&str) -> Vec<Action> {
+        let action_map = self.action_map.borrow();
+        let rule_maps  = action_map.iter().flat_map(|(action_type,rule_map)| {
+            rule_map.iter().map(move |(pattern,rules)| (action_type,pattern,rules))
+        });
+        rule_maps.filter(|(_,_,rules)| rules.iter().any(|rule| {
+            rule.upgrade().map_or(false,|rule| rule.target == target && *rule.command == command)
+        })).map(|(action_type,pattern,_)| Action::new(*action_type,pattern.clone())).collect()
+    }
+
     fn process_action(&self, action_type:ActionType, mask:&ActionMask) {
         let pattern        = ActionPattern::from(mask);
         let action_map_mut = &mut self.action_map.borrow_mut();
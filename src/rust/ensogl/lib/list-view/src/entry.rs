@@ -126,10 +126,11 @@ impl ModelProvider for EmptyProvider {
 /// simpler.
 #[derive(Clone,CloneRef,Debug)]
 pub struct Entry {
-    id             : Rc<Cell<Option<Id>>>,
-    label          : text::Area,
-    icon           : Rc<CloneCell<Option<display::object::Any>>>,
-    display_object : display::object::Instance,
+    id              : Rc<Cell<Option<Id>>>,
+    label           : text::Area,
+    icon            : Rc<CloneCell<Option<display::object::Any>>>,
+    display_object  : display::object::Instance,
+    highlight_color : Immutable<color::Rgba>,
 }
 
 impl Entry {
@@ -144,9 +145,12 @@ impl Entry {
         // FIXME : StyleWatch is unsuitable here, as it was designed as an internal tool for shape system (#795)
         let styles     = StyleWatch::new(&app.display.scene().style_sheet);
         let text_color = styles.get_color(ensogl_theme::vars::widget::list_view::text::color);
+        let highlight  = ensogl_theme::vars::widget::list_view::text::highlight::color;
+        let highlight  = styles.get_color(highlight);
         label.set_default_color(color::Rgba::from(text_color));
         label.set_default_text_size(text::Size(LABEL_SIZE));
-        Entry{id,label,icon,display_object}
+        let highlight_color = Immutable(color::Rgba::from(highlight));
+        Entry{id,label,icon,display_object,highlight_color}
     }
 
     /// Set the new model for this view.
@@ -164,7 +168,7 @@ impl Entry {
         self.icon.set(model.icon.clone());
         self.label.set_content(&model.label);
         for highlighted in &model.highlighted {
-            self.label.set_color_bytes(highlighted,color::Rgba::new(1.0,1.0,1.0,1.0));
+            self.label.set_color_bytes(highlighted,*self.highlight_color);
         }
     }
 }
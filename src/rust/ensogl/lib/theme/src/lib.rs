@@ -123,6 +123,35 @@ define_theme! { dark
                     color = color::Lcha::new(0.7,0.0,0.125,0.7)
                 }
             }
+            comment {
+                color = color::Lcha::new(1.0,0.0,0.0,0.4)
+            }
+            widget {
+                background {
+                    color = color::Lcha::new(0.3,0.013,0.18,1.0)
+                }
+            }
+            port {
+                compatible {
+                    color = color::Lcha::new(0.8,0.6,0.35,0.4)
+                }
+                incompatible {
+                    color = color::Lcha::new(0.13,0.013,0.18,0.6)
+                }
+            }
+        }
+        note {
+            background {
+                color = color::Lcha::new(0.35,0.12,0.27,1.0)
+            }
+            text {
+                color = color::Lcha::new(1.0,0.0,0.0,0.7)
+            }
+        }
+        group {
+            title {
+                color = color::Lcha::new(1.0,0.0,0.0,0.7)
+            }
         }
         visualization {
             background {
@@ -156,6 +185,12 @@ define_theme! { dark
             hover {
                 color = color::Lcha::new(1.0,0.0,0.0,0.6)
             }
+            execution_failure {
+                color = color::Lcha::new(0.6,0.7,0.05,1.0)
+            }
+            file_warning {
+                color = color::Lcha::new(0.7,0.6,0.2,1.0)
+            }
         }
         edge {
             split_color {
@@ -166,6 +201,9 @@ define_theme! { dark
                 missing {
                      color = color::Lcha::new(0.5,0.0,0.0,1.0)
                 }
+                mismatch {
+                    color = color::Lcha::new(0.55,0.8,0.07,1.0)
+                }
                 color {
                     luminance = 0.5;
                     chroma = 0.8
@@ -183,6 +221,9 @@ define_theme! { dark
             }
             text {
                 color = color::Lcha::new(1.0,0.0,0.0,0.7);
+                highlight {
+                    color = color::Lcha::new(1.0,0.0,0.0,1.0)
+                }
                 selection {
                     color = color::Lcha::new(0.7,0.0,0.125,0.7)
                 }
@@ -220,6 +261,35 @@ define_default_theme! { light
                     color = color::Lcha::new(0.7,0.0,0.125,0.7)
                 }
             }
+            comment {
+                color = color::Lcha::new(0.0,0.0,0.0,0.4)
+            }
+            widget {
+                background {
+                    color = color::Lcha::new(0.9,0.013,0.18,1.0)
+                }
+            }
+            port {
+                compatible {
+                    color = color::Lcha::new(0.6,0.6,0.35,0.4)
+                }
+                incompatible {
+                    color = color::Lcha::new(0.96,0.013,0.18,0.6)
+                }
+            }
+        }
+        note {
+            background {
+                color = color::Lcha::new(0.94,0.2,0.27,1.0)
+            }
+            text {
+                color = color::Lcha::new(0.0,0.0,0.0,0.7)
+            }
+        }
+        group {
+            title {
+                color = color::Lcha::new(0.0,0.0,0.0,0.7)
+            }
         }
         visualization {
             background {
@@ -253,6 +323,12 @@ define_default_theme! { light
             hover {
                 color = color::Lcha::new(0.0,0.0,0.0,0.6)
             }
+            execution_failure {
+                color = color::Lcha::new(0.45,0.7,0.05,1.0)
+            }
+            file_warning {
+                color = color::Lcha::new(0.5,0.6,0.2,1.0)
+            }
         }
         edge {
             split_color {
@@ -263,6 +339,9 @@ define_default_theme! { light
                 missing {
                     color = color::Lcha::new(0.8,0.0,0.0,1.0)
                 }
+                mismatch {
+                    color = color::Lcha::new(0.55,0.8,0.07,1.0)
+                }
                 color {
                     luminance = 0.8;
                     chroma = 0.6
@@ -280,6 +359,9 @@ define_default_theme! { light
             }
             text {
                 color = color::Lcha::new(0.0,0.0,0.0,0.7);
+                highlight {
+                    color = color::Lcha::new(0.0,0.0,0.0,1.0)
+                }
                 selection {
                     color = color::Lcha::new(0.7,0.0,0.125,0.7)
                 }
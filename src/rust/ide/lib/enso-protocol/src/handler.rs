@@ -114,19 +114,25 @@ where Id           : Copy + Debug + Display + Hash + Eq + Send + Sync + 'static,
     }
 
     /// Handles incoming transport event. The `processor` is used to decide the further processing
-    /// path.
+    /// path, unless the transport has been closed.
     ///
     /// Main entry point for input data while running. Should be connected to the `Transport`s
     /// output event stream.
     pub fn process_event(&mut self, event:TransportEvent) {
         group!(self.logger, "Processing incoming transport event", {
             debug!(self.logger, "Transport event contents: {event:?}.");
-            let disposition = (self.processor)(event);
-            debug!(self.logger, "Disposition: {disposition:?}");
-            match disposition {
-                Disposition::HandleReply {id,reply} => self.process_reply(id,reply),
-                Disposition::EmitEvent {event} => self.emit_event(event),
-                Disposition::Ignore => {}
+            if let TransportEvent::Closed = event {
+                // Dropping all ongoing calls will cancel their futures.
+                self.ongoing_calls.clear();
+                self.emit_event(Event::Closed);
+            } else {
+                let disposition = (self.processor)(event);
+                debug!(self.logger, "Disposition: {disposition:?}");
+                match disposition {
+                    Disposition::HandleReply {id,reply} => self.process_reply(id,reply),
+                    Disposition::EmitEvent {event} => self.emit_event(event),
+                    Disposition::Ignore => {}
+                }
             }
         });
     }
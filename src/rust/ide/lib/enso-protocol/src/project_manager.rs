@@ -51,9 +51,11 @@ trait API {
     #[MethodInput=ListRecentProjectsInput,rpc_name="project/list"]
     fn list_projects(&self, number_of_projects:Option<u32>) -> response::ProjectList;
 
-    /// Request the creation of a new project.
+    /// Request the creation of a new project. If the template is given, the project is created as
+    /// a copy of the sample project with that name (see `list_samples`).
     #[MethodInput=CreateProjectInput,rpc_name="project/create"]
-    fn create_project(&self, name:String) -> response::CreateProject;
+    fn create_project(&self, name:String, project_template:Option<String>)
+    -> response::CreateProject;
 
     /// Request project renaming.
     #[MethodInput=RenameProject,rpc_name="project/rename"]
@@ -181,7 +183,8 @@ mod mock_client_tests {
             language_server_binary_address : language_server_address,
         };
         let open_result             = Ok(expected_ip_with_socket.clone());
-        expect_call!(mock_client.create_project(name="HelloWorld".to_string()) => Ok(creation_response));
+        expect_call!(mock_client.create_project(name="HelloWorld".to_string(),project_template=None)
+            => Ok(creation_response));
         expect_call!(mock_client.open_project(expected_uuid) => open_result);
         expect_call!(mock_client.close_project(expected_uuid) => error("Project isn't open."));
         expect_call!(mock_client.delete_project(expected_uuid) => error("Project doesn't exist."));
@@ -189,7 +192,7 @@ mod mock_client_tests {
         let delete_result = mock_client.delete_project(&expected_uuid);
         result(delete_result).expect_err("Project shouldn't exist.");
 
-        let creation_response = mock_client.create_project(&"HelloWorld".to_string());
+        let creation_response = mock_client.create_project(&"HelloWorld".to_string(),&None);
         let uuid = result(creation_response).expect("Couldn't create project").project_id;
         assert_eq!(uuid, expected_uuid);
 
@@ -333,7 +336,11 @@ mod remote_client_tests {
             }
         });
         let project_name            = String::from("HelloWorld");
-        let project_name_json       = json!({"name":serde_json::to_value(&project_name).unwrap()});
+        let project_template        = Some(String::from("Sample"));
+        let project_name_json       = json!({
+            "name"            : serde_json::to_value(&project_name).unwrap(),
+            "projectTemplate" : serde_json::to_value(&project_template).unwrap()
+        });
         let number_of_projects      = 2;
         let number_of_projects_json = json!({"numberOfProjects":number_of_projects});
         let num_projects_json       = json!({"numProjects":number_of_projects});
@@ -399,7 +406,7 @@ mod remote_client_tests {
             &()
         );
         test_request(
-            |client| client.create_project(&project_name),
+            |client| client.create_project(&project_name,&project_template),
             "project/create",
             &project_name_json,
             &project_id_json,
@@ -21,6 +21,12 @@ pub const DEFAULT_PROJECT_NAME:&str = "Unnamed";
 /// Visualization folder where IDE can look for user-defined visualizations per project.
 pub const VISUALIZATION_DIRECTORY:&str = "visualization";
 
+/// The directory in the project where IDE keeps the data cached between sessions.
+pub const CACHE_DIRECTORY:&str = ".enso";
+
+/// The file in the `CACHE_DIRECTORY` containing the cached Suggestion Database.
+pub const SUGGESTION_DATABASE_CACHE_FILE:&str = "suggestion-database.json";
+
 /// A module with language-specific constants.
 pub mod keywords {
     /// A keyword indicating current module.
@@ -17,6 +17,7 @@
 
 pub mod graph;
 pub mod module;
+pub mod project_browser;
 pub mod text;
 pub mod visualization;
 pub mod searcher;
@@ -24,6 +25,7 @@ pub mod searcher;
 pub use graph::Handle           as Graph;
 pub use graph::executed::Handle as ExecutedGraph;
 pub use module::Handle          as Module;
+pub use project_browser::Handle as ProjectBrowser;
 pub use text::Handle            as Text;
 pub use visualization::Handle   as Visualization;
 pub use searcher::Searcher;
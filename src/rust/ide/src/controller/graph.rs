@@ -2,19 +2,31 @@
 //!
 //! This controller provides access to a specific graph. It lives under a module controller, as
 //! each graph belongs to some module.
+pub mod clipboard;
 pub mod executed;
+pub mod layout;
+pub mod widget;
 
 use crate::prelude::*;
 
+use crate::controller::graph::clipboard::ClipboardContent;
 use crate::double_representation::definition;
 use crate::double_representation::graph::GraphInfo;
 use crate::double_representation::identifier::LocatedName;
 use crate::double_representation::identifier::NormalizedName;
 use crate::double_representation::identifier::generate_name;
 use crate::double_representation::module;
+use crate::double_representation::module::ImportInfo;
 use crate::double_representation::node;
 use crate::double_representation::node::NodeInfo;
+use crate::model::module::Group;
+use crate::model::module::GroupId;
+use crate::model::module::GroupNotFound;
 use crate::model::module::NodeMetadata;
+use crate::model::module::Note;
+use crate::model::module::NoteId;
+use crate::model::module::NoteNotFound;
+use crate::model::module::Position;
 
 use ast::crumbs::InfixCrumb;
 use enso_protocol::language_server;
@@ -67,6 +79,8 @@ pub struct NoPatternOnNode {
 pub enum Notification {
     /// The content should be fully reloaded.
     Invalidate,
+    /// The module's file has been changed by another program.
+    ExternalChange(model::module::ExternalChange),
 }
 
 
@@ -605,6 +619,20 @@ impl Handle {
         }
     }
 
+    /// Renames the variable introduced by the node's pattern. All usages of the variable in the
+    /// graph are updated, so the node's connections are kept.
+    ///
+    /// Fails if the pattern is not a single variable or if the new name is already used in this
+    /// graph, as the renamed variable could shadow or be shadowed by another one.
+    pub fn rename_node_variable(&self, id:node::Id, new_name:&str) -> FallibleResult<()> {
+        use double_representation::refactorings::rename::rename_variable;
+        info!(self.logger, "Renaming variable of node {id} to `{new_name}`.");
+        let _transaction = self.module.open_transaction("Rename variable");
+        let graph        = self.graph_info()?;
+        let updated      = rename_variable(&graph,id,new_name)?;
+        self.update_definition_ast(|_| Ok(updated))
+    }
+
     /// Reorders lines so the former node is placed after the latter.
     /// Does nothing, if the latter node is already placed after former.
     pub fn place_node_line_after
@@ -666,6 +694,19 @@ impl Handle {
         self.set_expression_ast(connection.destination.node, updated_expression)
     }
 
+    /// Put the value given as code into the port, replacing its current expression. Used by the
+    /// value-editing widgets of node's input ports.
+    pub fn set_port_value
+    (&self, endpoint:&Endpoint, value:impl Str, context:&impl SpanTreeContext)
+    -> FallibleResult<()> {
+        info!(self.logger, "Setting port {endpoint.port:?} value to `{value.as_ref()}`");
+        let node               = self.node_info(endpoint.node)?;
+        let info               = EndpointInfo::new(endpoint,node.expression(),context)?;
+        let value_ast          = self.parse_node_expression(value)?;
+        let updated_expression = info.set_ast(value_ast)?;
+        self.set_expression_ast(endpoint.node,updated_expression)
+    }
+
     /// Obtain the definition information for this graph from the module's AST.
     pub fn definition(&self) -> FallibleResult<definition::ChildDefinition> {
         let module_ast = self.module.ast();
@@ -699,6 +740,7 @@ impl Handle {
     /// Adds a new node to the graph and returns information about created node.
     pub fn add_node(&self, node:NewNodeInfo) -> FallibleResult<ast::Id> {
         info!(self.logger, "Adding node with expression `{node.expression}`");
+        let _transaction  = self.module.open_transaction("Add node");
         let ast           = self.parse_node_expression(&node.expression)?;
         let mut node_info = node::NodeInfo::from_line_ast(&ast).ok_or(FailedToCreateNode)?;
         if let Some(desired_id) = node.id {
@@ -722,6 +764,7 @@ impl Handle {
     /// Removes the node with given Id.
     pub fn remove_node(&self, id:ast::Id) -> FallibleResult<()> {
         info!(self.logger, "Removing node {id}");
+        let _transaction = self.module.open_transaction("Remove node");
         self.update_definition_ast(|definition| {
             let mut graph = GraphInfo::from_definition(definition);
             graph.remove_node(id)?;
@@ -730,6 +773,7 @@ impl Handle {
 
         // It's fine if there were no metadata.
         let _ = self.module.remove_node_metadata(id);
+        self.replace_nodes_in_groups(&[id],&[]);
         Ok(())
     }
 
@@ -761,6 +805,7 @@ impl Handle {
         use double_representation::refactorings::collapse::Collapsed;
         let nodes : Vec<_> = Result::from_iter(nodes.into_iter().map(|id| self.node(id)))?;
         info!(self.logger, "Collapsing {nodes:?}.");
+        let _transaction = self.module.open_transaction("Collapse nodes");
         let collapsed_positions = nodes.iter().filter_map(|node| {
             node.metadata.as_ref().and_then(|metadata| metadata.position)
         });
@@ -780,9 +825,297 @@ impl Handle {
         let position = Some(model::module::Position::mean(collapsed_positions));
         let metadata = NodeMetadata {position,..default()};
         self.module.set_node_metadata(collapsed_node,metadata);
+        let collapsed_ids = nodes.iter().map(|node| node.info.id()).collect_vec();
+        self.replace_nodes_in_groups(&collapsed_ids,&[collapsed_node]);
         Ok(collapsed_node)
     }
 
+    /// Expands the node calling a method defined in this module, replacing it with the method's
+    /// body. This is an inverse of the `collapse` operation.
+    ///
+    /// If `remove_unused_method` is set, the inlined method is removed from the module once it is
+    /// not used anywhere else. Returns the ids of nodes replacing the expanded one.
+    pub fn expand_node
+    (&self, id:node::Id, remove_unused_method:bool) -> FallibleResult<Vec<node::Id>> {
+        use double_representation::refactorings::expand;
+        use double_representation::refactorings::expand::MethodCall;
+        let node = self.node(id)?;
+        info!(self.logger, "Expanding {node:?}.");
+        let _transaction = self.module.open_transaction("Expand node");
+        let call         = MethodCall::try_new(node.info.expression())?;
+        let method       = module::locate_child(&self.module.ast(),&call.name)?;
+        let used_names   = self.used_names()?.into_iter().map(|located_name| located_name.item);
+        let graph        = self.graph_info()?;
+        let expanded     = expand::expand(&graph,id,&method.item,used_names)?;
+        self.update_definition_ast(|_| Ok(expanded.updated_definition))?;
+
+        let original_position = node.metadata.and_then(|metadata| metadata.position);
+        let source_positions  = expanded.expanded_nodes.iter().filter_map(|node| {
+            self.module.node_metadata(node.source?).ok()?.position
+        }).collect_vec();
+        let mean_position = Position::mean(source_positions.into_iter());
+        for node in expanded.expanded_nodes.iter().filter(|node| node.id != id) {
+            let source_metadata = node.source.and_then(|src| self.module.node_metadata(src).ok());
+            let source_metadata = source_metadata.unwrap_or_default();
+            let position        = original_position.and_then(|original_position| {
+                Some(original_position + (source_metadata.position? - mean_position))
+            });
+            let intended_method = source_metadata.intended_method;
+            let comment         = source_metadata.comment;
+            let metadata        = NodeMetadata {position,intended_method,comment};
+            self.module.set_node_metadata(node.id,metadata);
+        }
+
+        let module_ast    = self.module.ast();
+        let is_this_graph = self.id.crumbs.first() == Some(&call.name);
+        let is_used       = is_this_graph || expand::is_method_used(&module_ast,&call.name);
+        if remove_unused_method && !is_used {
+            let mut module = module::Info {ast:module_ast};
+            module.remove_method(&call.name)?;
+            self.module.update_ast(module.ast);
+        }
+        let expanded_ids = expanded.expanded_nodes.iter().map(|node| node.id).collect_vec();
+        self.replace_nodes_in_groups(&[id],&expanded_ids);
+        Ok(expanded_ids)
+    }
+
+    /// Describe the given nodes, so they can be put into the clipboard and pasted later with
+    /// `paste_nodes`. The nodes are stored in the order of their lines in the graph.
+    pub fn copy_nodes
+    (&self, ids:impl IntoIterator<Item=node::Id>) -> FallibleResult<ClipboardContent> {
+        let ids   = ids.into_iter().collect::<HashSet<_>>();
+        let nodes = self.nodes()?.into_iter().filter(|node| ids.contains(&node.info.id()));
+        ClipboardContent::from_nodes(&nodes.collect_vec())
+    }
+
+    /// Paste the copied nodes at the end of the graph, around the given position.
+    ///
+    /// The variables introduced by pasted nodes get fresh names, so they won't collide with the
+    /// identifiers used in this graph. The modules defining the methods called by pasted nodes are
+    /// imported if needed. Returns the ids of the pasted nodes.
+    pub fn paste_nodes
+    (&self, content:&ClipboardContent, position:Position) -> FallibleResult<Vec<node::Id>> {
+        info!(self.logger, "Pasting {content.nodes.len()} nodes.");
+        let _transaction = self.module.open_transaction("Paste nodes");
+        let used_names   = self.used_names()?.into_iter().map(|located_name| located_name.item);
+        let nodes        = content.parse_with_fresh_names(&self.parser,used_names)?;
+        self.update_definition_ast(|definition| {
+            let mut graph = GraphInfo::from_definition(definition);
+            for node in &nodes {
+                graph.add_node(node.ast().clone_ref(),LocationHint::End)?;
+            }
+            Ok(graph.source)
+        })?;
+        for (node,copied) in nodes.iter().zip(&content.nodes) {
+            let position        = Some(position + copied.position.unwrap_or_default());
+            let intended_method = copied.intended_method.clone();
+            let comment         = copied.comment.clone();
+            let metadata        = NodeMetadata {position,intended_method,comment};
+            self.module.set_node_metadata(node.id(),metadata);
+        }
+        self.add_imports_for_pasted_nodes(content);
+        Ok(nodes.iter().map(|node| node.id()).collect())
+    }
+
+    fn add_imports_for_pasted_nodes(&self, content:&ClipboardContent) {
+        let mut module   = module::Info {ast:self.module.ast()};
+        let mut modified = false;
+        let here         = self.module.path().id();
+        for import in content.required_modules() {
+            let is_here          = *import.id() == here;
+            let import           = ImportInfo::from_qualified_name(import);
+            let already_imported = module.iter_imports().any(|imp| imp == import);
+            if !is_here && !already_imported {
+                module.add_import(&self.parser,import);
+                modified = true;
+            }
+        }
+        if modified {
+            self.module.update_ast(module.ast);
+        }
+    }
+
+    /// Arrange the given nodes with the automatic layout (see `layout` module) and store their new
+    /// positions in the module metadata.
+    ///
+    /// The layout is horizontally centered at the mean position of the arranged nodes and starts
+    /// at the topmost of them, so the nodes stay in the same part of the scene.
+    pub fn arrange_nodes(&self, ids:impl IntoIterator<Item=node::Id>) -> FallibleResult<()> {
+        let ids          = ids.into_iter().collect::<HashSet<_>>();
+        let nodes        = self.nodes()?.into_iter().filter(|node| ids.contains(&node.info.id()));
+        let nodes        = nodes.collect_vec();
+        info!(self.logger, "Arranging {nodes.len()} nodes.");
+        let _transaction = self.module.open_transaction("Arrange nodes");
+        let connections  = self.connections(self)?.connections;
+        let positions    = nodes.iter().filter_map(|node| node.metadata.as_ref()?.position);
+        let positions    = positions.collect_vec();
+        let mean         = Position::mean(positions.iter().copied());
+        let top          = positions.iter().map(|position| position.vector.y).fold(None,|top,y| {
+            Some(top.map_or(y,|top:f32| top.max(y)))
+        });
+        let origin       = Position::new(mean.vector.x,top.unwrap_or_default());
+        let graph        = layout::Graph::from_nodes(&nodes,&connections);
+        for (id,position) in graph.layout_at(default(),origin) {
+            self.module.with_node_metadata(id,Box::new(|md| md.position = Some(position)));
+        }
+        Ok(())
+    }
+
+    /// Set the comment displayed next to the given node. The empty comment is removed.
+    pub fn set_node_comment(&self, id:node::Id, comment:Option<String>) -> FallibleResult<()> {
+        self.node_info(id)?;
+        let comment = comment.filter(|comment| !comment.trim().is_empty());
+        self.module.with_node_metadata(id,Box::new(|md| md.comment = comment));
+        Ok(())
+    }
+
+    /// Get the notes placed in this graph.
+    pub fn notes(&self) -> HashMap<NoteId,Note> {
+        let graph = self.id.to_string();
+        self.module.notes().into_iter().filter(|(_,note)| note.graph == graph).collect()
+    }
+
+    /// Get the note with given id.
+    pub fn note(&self, id:NoteId) -> FallibleResult<Note> {
+        self.notes().remove(&id).ok_or_else(|| NoteNotFound(id).into())
+    }
+
+    /// Place a new note in this graph. Returns the id of the added note.
+    pub fn add_note(&self, text:impl Str, position:Position) -> NoteId {
+        let id    = NoteId::new_v4();
+        let graph = self.id.to_string();
+        let text  = text.into();
+        info!(self.logger, "Adding note {id}.");
+        self.module.set_note(id,Note {graph,text,position});
+        id
+    }
+
+    /// Set the text of the note. The note whose text is empty is removed.
+    pub fn set_note_text(&self, id:NoteId, text:impl Str) -> FallibleResult<()> {
+        let text = text.into();
+        if text.trim().is_empty() {
+            self.remove_note(id)
+        } else {
+            let note = self.note(id)?;
+            self.module.set_note(id,Note {text,..note});
+            Ok(())
+        }
+    }
+
+    /// Move the note to the given position.
+    pub fn set_note_position(&self, id:NoteId, position:Position) -> FallibleResult<()> {
+        let note = self.note(id)?;
+        self.module.set_note(id,Note {position,..note});
+        Ok(())
+    }
+
+    /// Remove the note from this graph.
+    pub fn remove_note(&self, id:NoteId) -> FallibleResult<()> {
+        self.note(id)?;
+        info!(self.logger, "Removing note {id}.");
+        self.module.remove_note(id)?;
+        Ok(())
+    }
+
+    /// Get the node groups defined in this graph.
+    ///
+    /// The groups refer to their nodes by ids, so a node whose id was changed, e.g. when its line
+    /// was rewritten by an external edit of the module's file, is no longer listed in its group.
+    pub fn groups(&self) -> HashMap<GroupId,Group> {
+        let graph = self.id.to_string();
+        self.module.groups().into_iter().filter(|(_,group)| group.graph == graph).collect()
+    }
+
+    /// Get the node group with given id.
+    pub fn group(&self, id:GroupId) -> FallibleResult<Group> {
+        self.groups().remove(&id).ok_or_else(|| GroupNotFound(id).into())
+    }
+
+    /// Create a new group of the given nodes, displayed as a titled frame around them. Returns the
+    /// id of the added group.
+    ///
+    /// The groups get the subsequent colors from the palette, so the neighbouring groups are
+    /// likely to be distinguishable.
+    pub fn add_group
+    (&self, title:impl Str, nodes:impl IntoIterator<Item=node::Id>) -> FallibleResult<GroupId> {
+        let nodes : Vec<_> = nodes.into_iter().collect();
+        for node in &nodes {
+            self.node_info(*node)?;
+        }
+        let id        = GroupId::new_v4();
+        let graph     = self.id.to_string();
+        let title     = title.into();
+        let color     = self.module.groups().len();
+        let collapsed = false;
+        info!(self.logger, "Adding node group {id} of {nodes.len()} nodes.");
+        self.module.set_group(id,Group {graph,title,color,nodes,collapsed});
+        Ok(id)
+    }
+
+    /// Set the title displayed above the group.
+    pub fn set_group_title(&self, id:GroupId, title:impl Str) -> FallibleResult<()> {
+        let title = title.into();
+        let group = self.group(id)?;
+        self.module.set_group(id,Group {title,..group});
+        Ok(())
+    }
+
+    /// Collapse or expand the group. The nodes of collapsed group are not displayed, but the code
+    /// stays untouched.
+    pub fn set_group_collapsed(&self, id:GroupId, collapsed:bool) -> FallibleResult<()> {
+        let group = self.group(id)?;
+        self.module.set_group(id,Group {collapsed,..group});
+        Ok(())
+    }
+
+    /// Move all the nodes of the group by the given offset.
+    ///
+    /// The nodes with no position stored in metadata are not moved, as their position is chosen
+    /// by the view. The caller should store their displayed positions first.
+    pub fn move_group(&self, id:GroupId, offset:Vector2<f32>) -> FallibleResult<()> {
+        let group        = self.group(id)?;
+        let _transaction = self.module.open_transaction("Move node group");
+        for node in group.nodes {
+            let position = self.module.node_metadata(node).ok().and_then(|md| md.position);
+            if let Some(position) = position {
+                self.module.with_node_metadata(node,Box::new(|md| {
+                    md.position = Some(position + Position {vector:offset});
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the group. The nodes belonging to it are not affected.
+    pub fn remove_group(&self, id:GroupId) -> FallibleResult<()> {
+        self.group(id)?;
+        info!(self.logger, "Removing node group {id}.");
+        self.module.remove_group(id)?;
+        Ok(())
+    }
+
+    /// Update the groups of this graph after the `replaced` nodes were substituted with the
+    /// `replacement` ones, e.g. by collapsing or expanding nodes. The groups which are left with
+    /// no nodes are removed.
+    fn replace_nodes_in_groups(&self, replaced:&[node::Id], replacement:&[node::Id]) {
+        for (id,group) in self.groups() {
+            if group.nodes.iter().any(|node| replaced.contains(node)) {
+                let kept      = group.nodes.iter().filter(|node| !replaced.contains(node));
+                let mut nodes = kept.copied().collect_vec();
+                for node in replacement {
+                    if !nodes.contains(node) {
+                        nodes.push(*node)
+                    }
+                }
+                if nodes.is_empty() {
+                    let _ = self.module.remove_group(id);
+                } else {
+                    self.module.set_group(id,Group {nodes,..group});
+                }
+            }
+        }
+    }
+
     /// Updates the given node in the definition.
     ///
     /// The function `F` is called with the information with the state of the node so far and
@@ -804,9 +1137,10 @@ impl Handle {
     pub fn subscribe(&self) -> impl Stream<Item=Notification> {
         let module_sub = self.module.subscribe().map(|notification| {
             match notification {
-                model::module::Notification::Invalidate      |
-                model::module::Notification::CodeChanged{..} |
-                model::module::Notification::MetadataChanged => Notification::Invalidate,
+                model::module::Notification::Invalidate        |
+                model::module::Notification::CodeChanged{..}   |
+                model::module::Notification::MetadataChanged   => Notification::Invalidate,
+                model::module::Notification::ExternalChange(c) => Notification::ExternalChange(c),
             }
         });
         let db_sub = self.suggestion_db.subscribe().map(|notification| {
@@ -1057,6 +1391,7 @@ main =
             graph.module.set_node_metadata(id,NodeMetadata {
                 position        : None,
                 intended_method : entry.method_id(),
+                ..default()
             });
 
             let get_invocation_info = || {
@@ -1192,6 +1527,169 @@ main =
         })
     }
 
+    #[wasm_bindgen_test]
+    fn expanding_node() {
+        let mut test  = Fixture::set_up();
+        let code = r"
+foo a = a * 2
+
+main =
+    x = 5
+    y = here.foo x
+    print y";
+
+        let expected_code = "
+main =
+    x = 5
+    y = x * 2
+    print y";
+
+        test.data.code = code.to_owned();
+        test.run(move |graph| async move {
+            let expanded_node = graph.nodes().unwrap()[1].info.id();
+            let new_nodes     = graph.expand_node(expanded_node,true).unwrap();
+            model::module::test::expect_code(&*graph.module,expected_code);
+            assert_eq!(new_nodes, vec![expanded_node]);
+            assert!(graph.expand_node(expanded_node,true).is_err());
+        })
+    }
+
+    #[wasm_bindgen_test]
+    fn graph_controller_copy_and_paste_nodes() {
+        let mut test  = Fixture::set_up();
+        const PROGRAM:&str = r"
+main =
+    sum = 2 + 2
+    foo = sum + 3
+    print foo";
+        test.data.code = PROGRAM.into();
+        test.run(|graph| async move {
+            let nodes    = graph.nodes().unwrap();
+            let copied   = nodes[0..2].iter().map(|node| node.info.id());
+            let content  = graph.copy_nodes(copied).unwrap();
+            let position = Position::new(100.0,200.0);
+            let pasted   = graph.paste_nodes(&content,position).unwrap();
+            let expected_program = r"
+main =
+    sum = 2 + 2
+    foo = sum + 3
+    print foo
+    sum1 = 2 + 2
+    foo1 = sum1 + 3";
+            model::module::test::expect_code(&*graph.module,expected_program);
+            assert_eq!(pasted.len(),2);
+            let connections = connections(&graph).unwrap();
+            let connected   = connections.connections.iter().any(|connection| {
+                connection.source.node == pasted[0] && connection.destination.node == pasted[1]
+            });
+            assert!(connected);
+        })
+    }
+
+    #[wasm_bindgen_test]
+    fn graph_controller_arrange_nodes() {
+        let mut test  = Fixture::set_up();
+        const PROGRAM:&str = r"
+main =
+    foo = bar + 3
+    bar = 2
+    print foo";
+        test.data.code = PROGRAM.into();
+        test.run(|graph| async move {
+            let nodes = graph.nodes().unwrap();
+            let ids   = nodes.iter().map(|node| node.info.id()).collect_vec();
+            let start = Position::new(0.0,100.0);
+            graph.module.with_node_metadata(ids[0],Box::new(|md| md.position = Some(start)));
+            graph.arrange_nodes(ids.iter().copied()).unwrap();
+            let y_of  = |id| graph.module.node_metadata(id).unwrap().position.unwrap().vector.y;
+            let (foo,bar,print) = (y_of(ids[0]),y_of(ids[1]),y_of(ids[2]));
+            assert_eq!(bar,100.0);
+            assert!(foo < bar);
+            assert!(print < foo);
+            model::module::test::expect_code(&*graph.module,PROGRAM);
+        })
+    }
+
+    #[wasm_bindgen_test]
+    fn graph_controller_notes_and_comments() {
+        Fixture::set_up().run(|graph| async move {
+            let node_id  = graph.nodes().unwrap()[0].info.id();
+            let comment  = Some("The answer.".to_string());
+            graph.set_node_comment(node_id,comment.clone()).unwrap();
+            assert_eq!(graph.node(node_id).unwrap().metadata.unwrap().comment,comment);
+            graph.set_node_comment(node_id,Some("  ".into())).unwrap();
+            assert_eq!(graph.node(node_id).unwrap().metadata.unwrap().comment,None);
+            assert!(graph.set_node_comment(ast::Id::new_v4(),comment).is_err());
+
+            let position = Position::new(10.0,20.0);
+            let id       = graph.add_note("Remember to test.",position);
+            let note     = graph.note(id).unwrap();
+            assert_eq!(note.text,"Remember to test.");
+            assert_eq!(note.position,position);
+            assert_eq!(graph.notes().len(),1);
+
+            let other_graph = Note {graph:"other".into(),..default()};
+            graph.module.set_note(NoteId::new_v4(),other_graph);
+            assert_eq!(graph.notes().len(),1);
+
+            let new_position = Position::new(30.0,40.0);
+            graph.set_note_position(id,new_position).unwrap();
+            graph.set_note_text(id,"Tested.").unwrap();
+            let note = graph.note(id).unwrap();
+            assert_eq!(note.text,"Tested.");
+            assert_eq!(note.position,new_position);
+
+            graph.set_note_text(id,"").unwrap();
+            assert!(graph.note(id).is_err());
+            assert!(graph.remove_note(id).is_err());
+        })
+    }
+
+    #[wasm_bindgen_test]
+    fn graph_controller_node_groups() {
+        let mut test  = Fixture::set_up();
+        let code = r"
+foo a = a * 2
+
+main =
+    x = 5
+    y = here.foo x
+    print y";
+        test.data.code = code.to_owned();
+        test.run(|graph| async move {
+            let ids   = graph.nodes().unwrap().iter().map(|node| node.info.id()).collect_vec();
+            let start = Position::new(10.0,20.0);
+            graph.module.with_node_metadata(ids[0],Box::new(|md| md.position = Some(start)));
+            assert!(graph.add_group("load",vec![ast::Id::new_v4()]).is_err());
+            let id = graph.add_group("load",ids[0..2].iter().copied()).unwrap();
+            graph.set_group_title(id,"clean").unwrap();
+            graph.set_group_collapsed(id,true).unwrap();
+            let group = graph.group(id).unwrap();
+            assert_eq!(group.title,"clean");
+            assert_eq!(group.nodes,ids[0..2].to_vec());
+            assert!(group.collapsed);
+
+            graph.move_group(id,Vector2::new(5.0,5.0)).unwrap();
+            let position = graph.module.node_metadata(ids[0]).unwrap().position;
+            assert_eq!(position,Some(Position::new(15.0,25.0)));
+
+            let collapsed = graph.collapse(ids[0..2].iter().copied(),"func").unwrap();
+            assert_eq!(graph.group(id).unwrap().nodes,vec![collapsed]);
+            graph.remove_node(collapsed).unwrap();
+            assert!(graph.group(id).is_err());
+            // The node removal and the group update are undone together.
+            graph.module.undo().unwrap();
+            assert_eq!(graph.group(id).unwrap().nodes,vec![collapsed]);
+            graph.module.redo().unwrap();
+            assert!(graph.group(id).is_err());
+
+            let id = graph.add_group("report",vec![ids[2]]).unwrap();
+            graph.remove_group(id).unwrap();
+            assert!(graph.groups().is_empty());
+            assert!(graph.remove_group(id).is_err());
+        })
+    }
+
     #[wasm_bindgen_test]
     fn graph_controller_doubly_nested_definition() {
         // Tests editing nested definition that requires transforming inline expression into
@@ -1211,6 +1709,33 @@ main =
         })
     }
 
+    #[wasm_bindgen_test]
+    fn graph_controller_renaming_node_variable() {
+        let mut test  = Fixture::set_up();
+        const PROGRAM:&str = r"
+main =
+    foo = 2
+    bar = foo + 1
+    print foo";
+        test.data.code = PROGRAM.into();
+        test.run(|graph| async move {
+            let (node1,_,node3)    = graph.nodes().unwrap().expect_tuple();
+            let connections_before = connections(&graph).unwrap().connections.len();
+            graph.rename_node_variable(node1.info.id(),"baz").unwrap();
+            let expected_program = r"
+main =
+    baz = 2
+    bar = baz + 1
+    print baz";
+            model::module::test::expect_code(&*graph.module,expected_program);
+            assert_eq!(connections(&graph).unwrap().connections.len(), connections_before);
+
+            assert!(graph.rename_node_variable(node1.info.id(),"bar").is_err());
+            assert!(graph.rename_node_variable(node3.info.id(),"qux").is_err());
+            model::module::test::expect_code(&*graph.module,expected_program);
+        })
+    }
+
     #[wasm_bindgen_test]
     fn graph_controller_node_operations_node() {
         let mut test  = Fixture::set_up();
@@ -1375,6 +1900,31 @@ main =
         }
     }
 
+    #[wasm_bindgen_test]
+    fn graph_controller_set_port_value() {
+        let cases = &
+            [ ("foo a"    , &[2]   as &[usize], "5"     , "foo 5"    )
+            , ("foo a"    , &[2]              , "\"hi\"", "foo \"hi\"")
+            , ("1 + 2 + 3", &[0,1]            , "5"     , "5 + 2 + 3")
+            ];
+        for (expression,port,value,expected) in cases {
+            let mut test    = Fixture::set_up();
+            let main_prefix = "main = \n    ";
+            test.data.code  = format!("{}{}",main_prefix,expression);
+            let expected    = format!("{}{}",main_prefix,expected);
+            let port        = port.to_vec();
+            let value       = value.to_string();
+            test.run(|graph| async move {
+                let (node,)  = graph.nodes().unwrap().expect_tuple();
+                let endpoint = Endpoint::new(node.info.id(),port);
+                let context  = &span_tree::generate::context::Empty;
+                graph.set_port_value(&endpoint,value,context).unwrap();
+                let new_main = graph.graph_definition_info().unwrap().ast.repr();
+                assert_eq!(new_main,expected);
+            })
+        }
+    }
+
     #[wasm_bindgen_test]
     fn graph_controller_create_connection_reordering() {
         let mut test  = Fixture::set_up();
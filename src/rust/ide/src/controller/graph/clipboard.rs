@@ -0,0 +1,202 @@
+//! The description of the copied nodes, which is put into the system clipboard.
+//!
+//! The copied nodes are serialized to JSON, so they can be pasted into any graph, also in the other
+//! project.
+
+use crate::prelude::*;
+
+use crate::controller::graph::FailedToCreateNode;
+use crate::controller::graph::Node;
+use crate::double_representation::alias_analysis;
+use crate::double_representation::identifier::NormalizedName;
+use crate::double_representation::identifier::generate_name;
+use crate::double_representation::node::NodeInfo;
+use crate::model::module::MethodId;
+use crate::model::module::Position;
+use crate::model::module::QualifiedName;
+
+use parser::Parser;
+use serde::Deserialize;
+use serde::Serialize;
+
+
+
+// ==============
+// === Errors ===
+// ==============
+
+#[allow(missing_docs)]
+#[derive(Clone,Copy,Debug,Fail)]
+#[fail(display="No nodes were given to be copied.")]
+pub struct NoNodesToCopy;
+
+#[allow(missing_docs)]
+#[derive(Clone,Debug,Fail)]
+#[fail(display="The clipboard does not contain copied nodes: {}.",_0)]
+pub struct NotCopiedNodes(String);
+
+
+
+// ==================
+// === CopiedNode ===
+// ==================
+
+/// A single node stored in the clipboard.
+#[derive(Clone,Debug,Deserialize,PartialEq,Serialize)]
+pub struct CopiedNode {
+    /// The code of the whole node line, including its pattern, e.g. `sum = a + b`.
+    pub code : String,
+    /// The node position relative to the mean position of all copied nodes.
+    pub position : Option<Position>,
+    /// The method which is intended to be called by the node, see `NodeMetadata`.
+    pub intended_method : Option<MethodId>,
+    /// The comment attached to the node, see `NodeMetadata`.
+    #[serde(default)]
+    pub comment : Option<String>,
+}
+
+
+
+// ========================
+// === ClipboardContent ===
+// ========================
+
+/// The nodes copied to the clipboard.
+///
+/// The connections between the copied nodes are kept as the variable usages in the nodes' code.
+#[derive(Clone,Debug,Deserialize,PartialEq,Serialize)]
+pub struct ClipboardContent {
+    /// Copied nodes, in the order of their lines in the source graph.
+    #[serde(rename="ensoCopiedNodes")]
+    pub nodes : Vec<CopiedNode>,
+}
+
+impl ClipboardContent {
+    /// Describe the given nodes. The nodes should be given in the order of their lines in the
+    /// graph.
+    pub fn from_nodes(nodes:&[Node]) -> FallibleResult<Self> {
+        if nodes.is_empty() {
+            return Err(NoNodesToCopy.into());
+        }
+        let positions = nodes.iter().filter_map(|node| node.metadata.as_ref()?.position);
+        let mean      = Position::mean(positions);
+        let nodes     = nodes.iter().map(|node| {
+            let code            = node.info.ast().repr();
+            let metadata        = node.metadata.as_ref();
+            let position        = metadata.and_then(|md| md.position).map(|pos| pos - mean);
+            let intended_method = metadata.and_then(|md| md.intended_method.clone());
+            let comment         = metadata.and_then(|md| md.comment.clone());
+            CopiedNode {code,position,intended_method,comment}
+        }).collect();
+        Ok(ClipboardContent {nodes})
+    }
+
+    /// Serialize to the text which can be put into the clipboard.
+    pub fn to_json(&self) -> FallibleResult<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserialize from the clipboard text. Fails if the text does not describe copied nodes.
+    pub fn from_json(text:&str) -> FallibleResult<Self> {
+        serde_json::from_str(text).map_err(|err| NotCopiedNodes(err.to_string()).into())
+    }
+
+    /// The modules defining methods called by the copied nodes. They should be imported in the
+    /// module where the nodes are pasted.
+    pub fn required_modules(&self) -> impl Iterator<Item=&QualifiedName> {
+        let methods = self.nodes.iter().filter_map(|node| node.intended_method.as_ref());
+        methods.map(|method| &method.module).unique()
+    }
+
+    /// Parse the code of copied nodes, giving them fresh variable names that are not present in
+    /// `unavailable`.
+    ///
+    /// The usages of the renamed variables in the following copied nodes are renamed accordingly,
+    /// so the connections between the copied nodes are retained.
+    pub fn parse_with_fresh_names
+    (&self, parser:&Parser, unavailable:impl IntoIterator<Item=NormalizedName>)
+    -> FallibleResult<Vec<NodeInfo>> {
+        let mut unavailable = unavailable.into_iter().collect_vec();
+        let mut renamed     = HashMap::<NormalizedName,Ast>::new();
+        self.nodes.iter().map(|node| {
+            let ast   = parser.parse_line(&node.code)?;
+            let info  = NodeInfo::from_line_ast(&ast).ok_or(FailedToCreateNode)?;
+            let usage = alias_analysis::analyze_node(&info);
+            let mut ast = info.ast().clone_ref();
+            for used in usage.used {
+                if let Some(new_name) = renamed.get(&used.item) {
+                    ast = ast.set_traversing(&used.crumbs,new_name.clone_ref())?;
+                }
+            }
+            for introduced in usage.introduced {
+                let old_name = ast.get_traversing(&introduced.crumbs)?.repr();
+                let base     = old_name.trim_end_matches(|c:char| c.is_ascii_digit());
+                let new_name = generate_name(base,unavailable.iter().cloned())?;
+                unavailable.push(new_name.normalized());
+                let new_name = Ast::from(new_name.as_var()?);
+                ast = ast.set_traversing(&introduced.crumbs,new_name.clone_ref())?;
+                renamed.insert(introduced.item,new_name);
+            }
+            NodeInfo::from_line_ast(&ast).ok_or_else(|| FailedToCreateNode.into())
+        }).collect()
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::model::module::NodeMetadata;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn copied_node(code:&str) -> CopiedNode {
+        CopiedNode {code:code.to_owned(),position:None,intended_method:None,comment:None}
+    }
+
+    #[wasm_bindgen_test]
+    fn copying_nodes() {
+        let parser = Parser::new_or_panic();
+        let node   = |code:&str, position:Option<Position>, comment:Option<&str>| {
+            let ast      = parser.parse_line(code).unwrap();
+            let info     = NodeInfo::from_line_ast(&ast).unwrap();
+            let comment  = comment.map(ToString::to_string);
+            let metadata = Some(NodeMetadata {position,comment,..default()});
+            Node {info,metadata}
+        };
+        let nodes = vec!
+            [ node("a = 2 + 2"    , Some(Position::new(0.0,0.0))   , Some("Four"))
+            , node("b = a.print"  , Some(Position::new(10.0,20.0)) , None)
+            ];
+        let content = ClipboardContent::from_nodes(&nodes).unwrap();
+        assert_eq!(content.nodes[0].code, "a = 2 + 2");
+        assert_eq!(content.nodes[0].position, Some(Position::new(-5.0,-10.0)));
+        assert_eq!(content.nodes[0].comment, Some("Four".to_string()));
+        assert_eq!(content.nodes[1].code, "b = a.print");
+        assert_eq!(content.nodes[1].position, Some(Position::new(5.0,10.0)));
+        assert_eq!(content.nodes[1].comment, None);
+
+        let json = content.to_json().unwrap();
+        assert_eq!(ClipboardContent::from_json(&json).unwrap(), content);
+        assert!(ClipboardContent::from_json("a = 2 + 2").is_err());
+        assert!(ClipboardContent::from_nodes(&[]).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn pasting_with_fresh_names() {
+        let parser  = Parser::new_or_panic();
+        let nodes   = vec![copied_node("sum1 = 2 + 2"),copied_node("foo = sum1 + x"),
+            copied_node("sum1 + foo")];
+        let content = ClipboardContent {nodes};
+        let taken   = vec![NormalizedName::new("sum1"),NormalizedName::new("foo1")];
+        let pasted  = content.parse_with_fresh_names(&parser,taken).unwrap();
+        let code    = pasted.iter().map(|node| node.ast().repr()).collect_vec();
+        assert_eq!(code, vec!["sum2 = 2 + 2","foo2 = sum2 + x","sum2 + foo2"]);
+    }
+}
@@ -10,6 +10,8 @@ use crate::model::execution_context::LocalCall;
 use crate::model::execution_context::Visualization;
 use crate::model::execution_context::VisualizationId;
 use crate::model::execution_context::VisualizationUpdateData;
+use crate::model::module::MethodId;
+use crate::model::module::QualifiedName;
 
 use enso_protocol::language_server::MethodPointer;
 use span_tree::generate::context::Context;
@@ -17,6 +19,7 @@ use span_tree::generate::context::CalledMethodInfo;
 
 pub use crate::controller::graph::Connection;
 pub use crate::controller::graph::Connections;
+pub use crate::controller::graph::Endpoint;
 
 
 
@@ -54,6 +57,11 @@ pub enum Notification {
     EnteredNode(LocalCall),
     /// Notification emitted when the node was step out.
     SteppedOutOfNode(double_representation::node::Id),
+    /// The execution of the context failed with the given error message.
+    ExecutionFailed(String),
+    /// The previously reported execution failure is no longer valid, e.g. because the values were
+    /// successfully computed again.
+    ExecutionFailureCleared,
 }
 
 
@@ -138,15 +146,25 @@ impl Handle {
         self.execution_ctx.computed_value_info_registry()
     }
 
+    /// Get the error message if the last execution of the context failed.
+    pub fn execution_failure(&self) -> Option<String> {
+        self.execution_ctx.execution_status().failure()
+    }
+
     /// Subscribe to updates about changes in this executed graph.
     ///
     /// The stream of notification contains both notifications from the graph and from the execution
     /// context.
     pub fn subscribe(&self) -> impl Stream<Item=Notification> {
-        let registry     = self.execution_ctx.computed_value_info_registry();
-        let value_stream = registry.subscribe().map(Notification::ComputedValueInfo).boxed_local();
-        let graph_stream = self.graph().subscribe().map(Notification::Graph).boxed_local();
-        let self_stream  = self.notifier.subscribe().boxed_local();
+        let registry      = self.execution_ctx.computed_value_info_registry();
+        let value_stream  = registry.subscribe().map(Notification::ComputedValueInfo).boxed_local();
+        let graph_stream  = self.graph().subscribe().map(Notification::Graph).boxed_local();
+        let self_stream   = self.notifier.subscribe().boxed_local();
+        let status        = self.execution_ctx.execution_status().subscribe();
+        let status_stream = status.map(|failure| match failure {
+            Some(message) => Notification::ExecutionFailed(message),
+            None          => Notification::ExecutionFailureCleared,
+        }).boxed_local();
 
         // Note: [Argument Names-related invalidations]
         let db_stream = self.project.suggestion_db().subscribe().map(|notification| {
@@ -159,7 +177,8 @@ impl Handle {
             Notification::Graph(controller::graph::Notification::Invalidate)
         }).boxed_local();
 
-        let streams = vec![value_stream,graph_stream,self_stream,db_stream,update_stream];
+        let streams = vec!
+            [value_stream,graph_stream,self_stream,db_stream,update_stream,status_stream];
         futures::stream::select_all(streams)
     }
 
@@ -266,6 +285,45 @@ impl Handle {
     pub fn disconnect(&self, connection:&Connection) -> FallibleResult<()> {
         self.graph.borrow().disconnect(connection,self)
     }
+
+    /// Put the value given as code into the port, replacing its current expression.
+    pub fn set_port_value(&self, endpoint:&Endpoint, value:impl Str) -> FallibleResult<()> {
+        self.graph.borrow().set_port_value(endpoint,value,self)
+    }
+
+    /// Get the method called by the node, if it is a module method defined in the project, so it
+    /// can be renamed with `rename_node_method`.
+    ///
+    /// The method is read from the values computed by the engine, using the node's intended method
+    /// as a fallback.
+    pub fn node_project_method(&self, node:double_representation::node::Id) -> Option<MethodId> {
+        let computed = self.node_method_pointer(node).ok().and_then(|pointer| {
+            let module          = QualifiedName::try_from(pointer.deref()).ok()?;
+            let defined_on_type = pointer.defined_on_type.clone();
+            let name            = pointer.name.clone();
+            Some(MethodId {module,defined_on_type,name})
+        });
+        let intended = || {
+            let metadata = self.graph.borrow().module.node_metadata(node).ok()?;
+            metadata.intended_method
+        };
+        let method           = computed.or_else(intended)?;
+        let project_name     = self.project.name();
+        let is_in_project    = method.module.project_name().as_ref() == project_name.as_str();
+        let module_name      = method.module.to_string();
+        let on_type          = method.defined_on_type.as_str();
+        let is_module_method = on_type == module_name || on_type == method.module.name().as_ref();
+        (is_in_project && is_module_method).as_some(method)
+    }
+
+    /// Rename the project's module method called by the node, see `node_project_method`. The
+    /// method calls and the nodes intending it are updated in all the project's modules.
+    pub async fn rename_node_method
+    (&self, node:double_representation::node::Id, new_name:String) -> FallibleResult<()> {
+        let method = self.node_project_method(node).ok_or(NoResolvedMethod(node))?;
+        let path   = model::module::Path::from_name(self.project.content_root_id(),&method.module);
+        self.project.rename_method(path,method.name,new_name).await
+    }
 }
 
 
@@ -382,6 +440,30 @@ pub mod tests {
         notifications.expect_pending();
     }
 
+    // Test that checks that execution failures are relayed by the executed graph.
+    #[wasm_bindgen_test]
+    fn dispatching_execution_failure_notification() {
+        use crate::test::mock::Fixture;
+        let mut fixture = crate::test::mock::Unified::new().fixture();
+        let Fixture{executed_graph,execution,executor,..} = &mut fixture;
+
+        let mut notifications = executed_graph.subscribe().boxed_local();
+        notifications.expect_pending();
+        assert!(executed_graph.execution_failure().is_none());
+
+        let message = "Execution failed.".to_string();
+        execution.execution_status().set_failed(message.clone());
+        executor.run_until_stalled();
+        assert_eq!(notifications.expect_next(),Notification::ExecutionFailed(message.clone()));
+        assert_eq!(executed_graph.execution_failure(),Some(message));
+
+        execution.execution_status().clear();
+        executor.run_until_stalled();
+        assert_eq!(notifications.expect_next(),Notification::ExecutionFailureCleared);
+        assert!(executed_graph.execution_failure().is_none());
+        notifications.expect_pending();
+    }
+
     #[wasm_bindgen_test]
     fn span_tree_context() {
         use crate::test::mock;
@@ -401,6 +483,7 @@ pub mod tests {
         module.set_node_metadata(id,NodeMetadata {
             position        : None,
             intended_method : entry1.method_id(),
+            ..default()
         });
         let info = get_invocation_info().unwrap();
         assert_call_info(info,&entry1);
@@ -0,0 +1,345 @@
+//! Automatic layout of the graph nodes.
+//!
+//! The nodes are placed with a layered (Sugiyama-style) algorithm: every node is assigned to a
+//! layer, so the data flows from the upper layers to the lower ones. Then the nodes in each layer
+//! are reordered to reduce the number of crossing connections, and finally the layers are placed
+//! one below another, with their nodes spread horizontally around the common center.
+
+use crate::prelude::*;
+
+use crate::controller::graph::Connection;
+use crate::controller::graph::Node;
+use crate::double_representation::node;
+use crate::model::module::Position;
+
+use ide_view::graph_editor::component::node as node_view;
+use std::cmp::Ordering;
+
+
+
+// =================
+// === Node Size ===
+// =================
+
+/// The width of a single character of node's expression.
+pub const GLYPH_WIDTH : f32 = node_view::GLYPH_WIDTH;
+/// The horizontal padding on each side of node's expression.
+pub const NODE_PADDING : f32 = node_view::NODE_SHAPE_PADDING;
+/// The height of a single node.
+pub const NODE_HEIGHT : f32 = node_view::NODE_HEIGHT;
+
+/// Estimate the size of the node displaying the given expression.
+pub fn estimated_node_size(expression:&str) -> Vector2<f32> {
+    let width = expression.chars().count() as f32 * GLYPH_WIDTH + 2.0 * NODE_PADDING;
+    Vector2::new(width,NODE_HEIGHT)
+}
+
+
+
+// ==============
+// === Config ===
+// ==============
+
+/// The number of sweeps through the layers done when reordering the nodes.
+const ORDERING_SWEEPS : usize = 4;
+
+/// The distances kept between the laid out nodes.
+#[derive(Clone,Copy,Debug)]
+pub struct Config {
+    /// The vertical gap between two subsequent layers.
+    pub layer_gap : f32,
+    /// The horizontal gap between two neighbouring nodes in the same layer.
+    pub node_gap : f32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {layer_gap:40.0, node_gap:40.0}
+    }
+}
+
+
+
+// =============
+// === Graph ===
+// =============
+
+/// The nodes with their sizes and the data-flow connections between them, ready to be laid out.
+///
+/// The nodes are identified by their index in the order given in constructor, which is the order
+/// of node lines in the code.
+#[derive(Clone,Debug,Default)]
+pub struct Graph {
+    ids          : Vec<node::Id>,
+    sizes        : Vec<Vector2<f32>>,
+    predecessors : Vec<Vec<usize>>,
+    successors   : Vec<Vec<usize>>,
+}
+
+impl Graph {
+    /// Create a graph of nodes with given sizes. The edges lead from the node producing a value to
+    /// the node using it. The edges referring to nodes absent in `nodes` are ignored.
+    pub fn new
+    ( nodes : impl IntoIterator<Item=(node::Id,Vector2<f32>)>
+    , edges : impl IntoIterator<Item=(node::Id,node::Id)>
+    ) -> Self {
+        let (ids,sizes) : (Vec<_>,Vec<_>) = nodes.into_iter().unzip();
+        let index_of = ids.iter().enumerate().map(|(i,id)| (*id,i)).collect::<HashMap<_,_>>();
+        let mut predecessors = vec![Vec::new();ids.len()];
+        let mut successors   = vec![Vec::new();ids.len()];
+        for (source,target) in edges {
+            if let (Some(&source),Some(&target)) = (index_of.get(&source),index_of.get(&target)) {
+                if source != target && !successors[source].contains(&target) {
+                    successors[source].push(target);
+                    predecessors[target].push(source);
+                }
+            }
+        }
+        Graph {ids,sizes,predecessors,successors}
+    }
+
+    /// Create a graph of given controller nodes, with sizes estimated from their expressions.
+    pub fn from_nodes(nodes:&[Node], connections:&[Connection]) -> Self {
+        let nodes = nodes.iter().map(|node| {
+            (node.info.id(),estimated_node_size(&node.info.expression().repr()))
+        });
+        let edges = connections.iter().map(|c| (c.source.node,c.destination.node));
+        Self::new(nodes,edges)
+    }
+
+    /// Check if there are no nodes to lay out.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Compute the positions of all nodes. The top of the first layer is at `y = 0`, and every
+    /// layer is horizontally centered at `x = 0`.
+    pub fn layout(&self, config:Config) -> HashMap<node::Id,Position> {
+        let mut positions = HashMap::new();
+        let mut top       = 0.0;
+        for layer in self.ordered_layers() {
+            let nodes_width = layer.iter().map(|index| self.sizes[*index].x).sum::<f32>();
+            let gaps_width  = config.node_gap * layer.len().saturating_sub(1) as f32;
+            let mut left    = -(nodes_width + gaps_width) / 2.0;
+            let mut height  = 0.0_f32;
+            for index in layer {
+                let size = self.sizes[index];
+                positions.insert(self.ids[index],Position::new(left,top));
+                left   += size.x + config.node_gap;
+                height  = height.max(size.y);
+            }
+            top -= height + config.layer_gap;
+        }
+        positions
+    }
+
+    /// Compute the positions of all nodes, placing them so the top of the first layer is at
+    /// `origin.y` and the layers are centered at `origin.x`.
+    pub fn layout_at(&self, config:Config, origin:Position) -> HashMap<node::Id,Position> {
+        let positions = self.layout(config).into_iter();
+        positions.map(|(id,position)| (id,position + origin)).collect()
+    }
+
+    /// Split the nodes into layers and order nodes in each layer to reduce the edge crossings.
+    fn ordered_layers(&self) -> Vec<Vec<usize>> {
+        let layer_of   = self.layer_indices();
+        let count      = layer_of.iter().max().map_or(0,|max| max + 1);
+        let mut layers = vec![Vec::new();count];
+        for (index,layer) in layer_of.iter().enumerate() {
+            layers[*layer].push(index);
+        }
+        for sweep in 0..ORDERING_SWEEPS {
+            if sweep % 2 == 0 {
+                for layer in 1..count {
+                    let (fixed,reordered) = layers.split_at_mut(layer);
+                    Self::reorder(&mut reordered[0],&fixed[layer-1],&self.predecessors);
+                }
+            } else {
+                for layer in (0..count.saturating_sub(1)).rev() {
+                    let (reordered,fixed) = layers.split_at_mut(layer + 1);
+                    Self::reorder(&mut reordered[layer],&fixed[0],&self.successors);
+                }
+            }
+        }
+        layers
+    }
+
+    /// Sort the `layer` by barycenters: mean positions of each node's neighbours in the `fixed`
+    /// layer. The nodes without neighbours there keep their current position.
+    fn reorder(layer:&mut Vec<usize>, fixed:&[usize], neighbours:&[Vec<usize>]) {
+        let fixed_position = fixed.iter().enumerate().map(|(i,index)| (*index,i as f32));
+        let fixed_position = fixed_position.collect::<HashMap<_,_>>();
+        let barycenters    = layer.iter().enumerate().map(|(current,index)| {
+            let positions  = neighbours[*index].iter().filter_map(|n| fixed_position.get(n));
+            let positions  = positions.collect_vec();
+            let barycenter = if positions.is_empty() { current as f32 } else {
+                positions.iter().copied().sum::<f32>() / positions.len() as f32
+            };
+            (barycenter,*index)
+        });
+        let mut barycenters = barycenters.collect_vec();
+        barycenters.sort_by(|lhs,rhs| lhs.0.partial_cmp(&rhs.0).unwrap_or(Ordering::Equal));
+        *layer = barycenters.into_iter().map(|(_,index)| index).collect();
+    }
+
+    /// Assign each node to the layer one below its lowest predecessor (the longest path layering).
+    ///
+    /// The valid graph has no cycles, but they may appear in the code being edited. The cycles are
+    /// broken by ignoring the edges leading to nodes whose layer is currently being computed.
+    fn layer_indices(&self) -> Vec<usize> {
+        let mut layers   = vec![None;self.ids.len()];
+        let mut visiting = vec![false;self.ids.len()];
+        for index in 0..self.ids.len() {
+            self.assign_layer(index,&mut layers,&mut visiting);
+        }
+        layers.into_iter().map(|layer| layer.unwrap_or_default()).collect()
+    }
+
+    fn assign_layer
+    (&self, index:usize, layers:&mut Vec<Option<usize>>, visiting:&mut Vec<bool>) -> usize {
+        if let Some(layer) = layers[index] {
+            layer
+        } else {
+            visiting[index] = true;
+            let mut layer   = 0;
+            for &predecessor in &self.predecessors[index] {
+                if !visiting[predecessor] {
+                    let predecessor_layer = self.assign_layer(predecessor,layers,visiting);
+                    layer = layer.max(predecessor_layer + 1);
+                }
+            }
+            visiting[index] = false;
+            layers[index]   = Some(layer);
+            layer
+        }
+    }
+}
+
+
+
+// ==========================
+// === Unpositioned Nodes ===
+// ==========================
+
+/// Compute the positions of the nodes which have no position stored in their metadata.
+///
+/// The nodes are laid out together, below all the positioned nodes. If there are no positioned
+/// nodes, the top of the layout is at `default_origin`.
+pub fn positions_of_unpositioned_nodes
+(nodes:&[Node], connections:&[Connection], config:Config, default_origin:Position)
+-> HashMap<node::Id,Position> {
+    let has_position = |node:&&Node| node.metadata.as_ref().and_then(|md| md.position).is_some();
+    let (positioned,unpositioned) : (Vec<_>,Vec<_>) = nodes.iter().partition(has_position);
+    let unpositioned = unpositioned.into_iter().cloned().collect_vec();
+    let graph        = Graph::from_nodes(&unpositioned,connections);
+    let positions    = positioned.iter().filter_map(|node| node.metadata.as_ref()?.position);
+    let bottom       = positions.map(|position| position.vector.y).fold(None,|lowest,y| {
+        Some(lowest.map_or(y,|lowest:f32| lowest.min(y)))
+    });
+    let below  = |bottom:f32| bottom - NODE_HEIGHT - config.layer_gap;
+    let origin = match bottom {
+        Some(bottom) => Position::new(default_origin.vector.x,below(bottom)),
+        None         => default_origin,
+    };
+    graph.layout_at(config,origin)
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn size(width:f32) -> Vector2<f32> {
+        Vector2::new(width,NODE_HEIGHT)
+    }
+
+    fn ids(count:usize) -> Vec<node::Id> {
+        (0..count).map(|_| node::Id::new_v4()).collect()
+    }
+
+    #[test]
+    fn estimating_node_size() {
+        assert_eq!(estimated_node_size("").x,2.0 * NODE_PADDING);
+        assert_eq!(estimated_node_size("ab").x,2.0 * GLYPH_WIDTH + 2.0 * NODE_PADDING);
+        assert_eq!(estimated_node_size("ab").y,NODE_HEIGHT);
+    }
+
+    #[test]
+    fn layering_follows_data_flow() {
+        let ids       = ids(4);
+        let (a,b,c,d) = (ids[0],ids[1],ids[2],ids[3]);
+        // The code order is different than the data flow: `c` uses `d`, which uses `b`.
+        let nodes     = ids.iter().map(|id| (*id,size(100.0)));
+        let edges     = vec![(a,b),(b,d),(d,c),(a,c)];
+        let graph     = Graph::new(nodes,edges);
+        let layout    = graph.layout(default());
+        assert_eq!(layout.len(),4);
+        assert_eq!(layout[&a].vector.y,0.0);
+        assert!(layout[&b].vector.y < layout[&a].vector.y);
+        assert!(layout[&d].vector.y < layout[&b].vector.y);
+        assert!(layout[&c].vector.y < layout[&d].vector.y);
+        assert_eq!(layout[&a].vector.x,-50.0);
+    }
+
+    #[test]
+    fn nodes_in_layer_do_not_overlap() {
+        let ids       = ids(4);
+        let widths    = [100.0,200.0,50.0,80.0];
+        let config    = Config::default();
+        let nodes     = ids.iter().zip(&widths).map(|(id,width)| (*id,size(*width)));
+        let graph     = Graph::new(nodes,vec![]);
+        let layout    = graph.layout(config);
+        let mut lefts = ids.iter().zip(&widths).map(|(id,width)| {
+            (layout[id].vector.x,*width)
+        }).collect_vec();
+        lefts.sort_by(|lhs,rhs| lhs.0.partial_cmp(&rhs.0).unwrap());
+        for pair in lefts.windows(2) {
+            assert!(pair[0].0 + pair[0].1 + config.node_gap <= pair[1].0 + 0.001);
+        }
+        let total_width = 430.0 + 3.0 * config.node_gap;
+        assert_eq!(lefts[0].0,-total_width / 2.0);
+        assert!(ids.iter().all(|id| layout[id].vector.y == 0.0));
+    }
+
+    #[test]
+    fn reordering_reduces_crossings() {
+        let ids       = ids(4);
+        let (a,b,c,d) = (ids[0],ids[1],ids[2],ids[3]);
+        let nodes     = ids.iter().map(|id| (*id,size(100.0)));
+        let graph     = Graph::new(nodes,vec![(a,d),(b,c)]);
+        let layout    = graph.layout(default());
+        assert!(layout[&a].vector.x < layout[&b].vector.x);
+        assert!(layout[&d].vector.x < layout[&c].vector.x);
+    }
+
+    #[test]
+    fn cycles_and_unknown_nodes_are_handled() {
+        let ids     = ids(3);
+        let unknown = node::Id::new_v4();
+        let (a,b,c) = (ids[0],ids[1],ids[2]);
+        let nodes   = ids.iter().map(|id| (*id,size(100.0)));
+        let graph   = Graph::new(nodes,vec![(a,b),(b,c),(c,a),(unknown,a),(b,b)]);
+        let layout  = graph.layout(default());
+        assert_eq!(layout.len(),3);
+        assert!(!layout.contains_key(&unknown));
+        let mut ys = ids.iter().map(|id| layout[id].vector.y).collect_vec();
+        ys.sort_by(|lhs,rhs| lhs.partial_cmp(rhs).unwrap());
+        ys.dedup();
+        assert_eq!(ys.len(),3);
+    }
+
+    #[test]
+    fn layout_at_origin() {
+        let ids    = ids(1);
+        let graph  = Graph::new(vec![(ids[0],size(100.0))],vec![]);
+        let layout = graph.layout_at(default(),Position::new(10.0,20.0));
+        assert_eq!(layout[&ids[0]],Position::new(-40.0,20.0));
+        assert!(Graph::default().is_empty());
+        assert!(Graph::default().layout(default()).is_empty());
+    }
+}
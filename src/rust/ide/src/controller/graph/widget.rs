@@ -0,0 +1,193 @@
+//! Value-editing widgets of the node input ports.
+//!
+//! The port gets a widget when it is an argument of known type given as a literal: numbers are
+//! edited by dragging, texts in a text field, booleans with a checkbox, and the values of
+//! atom-based types are picked from a dropdown filled with the atoms found in the Suggestion
+//! Database. The arguments being connection targets or other expressions get no widget.
+
+use crate::prelude::*;
+
+use crate::model::SuggestionDatabase;
+
+use span_tree::SpanTree;
+use span_tree::node;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The names of the types edited with the numeric widget.
+pub const NUMBER_TYPES : &[&str] = &["Number","Integer","Decimal"];
+/// The name of the type edited with the text field.
+pub const TEXT_TYPE : &str = "Text";
+/// The name of the type edited with the checkbox.
+pub const BOOLEAN_TYPE : &str = "Boolean";
+
+
+
+// ============
+// === Kind ===
+// ============
+
+/// The kind of widget displayed on the port.
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub enum Kind {
+    /// The number changed by dragging.
+    Number,
+    /// The text field.
+    Text,
+    /// The checkbox toggling between `True` and `False`.
+    Checkbox,
+    /// The list of values to choose from, each given as a code to put into the port.
+    Dropdown {
+        #[allow(missing_docs)]
+        entries : Vec<String>
+    },
+}
+
+impl Kind {
+    /// Get the kind of widget editing the values of the given type. Returns `None` if there is no
+    /// widget suitable for this type.
+    pub fn for_type(typename:&str, database:&SuggestionDatabase) -> Option<Self> {
+        let name = typename.rsplit('.').next().unwrap_or(typename);
+        if NUMBER_TYPES.contains(&name) {
+            Some(Kind::Number)
+        } else if name == TEXT_TYPE {
+            Some(Kind::Text)
+        } else if name == BOOLEAN_TYPE {
+            Some(Kind::Checkbox)
+        } else {
+            let atoms   = database.lookup_atoms_of_type(typename);
+            let entries = atoms.iter().map(|atom| atom.code_to_insert(None)).collect_vec();
+            (!entries.is_empty()).as_some(Kind::Dropdown {entries})
+        }
+    }
+}
+
+
+
+// ==============
+// === Widget ===
+// ==============
+
+/// The widget of a single port.
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct Widget {
+    /// The crumbs of the port in the node's input span tree.
+    pub port : span_tree::Crumbs,
+    /// The kind of the widget.
+    pub kind : Kind,
+}
+
+/// Check if the AST is a literal value which may be edited with a widget: a number, a text or
+/// a constructor name, like `True` or an atom.
+pub fn is_literal(ast:&Ast) -> bool {
+    use ast::Shape;
+    matches!(ast.shape()
+        , Shape::Number(_) | Shape::Cons(_) | Shape::TextLineRaw(_) | Shape::TextLineFmt(_)
+        | Shape::TextBlockRaw(_) | Shape::TextBlockFmt(_))
+}
+
+/// Get the widgets of the arguments in the given span tree of node's inputs. The `expression` is
+/// the node's expression the span tree was generated from.
+///
+/// Only the arguments with known type get widgets, so the tree should be generated with the
+/// context providing the information about called methods. The arguments which are not literals,
+/// including the variables being connection targets, get no widget.
+pub fn widgets(span_tree:&SpanTree, expression:&Ast, database:&SuggestionDatabase) -> Vec<Widget> {
+    let mut widgets  = Vec::new();
+    let mut to_visit = vec![span_tree.root_ref()];
+    while let Some(node) = to_visit.pop() {
+        let argument    = expression.get_traversing(&node.ast_crumbs).ok();
+        let is_literal  = argument.map_or(false,is_literal);
+        let is_argument = matches!(node.kind, node::Kind::Argument{..}) && is_literal;
+        let typename    = node.parameter_info.as_ref().and_then(|info| info.typename.as_ref());
+        let kind        = typename.filter(|_| is_argument).and_then(|typename| {
+            Kind::for_type(typename,database)
+        });
+        match kind {
+            Some(kind) => widgets.push(Widget {port:node.crumbs.clone(),kind}),
+            None       => to_visit.extend(node.children_iter()),
+        }
+    }
+    widgets.sort_by(|a,b| a.port.cmp(&b.port));
+    widgets
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::model::suggestion_database::Entry;
+    use crate::model::suggestion_database::EntryKind;
+    use crate::model::suggestion_database::Scope;
+
+    use ast::crumbs::InfixCrumb;
+    use span_tree::ParameterInfo;
+    use span_tree::builder::Builder;
+    use span_tree::builder::TreeBuilder;
+    use span_tree::node::Kind::*;
+
+    fn database() -> SuggestionDatabase {
+        let database = SuggestionDatabase::new_empty(Logger::new("test"));
+        let atom     = |name:&str| Entry {
+            name          : name.to_string(),
+            kind          : EntryKind::Atom,
+            module        : crate::test::mock::data::module_qualified_name(),
+            arguments     : vec![],
+            return_type   : "Color".to_string(),
+            documentation : None,
+            self_type     : None,
+            scope         : Scope::Everywhere,
+        };
+        database.put_entry(1,atom("Red"));
+        database.put_entry(2,atom("Green"));
+        database
+    }
+
+    fn parameter(typename:&str) -> Option<ParameterInfo> {
+        let name     = None;
+        let typename = Some(typename.to_string());
+        Some(ParameterInfo {name,typename})
+    }
+
+    #[test]
+    fn widget_kinds_for_types() {
+        let database = database();
+        let dropdown = Kind::Dropdown {entries:vec!["Red".to_string(),"Green".to_string()]};
+        assert_eq!(Kind::for_type("Number",&database)           , Some(Kind::Number));
+        assert_eq!(Kind::for_type("Base.Data.Integer",&database), Some(Kind::Number));
+        assert_eq!(Kind::for_type("Text",&database)             , Some(Kind::Text));
+        assert_eq!(Kind::for_type("Boolean",&database)          , Some(Kind::Checkbox));
+        assert_eq!(Kind::for_type("Color",&database)            , Some(dropdown));
+        assert_eq!(Kind::for_type("Any",&database)              , None);
+    }
+
+    #[test]
+    fn widgets_of_arguments() {
+        let is_removable = false;
+        let mut tree: SpanTree = TreeBuilder::new(5)
+            .add_leaf(0,1,Target{is_removable},InfixCrumb::LeftOperand)
+            .add_leaf(2,1,Operation,InfixCrumb::Operator)
+            .add_leaf(4,1,Argument{is_removable},InfixCrumb::RightOperand)
+            .build();
+        let literal    = Ast::infix(Ast::var("a"),"+",Ast::number(2));
+        let connection = Ast::infix_var("a","+","b");
+        tree.root.children[0].node.parameter_info = parameter("Text");
+        tree.root.children[2].node.parameter_info = parameter("Any");
+        assert!(widgets(&tree,&literal,&database()).is_empty());
+
+        tree.root.children[2].node.parameter_info = parameter("Number");
+        let expected = vec![Widget {port:vec![2], kind:Kind::Number}];
+        assert_eq!(widgets(&tree,&literal,&database()),expected);
+        assert!(widgets(&tree,&connection,&database()).is_empty());
+    }
+}
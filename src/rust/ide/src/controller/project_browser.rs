@@ -0,0 +1,158 @@
+//! Project Browser Controller.
+//!
+//! Lists the user's projects and the available samples, and creates, opens, closes and deletes
+//! projects using the Project Manager service.
+
+use crate::prelude::*;
+
+use crate::ide::IdeInitializer;
+
+use enso_protocol::project_manager;
+use enso_protocol::project_manager::ProjectMetadata;
+use enso_protocol::project_manager::ProjectName;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The maximum number of samples listed in the project browser.
+pub const MAX_SAMPLES_COUNT:u32 = 20;
+
+
+
+// =======================
+// === Project Browser ===
+// =======================
+
+/// A Handle for Project Browser Controller.
+#[allow(missing_docs)]
+#[derive(Clone,CloneRef,Derivative)]
+#[derivative(Debug)]
+pub struct Handle {
+    pub logger          : Logger,
+    #[derivative(Debug = "ignore")]
+    pub project_manager : Rc<dyn project_manager::API>,
+}
+
+impl Handle {
+    /// Create a project browser controller using given Project Manager.
+    pub fn new(parent:impl AnyLogger, project_manager:Rc<dyn project_manager::API>) -> Self {
+        let logger = Logger::sub(parent,"Project Browser Controller");
+        Handle {logger,project_manager}
+    }
+
+    /// List all user's projects, the most recently opened first.
+    pub async fn recent_projects(&self) -> FallibleResult<Vec<ProjectMetadata>> {
+        Ok(self.project_manager.list_projects(&None).await?.projects)
+    }
+
+    /// List the sample projects which can be used to create new projects.
+    pub async fn samples(&self) -> FallibleResult<Vec<ProjectMetadata>> {
+        Ok(self.project_manager.list_samples(&MAX_SAMPLES_COUNT).await?.projects)
+    }
+
+    /// Create a new, empty project with given name.
+    pub async fn create_project(&self, name:&str) -> FallibleResult<ProjectMetadata> {
+        info!(self.logger,"Creating a new project named '{name}'.");
+        self.create_project_from_template(name.to_owned(),None).await
+    }
+
+    /// Create a new project being a copy of the given sample. The project is named after the
+    /// sample, with a numeric suffix if a project with such name already exists.
+    pub async fn create_project_from_sample
+    (&self, sample:&ProjectMetadata) -> FallibleResult<ProjectMetadata> {
+        let existing = self.recent_projects().await?;
+        let taken    = existing.iter().map(|project| project.name.as_str());
+        let name     = unique_project_name(sample.name.as_str(),taken);
+        info!(self.logger,"Creating a new project named '{name}' from sample '{sample.name}'.");
+        self.create_project_from_template(name,Some(sample.name.to_string())).await
+    }
+
+    /// Create a new project, being a copy of the sample named `template` if given.
+    async fn create_project_from_template
+    (&self, name:String, template:Option<String>) -> FallibleResult<ProjectMetadata> {
+        let response    = self.project_manager.create_project(&name,&template).await?;
+        let id          = response.project_id;
+        let name        = ProjectName::new(name);
+        let last_opened = default();
+        Ok(ProjectMetadata {name,id,last_opened})
+    }
+
+    /// Open the project, connecting to its Language Server.
+    pub async fn open_project(&self, project:ProjectMetadata) -> FallibleResult<model::Project> {
+        let project_manager = self.project_manager.clone_ref();
+        IdeInitializer::open_project(&self.logger,project_manager,project).await
+    }
+
+    /// Close the project with given id. The project's model should be dropped before, as closing
+    /// shuts its Language Server down.
+    pub async fn close_project(&self, id:Uuid) -> FallibleResult<()> {
+        info!(self.logger,"Closing the project {id}.");
+        Ok(self.project_manager.close_project(&id).await?)
+    }
+
+    /// Delete the project with given id. The project must not be opened.
+    pub async fn delete_project(&self, id:Uuid) -> FallibleResult<()> {
+        info!(self.logger,"Deleting the project {id}.");
+        Ok(self.project_manager.delete_project(&id).await?)
+    }
+}
+
+/// Get the project name based on `base` which is not in `taken`, by appending the first free
+/// numeric suffix if needed.
+fn unique_project_name<'a>(base:&str, taken:impl Iterator<Item=&'a str>) -> String {
+    let taken = taken.collect::<HashSet<_>>();
+    let names = std::iter::once(base.to_owned());
+    let names = names.chain((1..).map(|index| iformat!("{base}_{index}")));
+    // The set of taken names is finite, so a free name is eventually found.
+    names.find(|name| !taken.contains(name.as_str())).unwrap()
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use json_rpc::expect_call;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn project(name:&str) -> ProjectMetadata {
+        let name        = ProjectName::new(name);
+        let id          = Uuid::new_v4();
+        let last_opened = default();
+        ProjectMetadata {name,id,last_opened}
+    }
+
+    #[wasm_bindgen_test]
+    fn generating_unique_project_names() {
+        let taken = ["Sample","Sample_1","Other"];
+        assert_eq!(unique_project_name("New",taken.iter().cloned()),"New");
+        assert_eq!(unique_project_name("Sample",taken.iter().cloned()),"Sample_2");
+    }
+
+    #[wasm_bindgen_test(async)]
+    async fn creating_project_from_sample() {
+        let mock_client = project_manager::MockClient::default();
+        let sample      = project("Sample");
+        let projects    = vec![project("Sample"),project("Other")];
+        let project_id  = Uuid::new_v4();
+        let created     = project_manager::response::CreateProject {project_id};
+        let template    = Some("Sample".to_string());
+        expect_call!(mock_client.list_projects(number_of_projects=None)
+            => Ok(project_manager::response::ProjectList {projects}));
+        expect_call!(mock_client.create_project(name="Sample_1".to_string(),
+            project_template=template) => Ok(created));
+
+        let controller = Handle::new(Logger::default(),Rc::new(mock_client));
+        let project    = controller.create_project_from_sample(&sample).await.unwrap();
+        assert_eq!(project.id,project_id);
+        assert_eq!(project.name,ProjectName::new("Sample_1"));
+    }
+}
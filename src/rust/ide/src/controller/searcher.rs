@@ -1,4 +1,5 @@
 //! This module contains all structures related to Searcher Controller.
+pub mod browse;
 pub mod suggestion;
 
 use crate::prelude::*;
@@ -15,6 +16,7 @@ use data::text::TextLocation;
 use enso_protocol::language_server;
 use flo_stream::Subscriber;
 use parser::Parser;
+use span_tree::SpanTree;
 
 pub use suggestion::Suggestion;
 
@@ -32,6 +34,20 @@ pub struct NoSuchSuggestion{
     index : usize,
 }
 
+#[allow(missing_docs)]
+#[fail(display = "The suggestion with index {} is not a completion.", index)]
+#[derive(Copy,Clone,Debug,Fail)]
+pub struct NotACompletion{
+    index : usize,
+}
+
+#[allow(missing_docs)]
+#[fail(display = "The suggestion with index {} is not a category.", index)]
+#[derive(Copy,Clone,Debug,Fail)]
+pub struct NotACategory{
+    index : usize,
+}
+
 
 // =====================
 // === Notifications ===
@@ -216,6 +232,9 @@ impl Display for ParsedInput {
 pub struct ThisNode {
     /// Identifier of the node that will be connected if the initial suggestion is picked.
     pub id:double_representation::node::Id,
+    /// Identifier of the expression whose type is the `this` argument's type: the node itself, or
+    /// the variable in the node's pattern.
+    pub expression:ast::Id,
     /// Name of the variable that will be used to connect with the selected node.
     pub var:String,
     /// If the pattern with variable needs to be introduced on the node.
@@ -243,7 +262,27 @@ impl ThisNode {
         } else {
             (graph.variable_name_for(&node.info).ok()?.repr(),true)
         };
-        Some(ThisNode {id,var,needs_to_introduce_pattern})
+        let expression = id;
+        Some(ThisNode {id,expression,var,needs_to_introduce_pattern})
+    }
+
+    /// Retrieve information about the node whose output port is the given connection's source.
+    ///
+    /// If the port is a part of the node's pattern, like `x` in `Point x y = get_pos`, its variable
+    /// is used. Returns `None` if the port is not a single variable.
+    pub fn from_source(source:&controller::graph::Endpoint, graph:&controller::Graph)
+    -> Option<Self> {
+        if source.port.is_empty() && source.var_crumbs.is_empty() {
+            return Self::new(vec![source.node],graph)
+        }
+        let node       = graph.node_info(source.node).ok()?;
+        let pattern    = node.pattern()?;
+        let info       = controller::graph::EndpointInfo::new(source,pattern,graph).ok()?;
+        let var_ast    = info.target_ast().ok()?;
+        let var        = ast::identifier::as_var(var_ast)?.to_owned();
+        let id         = source.node;
+        let expression = var_ast.id?;
+        Some(ThisNode {id,expression,var,needs_to_introduce_pattern:false})
     }
 
     /// Introduce a pattern with variable on the node serving as provider of "this" argument.
@@ -264,13 +303,17 @@ impl ThisNode {
 // ===========================
 
 /// Describes how Searcher was brought to screen and how should behave when committing expression.
-#[derive(Copy,Clone,Debug)]
+#[derive(Clone,Debug)]
 #[allow(missing_docs)]
 pub enum Mode {
     /// Searcher should add a new node at given position.
     NewNode {position:Option<Position>},
     /// Searcher should edit existing node's expression.
     EditNode {node_id:ast::Id},
+    /// Searcher should add a new node at given position, inserting it into the connection: the
+    /// connection's source is used as `this` argument, and the connection's destination is
+    /// connected to the new node's output.
+    InsertIntoConnection {connection:controller::graph::Connection, position:Option<Position>},
 }
 
 /// A fragment filled by single picked completion suggestion.
@@ -357,6 +400,8 @@ pub struct Searcher {
     this_arg         : Rc<Option<ThisNode>>,
     position_in_code : Immutable<TextLocation>,
     project_name     : ImString,
+    actions          : Rc<RefCell<Vec<suggestion::Action>>>,
+    browse_path      : Rc<RefCell<Vec<ImString>>>,
 }
 
 impl Searcher {
@@ -391,7 +436,12 @@ impl Searcher {
         let def_id     = graph.graph().id;
         let def_span   = double_representation::module::definition_span(&module_ast,&def_id)?;
         let position   = TextLocation::convert_span(module_ast.repr(),&def_span).end;
-        let this_arg   = Rc::new(matches!(mode, Mode::NewNode{..}).and_option_from(|| ThisNode::new(selected_nodes,&graph.graph())));
+        let this_arg   = Rc::new(match &mode {
+            Mode::NewNode {..} => ThisNode::new(selected_nodes,&graph.graph()),
+            Mode::InsertIntoConnection {connection,..} =>
+                ThisNode::from_source(&connection.source,&graph.graph()),
+            Mode::EditNode {..} => None,
+        });
         let ret        = Self {
             logger,graph,this_arg,
             data             : Rc::new(RefCell::new(data)),
@@ -402,11 +452,20 @@ impl Searcher {
             parser           : project.parser(),
             position_in_code : Immutable(position),
             project_name     : project.name(),
+            actions          : default(),
+            browse_path      : default(),
         };
         ret.reload_list();
         Ok(ret)
     }
 
+    /// Set the actions which are suggested alongside the completions, until the user inserts any
+    /// code. The suggestion list is reloaded.
+    pub fn set_actions(&self, actions:Vec<suggestion::Action>) {
+        *self.actions.borrow_mut() = actions;
+        self.reload_list();
+    }
+
     /// Subscribe to controller's notifications.
     pub fn subscribe(&self) -> Subscriber<Notification> {
         self.notifier.subscribe()
@@ -426,10 +485,13 @@ impl Searcher {
         let parsed_input = ParsedInput::new(new_input,&self.parser)?;
         let old_expr     = self.data.borrow().input.expression.repr();
         let new_expr     = parsed_input.expression.repr();
+        let was_browsing = self.is_browsing();
 
         self.data.borrow_mut().input = parsed_input;
         self.invalidate_fragments_added_by_picking();
-        if old_expr != new_expr {
+        // The captions of the renaming actions contain the typed name.
+        let is_renaming = self.is_renaming_possible();
+        if old_expr != new_expr || was_browsing != self.is_browsing() || is_renaming {
             debug!(self.logger, "Reloading list.");
             self.reload_list();
         } else if let Suggestions::Loaded {list} = self.data.borrow().suggestions.clone_ref() {
@@ -461,16 +523,37 @@ impl Searcher {
     ///
     /// This function should be called when user chooses some completion suggestion. The picked
     /// suggestion will be remembered, and the searcher's input will be updated and returned by this
-    /// function.
+    /// function. The pick is also recorded in the Suggestion Database, to rank the frequently used
+    /// suggestions higher.
     pub fn pick_completion
     (&self, picked_suggestion:suggestion::Completion) -> FallibleResult<String> {
         info!(self.logger, "Picking suggestion: {picked_suggestion:?}");
+        self.database.record_pick(&picked_suggestion);
         let id                = self.data.borrow().input.next_completion_id();
         let code_to_insert    = self.code_to_insert(&picked_suggestion,id);
-        let added_ast         = self.parser.parse_line(&code_to_insert)?;
         let picked_completion = FragmentAddedByPickingSuggestion {id,picked_suggestion};
-        let pattern_offset    = self.data.borrow().input.pattern_offset;
-        let new_expression    = match self.data.borrow_mut().input.expression.take() {
+        let new_input         = self.insert_picked_code(&code_to_insert)?;
+        self.data.borrow_mut().fragments_added_by_picking.push(picked_completion);
+        self.reload_list();
+        Ok(new_input)
+    }
+
+    /// Pick a literal suggestion.
+    ///
+    /// This function should be called when user chooses some literal suggestion. The literal's
+    /// code is inserted as the next argument, and the searcher's new input is returned.
+    pub fn pick_literal(&self, literal:&suggestion::Literal) -> FallibleResult<String> {
+        info!(self.logger, "Picking literal: {literal:?}");
+        let new_input = self.insert_picked_code(&literal.code)?;
+        self.reload_list();
+        Ok(new_input)
+    }
+
+    /// Replace the pattern in the searcher's input with the picked code, and return the new input.
+    fn insert_picked_code(&self, code_to_insert:&str) -> FallibleResult<String> {
+        let added_ast      = self.parser.parse_line(code_to_insert)?;
+        let pattern_offset = self.data.borrow().input.pattern_offset;
+        let new_expression = match self.data.borrow_mut().input.expression.take() {
             None => {
                 let ast = ast::prefix::Chain::from_ast_non_strict(&added_ast);
                 ast::Shifted::new(pattern_offset,ast)
@@ -491,21 +574,68 @@ impl Searcher {
         };
         let new_input = new_parsed_input.repr();
         self.data.borrow_mut().input = new_parsed_input;
-        self.data.borrow_mut().fragments_added_by_picking.push(picked_completion);
-        self.reload_list();
         Ok(new_input)
     }
 
-    /// Pick a completion suggestion by index.
+    /// Get the suggestion with given index in the current list.
+    pub fn suggestion_by_index(&self, index:usize) -> FallibleResult<Suggestion> {
+        let error = || NoSuchSuggestion{index};
+        let data  = self.data.borrow();
+        let list  = data.suggestions.list().ok_or_else(error)?;
+        Ok(list.get_cloned(index).ok_or_else(error)?.suggestion)
+    }
+
+    /// Pick a completion or literal suggestion by index. Fails if the suggestion is an action or
+    /// a category.
     pub fn pick_completion_by_index(&self, index:usize) -> FallibleResult<String> {
-        let error      = || NoSuchSuggestion{index};
-        let suggestion = {
-            let data = self.data.borrow();
-            let list = data.suggestions.list().ok_or_else(error)?;
-            list.get_cloned(index).ok_or_else(error)?.suggestion
-        };
-        match suggestion {
-            Suggestion::Completion(completion) => self.pick_completion(completion)
+        match self.suggestion_by_index(index)? {
+            Suggestion::Completion(completion) => self.pick_completion(completion),
+            Suggestion::Literal(literal)       => self.pick_literal(&literal),
+            _                                  => Err(NotACompletion{index}.into()),
+        }
+    }
+
+    /// Check if the Searcher is in the browse mode, where the Suggestion Database entries are
+    /// grouped into categories by their modules and types.
+    ///
+    /// It is the case when the input is empty and the suggestions do not depend on the `this`
+    /// argument's type.
+    pub fn is_browsing(&self) -> bool {
+        let input = &self.data.borrow().input;
+        input.expression.is_none() && input.pattern.is_empty() && self.this_arg.is_none()
+    }
+
+    /// Get the path of the currently browsed category, e.g. for displaying breadcrumbs. Empty if
+    /// the top-level categories are listed.
+    pub fn browse_path(&self) -> Vec<ImString> {
+        self.browse_path.borrow().clone()
+    }
+
+    /// Enter the category in the browse mode. The suggestion list is reloaded with the category
+    /// content.
+    pub fn enter_category(&self, category:&suggestion::Category) {
+        *self.browse_path.borrow_mut() = category.path.deref().clone();
+        self.reload_list();
+    }
+
+    /// Enter the category with given index in the current list. Fails if the suggestion is not a
+    /// category.
+    pub fn enter_category_by_index(&self, index:usize) -> FallibleResult<()> {
+        match self.suggestion_by_index(index)? {
+            Suggestion::Category(category) => {
+                self.enter_category(&category);
+                Ok(())
+            }
+            _ => Err(NotACategory{index}.into()),
+        }
+    }
+
+    /// Leave the currently browsed category, going back to its parent. Does nothing if the
+    /// top-level categories are listed.
+    pub fn leave_category(&self) {
+        let left = self.browse_path.borrow_mut().pop().is_some();
+        if left {
+            self.reload_list();
         }
     }
 
@@ -532,20 +662,17 @@ impl Searcher {
         let data_borrowed   = self.data.borrow();
         let expression      = data_borrowed.input.repr();
         let intended_method = self.intended_method();
+        let _transaction    = self.graph.graph().module.open_transaction("Commit node");
 
-        let id = match *self.mode {
-            Mode::NewNode {position} => {
-                let mut new_node    = NewNodeInfo::new_pushed_back(expression);
-                new_node.metadata   = Some(NodeMetadata {position,intended_method});
-                let graph           = self.graph.graph();
-                if self.is_function_fragment_unmodified() {
-                    if let Some(this) = self.this_arg.deref().as_ref() {
-                        this.introduce_pattern(graph.clone_ref())?;
-                    }
-                }
-                graph.add_node(new_node)?
-            },
+        let id = match &*self.mode {
+            Mode::NewNode {position} => self.add_node(expression,*position,intended_method)?,
+            Mode::InsertIntoConnection {connection,position} => {
+                let node_id = self.add_node(expression,*position,intended_method)?;
+                self.connect_inserted_node(node_id,connection)?;
+                node_id
+            }
             Mode::EditNode {node_id} => {
+                let node_id = *node_id;
                 self.graph.graph().set_expression(node_id,expression)?;
                 self.graph.graph().module.with_node_metadata(node_id,Box::new(|md| {
                     md.intended_method = intended_method
@@ -557,6 +684,74 @@ impl Searcher {
         Ok(id)
     }
 
+    /// Add a new node with given expression. The pattern is introduced on the node serving as
+    /// `this` argument, if the expression still uses it.
+    fn add_node
+    (&self, expression:String, position:Option<Position>, intended_method:Option<MethodId>)
+    -> FallibleResult<ast::Id> {
+        let mut new_node  = NewNodeInfo::new_pushed_back(expression);
+        new_node.metadata = Some(NodeMetadata {position,intended_method,..default()});
+        let graph         = self.graph.graph();
+        if self.is_function_fragment_unmodified() {
+            if let Some(this) = self.this_arg.deref().as_ref() {
+                this.introduce_pattern(graph.clone_ref())?;
+            }
+        }
+        graph.add_node(new_node)
+    }
+
+    /// Connect the node inserted into the connection: its output is connected to the connection's
+    /// destination. If the connection's source is not used as `this` argument, it is connected to
+    /// the first empty argument placeholder of the node, or to its first insertion point. If there
+    /// is no such port, the node is not connected and the original connection is left intact.
+    fn connect_inserted_node
+    (&self, node_id:ast::Id, connection:&controller::graph::Connection) -> FallibleResult<()> {
+        let uses_this = self.is_function_fragment_unmodified() && self.this_arg.is_some();
+        if !uses_this {
+            match self.first_empty_port(node_id)? {
+                Some(port) => {
+                    let source      = connection.source.clone();
+                    let destination = controller::graph::Endpoint::new(node_id,port);
+                    self.graph.connect(&controller::graph::Connection {source,destination})?;
+                }
+                None => {
+                    info!(self.logger,"The inserted node has no empty port for the connection's \
+                        source; leaving the connection intact.");
+                    return Ok(())
+                }
+            }
+        }
+        let source      = controller::graph::Endpoint::new(node_id,default());
+        let destination = connection.destination.clone();
+        self.graph.connect(&controller::graph::Connection {source,destination})
+    }
+
+    /// Get the first empty argument placeholder in the node's expression: an expected argument
+    /// not given yet, or an argument or target being a blank `_`. If there is no such placeholder,
+    /// the first insertion point is returned.
+    fn first_empty_port
+    (&self, node_id:ast::Id) -> FallibleResult<Option<controller::graph::PortId>> {
+        use span_tree::node::InsertType;
+        use span_tree::node::Kind;
+        let node           = self.graph.graph().node(node_id)?;
+        let expression     = node.info.expression();
+        let span_tree      = SpanTree::new(expression,&self.graph)?;
+        let is_blank       = |port:&span_tree::node::Ref| {
+            let ast = expression.get_traversing(&port.ast_crumbs);
+            ast.map_or(false, |ast| ast::known::Blank::try_from(ast).is_ok())
+        };
+        let is_placeholder = |port:&span_tree::node::Ref| match port.node.kind {
+            Kind::Empty(InsertType::ExpectedArgument(_)) => true,
+            Kind::Argument {..} | Kind::Target {..}      => is_blank(port),
+            _                                            => false,
+        };
+        let mut ports       = span_tree.root_ref().leaf_iter();
+        let placeholder     = ports.find(is_placeholder);
+        let insertion_point = || span_tree.root_ref().leaf_iter().find(|port| port.node.is_empty());
+        let port            = placeholder.or_else(insertion_point);
+        Ok(port.map(|port| port.crumbs))
+    }
+
     fn invalidate_fragments_added_by_picking(&self) {
         let mut data = self.data.borrow_mut();
         let data     = data.deref_mut();
@@ -586,20 +781,147 @@ impl Searcher {
 
     /// Reload Suggestion List.
     ///
-    /// The current list will be set as "Loading" and Language Server will be requested for a new
-    /// list - once it be retrieved, the new list will be set and notification will be emitted.
+    /// If the suggestions do not depend on types, the new list is searched in the local Suggestion
+    /// Database. Otherwise the current list will be set as "Loading" and Language Server will be
+    /// requested for a new list - once it be retrieved, the new list will be set and notification
+    /// will be emitted.
     fn reload_list(&self) {
-        let this_type    = self.this_arg_type_for_next_completion();
-        let return_types = match self.data.borrow().input.next_completion_id() {
+        let next_id      = self.data.borrow().input.next_completion_id();
+        let return_types = match next_id {
             CompletedFragmentId::Function         => vec![],
             CompletedFragmentId::Argument {index} =>
                 self.return_types_for_argument_completion(index),
         };
-        self.get_suggestion_list_from_engine(this_type,return_types,None);
-        self.data.borrow_mut().suggestions = Suggestions::Loading;
+        let uses_this_type = next_id == CompletedFragmentId::Function && self.this_arg.is_some();
+        if uses_this_type || !return_types.is_empty() {
+            let this_type  = self.this_arg_type_for_next_completion();
+            let file_paths = self.file_path_suggestions_for_next_completion();
+            self.get_suggestion_list_from_engine(this_type,return_types,file_paths,None);
+            self.data.borrow_mut().suggestions = Suggestions::Loading;
+        } else {
+            let list = if self.is_browsing() {
+                self.suggestions_in_browsed_category()
+            } else {
+                self.suggestions_from_database()
+            };
+            self.data.borrow_mut().suggestions = Suggestions::Loaded {list:Rc::new(list)};
+        }
         executor::global::spawn(self.notifier.publish(Notification::NewSuggestionList));
     }
 
+    /// Get the suggestion list of all entries visible in the edited code, searched in the local
+    /// Suggestion Database.
+    fn suggestions_from_database(&self) -> suggestion::List {
+        let entries     = self.entries_visible_in_code();
+        let suggestions = suggestion::List::from(entries.into_iter().map(Suggestion::Completion));
+        suggestions.extend(self.action_suggestions());
+        suggestions.update_relevance(|suggestion| self.relevance(suggestion,&None,&[]));
+        suggestions.update_filtering(&self.data.borrow().input.pattern);
+        suggestions
+    }
+
+    /// Get the content of the currently browsed category. The actions are listed along with the
+    /// top-level categories.
+    fn suggestions_in_browsed_category(&self) -> suggestion::List {
+        let entries     = self.entries_visible_in_code();
+        let path        = self.browse_path();
+        let suggestions = suggestion::List::from(browse::category_content(entries,&path));
+        if path.is_empty() {
+            suggestions.extend(self.action_suggestions());
+        }
+        suggestions.update_relevance(|suggestion| self.relevance(suggestion,&None,&[]));
+        suggestions
+    }
+
+    /// Get all Suggestion Database entries visible in the edited code.
+    fn entries_visible_in_code(&self) -> Vec<suggestion::Completion> {
+        let module = self.module_qualified_name();
+        let query  = model::suggestion_database::Query::default();
+        let query  = query.visible_at(module,*self.position_in_code);
+        self.database.search(&query)
+    }
+
+    /// Get the relevance of the suggestion in the current context: the type of `this` argument and
+    /// the types expected by the argument being filled.
+    fn relevance
+    (&self, suggestion:&Suggestion, this_type:&Option<String>, return_types:&[String])
+    -> suggestion::Relevance {
+        match suggestion {
+            Suggestion::Completion(entry) => suggestion::Relevance {
+                pick_count          : self.database.pick_count(entry),
+                self_type_matches   : this_type.is_some() && entry.self_type == *this_type,
+                return_type_matches : return_types.contains(&entry.return_type),
+                is_local            : entry.module == self.module_qualified_name(),
+            },
+            Suggestion::Action(_) | Suggestion::Category(_) | Suggestion::Literal(_) => default(),
+        }
+    }
+
+    /// Get the suggestions of the actions. The actions are suggested only until the user inserts
+    /// any code, as they are executed instead of the inserted node.
+    fn action_suggestions(&self) -> Vec<Suggestion> {
+        if self.data.borrow().input.expression.is_none() {
+            let actions = self.actions.borrow().iter().cloned().chain(self.rename_actions());
+            actions.map(Suggestion::Action).collect()
+        } else {
+            default()
+        }
+    }
+
+    /// Check if the renaming actions may be suggested, i.e. an existing node is edited and its
+    /// expression was replaced with a single name.
+    fn is_renaming_possible(&self) -> bool {
+        let is_editing_node = matches!(*self.mode, Mode::EditNode {..});
+        is_editing_node && self.data.borrow().input.expression.is_none()
+    }
+
+    /// Get the actions renaming the edited node's variable and the project's method called by the
+    /// node to the name typed in the input. The action is executed instead of editing the node.
+    fn rename_actions(&self) -> Vec<suggestion::Action> {
+        let node_id = match *self.mode {
+            Mode::EditNode {node_id} => node_id,
+            _                        => return default(),
+        };
+        let new_name = self.data.borrow().input.pattern.clone();
+        let parsed   = self.parser.parse_line(&new_name).ok();
+        let is_name  = parsed.as_ref().and_then(ast::identifier::as_var) == Some(new_name.as_str());
+        if !is_name {
+            return default()
+        }
+        let mut actions = Vec::new();
+        let node        = self.graph.graph().node_info(node_id).ok();
+        let variable    = node.as_ref().and_then(|node| ast::identifier::as_var(node.pattern()?));
+        let variable    = variable.filter(|variable| *variable != new_name);
+        if let Some(variable) = variable {
+            let caption = iformat!("rename variable {variable} to {new_name}");
+            let graph   = self.graph.clone_ref();
+            let logger  = self.logger.clone_ref();
+            let name    = new_name.clone();
+            actions.push(suggestion::Action::new(caption,None,move || {
+                if let Err(err) = graph.graph().rename_node_variable(node_id,&name) {
+                    error!(logger,"Failed to rename the node's variable: {err}");
+                }
+            }));
+        }
+        let method = self.graph.node_project_method(node_id).filter(|m| m.name != new_name);
+        if let Some(method) = method {
+            let caption = iformat!("rename method {method.name} to {new_name}");
+            let graph   = self.graph.clone_ref();
+            let logger  = self.logger.clone_ref();
+            actions.push(suggestion::Action::new(caption,None,move || {
+                let graph    = graph.clone_ref();
+                let logger   = logger.clone_ref();
+                let new_name = new_name.clone();
+                executor::global::spawn(async move {
+                    if let Err(err) = graph.rename_node_method(node_id,new_name).await {
+                        error!(logger,"Failed to rename the node's method: {err}");
+                    }
+                });
+            }));
+        }
+        actions
+    }
+
     /// Get the typename of "this" value for current completion context. Returns `Future`, as the
     /// type information might not have came yet from the Language Server.
     fn this_arg_type_for_next_completion(&self) -> impl Future<Output=Option<String>> {
@@ -610,8 +932,8 @@ impl Searcher {
         async move {
             let is_function_fragment = next_id == CompletedFragmentId::Function;
             is_function_fragment.then(())?;
-            let ThisNode {id,..} = this.deref().as_ref()?;
-            let opt_type         = graph.expression_type(*id).await.map(Into::into);
+            let ThisNode {expression,..} = this.deref().as_ref()?;
+            let opt_type = graph.expression_type(*expression).await.map(Into::into);
             opt_type.map_none(move || error!(logger, "Failed to obtain type for this node."))
         }
     }
@@ -621,28 +943,85 @@ impl Searcher {
     /// Generally this corresponds to the type of the currently filled function argument. Returns
     /// empty list if no type could be determined.
     fn return_types_for_argument_completion(&self, arg_index:usize) -> Vec<String> {
+        let arguments = self.possible_arguments(arg_index).into_iter();
+        arguments.map(|argument| argument.repr_type).collect()
+    }
+
+    /// Get the definitions of the argument with given index in all functions which may be called
+    /// in the searcher's input.
+    fn possible_arguments(&self, arg_index:usize) -> Vec<model::suggestion_database::Argument> {
         let suggestions = if let Some(intended) = self.intended_function_suggestion() {
             std::iter::once(intended).collect()
         } else {
             self.possible_function_calls()
         };
+        let arg_index = arg_index + if self.has_this_argument() { 1 } else { 0 };
         suggestions.into_iter().filter_map(|suggestion| {
-            let arg_index = arg_index + if self.has_this_argument() { 1 } else { 0 };
-            let parameter = suggestion.arguments.get(arg_index)?;
-            Some(parameter.repr_type.clone())
+            suggestion.arguments.get(arg_index).cloned()
         }).collect()
     }
 
+    /// Get the literal suggestions for the argument being filled: the arguments' default values
+    /// and the atoms constructing values of the arguments' types.
+    fn literal_suggestions(&self) -> Vec<Suggestion> {
+        let next_id   = self.data.borrow().input.next_completion_id();
+        let arguments = match next_id {
+            CompletedFragmentId::Function         => default(),
+            CompletedFragmentId::Argument {index} => self.possible_arguments(index),
+        };
+        let default_values = arguments.iter().filter_map(|argument| {
+            let value = argument.default_value.as_ref()?;
+            Some(Suggestion::Literal(suggestion::Literal::default_value(value)))
+        });
+        let atoms = arguments.iter().flat_map(|argument| {
+            self.database.lookup_atoms_of_type(&argument.repr_type)
+        }).map(Suggestion::Completion);
+        let mut suggestions = Vec::new();
+        for suggestion in default_values.chain(atoms) {
+            if !suggestions.contains(&suggestion) {
+                suggestions.push(suggestion);
+            }
+        }
+        suggestions
+    }
+
+    /// Get the paths of the project's files as literal suggestions, if the argument being filled
+    /// is a file path. Returns `Future`, as the paths are listed by the Language Server.
+    fn file_path_suggestions_for_next_completion(&self) -> impl Future<Output=Vec<Suggestion>> {
+        let next_id = self.data.borrow().input.next_completion_id();
+        let is_path = match next_id {
+            CompletedFragmentId::Function         => false,
+            CompletedFragmentId::Argument {index} =>
+                self.possible_arguments(index).iter().any(is_path_argument),
+        };
+        let language_server = self.language_server.clone_ref();
+        let logger          = self.logger.clone_ref();
+        async move {
+            if is_path {
+                let paths = project_file_paths(&language_server).await.handle_err(|err| {
+                    error!(logger,"Failed to list the project's files: {err}.")
+                }).unwrap_or_default();
+                paths.into_iter().map(|path| {
+                    Suggestion::Literal(suggestion::Literal::file_path(&path.segments))
+                }).collect()
+            } else {
+                default()
+            }
+        }
+    }
+
     fn get_suggestion_list_from_engine
     ( &self
     , this_type    : impl Future<Output=Option<String>> + 'static
     , return_types : impl IntoIterator<Item=String>
+    , file_paths   : impl Future<Output=Vec<Suggestion>> + 'static
     , tags         : Option<Vec<language_server::SuggestionEntryType>>
     ) {
         let ls               = self.language_server.clone_ref();
         let graph            = self.graph.graph();
         let position         = self.position_in_code.deref().into();
-        let mut return_types = return_types.into_iter().map(Some).collect_vec();
+        let expected_types   = return_types.into_iter().collect_vec();
+        let mut return_types = expected_types.iter().cloned().map(Some).collect_vec();
         let this             = self.clone_ref();
         if return_types.is_empty() {
             return_types.push(None)
@@ -655,9 +1034,12 @@ impl Searcher {
                 let file = graph.module.path().file_path();
                 ls.completion(file,&position,&this_type,&return_type,&tags)
             });
-            let responses = futures::future::join_all(requests).await;
+            let responses  = futures::future::join_all(requests).await;
+            let file_paths = file_paths.await;
             info!(this.logger,"Received suggestions from Language Server.");
-            let new_suggestions = match this.suggestions_from_responses(responses) {
+            let list = this.suggestions_from_responses
+                (responses,file_paths,&this_type,&expected_types);
+            let new_suggestions = match list {
                 Ok(list)   => Suggestions::Loaded {list:Rc::new(list)},
                 Err(error) => Suggestions::Error(Rc::new(error))
             };
@@ -667,9 +1049,18 @@ impl Searcher {
     }
 
     /// Process multiple completion responses from the engine into a single list of suggestion.
+    /// The literal suggestions for the argument being filled, including the given file paths, are
+    /// added to the list.
+    ///
+    /// The suggestions are ranked according to the `this_type` and the `return_types` used in
+    /// requests.
     fn suggestions_from_responses
-    (&self, responses:Vec<json_rpc::Result<language_server::response::Completion>>)
-    -> FallibleResult<suggestion::List> {
+    ( &self
+    , responses    : Vec<json_rpc::Result<language_server::response::Completion>>
+    , file_paths   : Vec<Suggestion>
+    , this_type    : &Option<String>
+    , return_types : &[String]
+    ) -> FallibleResult<suggestion::List> {
         let suggestions = suggestion::List::new();
         for response in responses {
             let response = response?;
@@ -683,6 +1074,14 @@ impl Searcher {
             });
             suggestions.extend(entries);
         }
+        let listed   = suggestions.iter().map(|entry| entry.suggestion).collect_vec();
+        let literals = self.literal_suggestions().into_iter();
+        suggestions.extend(literals.filter(|literal| !listed.contains(literal)));
+        suggestions.extend(file_paths);
+        suggestions.extend(self.action_suggestions());
+        suggestions.update_relevance(|suggestion| {
+            self.relevance(suggestion,this_type,return_types)
+        });
         suggestions.update_filtering(&self.data.borrow().input.pattern);
         Ok(suggestions)
     }
@@ -788,6 +1187,46 @@ impl SimpleFunctionCall {
 
 
 
+// ==================
+// === File Paths ===
+// ==================
+
+/// The maximum number of nested directories searched for the files suggested as path arguments.
+const MAX_FILE_PATH_DEPTH:usize = 3;
+
+/// Check if the argument is a file path: a `Text` argument with "path" or "file" in its name.
+fn is_path_argument(argument:&model::suggestion_database::Argument) -> bool {
+    let name = argument.name.to_lowercase();
+    argument.repr_type == "Text" && (name.contains("path") || name.contains("file"))
+}
+
+/// List the paths of all files in the project, skipping the hidden files and directories.
+///
+/// Only the files nested in at most `MAX_FILE_PATH_DEPTH` directories are listed, as each directory
+/// requires a separate request to the Language Server.
+async fn project_file_paths
+(language_server:&language_server::Connection) -> FallibleResult<Vec<language_server::Path>> {
+    let root_id         = language_server.content_root();
+    let mut directories = vec![language_server::Path::new(root_id,&[] as &[&str])];
+    let mut files       = Vec::<language_server::Path>::new();
+    while let Some(directory) = directories.pop() {
+        let can_descend = directory.segments.len() < MAX_FILE_PATH_DEPTH;
+        for object in language_server.file_list(&directory).await?.paths {
+            use language_server::FileSystemObject::*;
+            match &object {
+                Directory{name,..} if can_descend && !name.starts_with('.') =>
+                    directories.push((&object).into()),
+                File{name,..} if !name.starts_with('.') => files.push((&object).into()),
+                _                                       => {}
+            }
+        }
+    }
+    files.sort_by(|lhs,rhs| lhs.segments.cmp(&rhs.segments));
+    Ok(files)
+}
+
+
+
 // =============
 // === Tests ===
 // =============
@@ -829,8 +1268,8 @@ pub mod test {
     }
 
     impl MockData {
-        fn change_main_body(&mut self, line:&str) {
-            let code     = dbg!(crate::test::mock::main_from_lines(&[line]));
+        fn change_main_body(&mut self, lines:&[&str]) {
+            let code     = dbg!(crate::test::mock::main_from_lines(lines));
             let location = data::text::TextLocation::at_document_end(&code);
             // TODO [mwu] Not nice that we ended up with duplicated mock data for code.
             self.graph.module.code = code.clone();
@@ -894,6 +1333,8 @@ pub mod test {
                 this_arg         : Rc::new(this),
                 position_in_code : Immutable(end_of_code),
                 project_name     : ImString::new(&data.graph.graph.project_name),
+                actions          : default(),
+                browse_path      : default(),
             };
             let entry1 = model::suggestion_database::Entry {
                 name          : "testFunction1".to_string(),
@@ -1019,14 +1460,13 @@ pub mod test {
 
         for case in &cases {
             let Fixture { mut test, searcher, entry1, .. } = Fixture::new_custom(|data,client| {
-                data.change_main_body(case.node_line);
+                data.change_main_body(&[case.node_line]);
                 data.selected_node = true;
-                // We expect following calls:
-                // 1) for the function - with the "this" filled (if the test case says so);
-                // 2) for subsequent completions - without "this"
-                data.expect_completion(client,case.sets_this.as_some(mock_type),None,&[1,5,9]);
-                data.expect_completion(client,None,None,&[1,5,9]);
-                data.expect_completion(client,None,None,&[1,5,9]);
+                // We expect the call for the function with the "this" filled (if the test case says
+                // so). The other completions do not depend on types and are searched locally.
+                if case.sets_this {
+                    data.expect_completion(client,Some(mock_type),None,&[1,5,9]);
+                }
             });
 
             searcher.reload_list();
@@ -1070,7 +1510,6 @@ pub mod test {
         let mut fixture = Fixture::new_custom(|data,client| {
             data.expect_completion(client,None,Some("Text"),&[]);   // First arg suggestion.
             data.expect_completion(client,None,Some("Number"),&[]); // Second arg suggestion.
-            data.expect_completion(client,None,None,&[]);           // Oversaturated arg position.
         });
 
 
@@ -1081,6 +1520,81 @@ pub mod test {
         searcher.set_input("testFunction2 'foo' 10 ".to_owned()).unwrap();
     }
 
+    #[wasm_bindgen_test]
+    fn literal_suggestions_for_arguments() {
+        use language_server::FileSystemObject;
+        use language_server::Path;
+        use model::suggestion_database::Entry;
+        use model::suggestion_database::EntryKind;
+
+        let root_id     = uuid::Uuid::default();
+        let data_path   = ["data".to_string(),"table.csv".to_string()];
+        let notes_path  = ["notes.txt".to_string()];
+        let mut fixture = Fixture::new_custom(|data,client| {
+            let root         = Path::new(root_id,&[] as &[&str]);
+            let data_dir     = Path::new(root_id,&["data"]);
+            let hidden_dir   = Path::new(root_id,&[".enso"]);
+            let root_content = vec![
+                FileSystemObject::new_directory(data_dir.clone()).unwrap(),
+                FileSystemObject::new_directory(hidden_dir).unwrap(),
+                FileSystemObject::new_file(Path::new(root_id,&notes_path)).unwrap(),
+            ];
+            let data_content = vec![
+                FileSystemObject::new_file(Path::new(root_id,&data_path)).unwrap(),
+            ];
+            let root_list = language_server::response::FileList {paths:root_content};
+            let data_list = language_server::response::FileList {paths:data_content};
+            data.expect_completion(client,None,Some("Text"),&[]);
+            expect_call!(client.file_list(path=root) => Ok(root_list));
+            expect_call!(client.file_list(path=data_dir) => Ok(data_list));
+            data.expect_completion(client,None,Some("Format"),&[]);
+        });
+        let Fixture{test,searcher,entry1,..} = &mut fixture;
+        let argument = |name:&str, repr_type:&str, default_value:Option<&str>| Argument {
+            name          : name.to_string(),
+            repr_type     : repr_type.to_string(),
+            default_value : default_value.map(ToString::to_string),
+            is_suspended  : false,
+        };
+        let read_table = Entry {
+            name      : "read_table".to_string(),
+            arguments : vec![
+                argument("file_path","Text",None),
+                argument("format","Format",Some("Format.Csv")),
+            ],
+            ..(**entry1).clone()
+        };
+        let atom = |name:&str| Entry {
+            name        : name.to_string(),
+            kind        : EntryKind::Atom,
+            return_type : "Format".to_string(),
+            ..(**entry1).clone()
+        };
+        searcher.database.put_entry(10,read_table);
+        searcher.database.put_entry(11,atom("Csv"));
+        searcher.database.put_entry(12,atom("Json"));
+        let read_table = searcher.database.lookup(10).unwrap();
+        let csv        = searcher.database.lookup(11).unwrap();
+        let json       = searcher.database.lookup(12).unwrap();
+        let list       = || searcher.suggestions().list().unwrap().to_suggestion_vec();
+
+        searcher.pick_completion(read_table).unwrap();
+        test.run_until_stalled();
+        let data_file  = Suggestion::Literal(suggestion::Literal::file_path(&data_path));
+        let notes_file = Suggestion::Literal(suggestion::Literal::file_path(&notes_path));
+        let literals   = list().into_iter().filter(|s| matches!(s,Suggestion::Literal(_)));
+        assert_eq!(literals.collect_vec(),vec![data_file.clone(),notes_file]);
+
+        let index = list().iter().position(|suggestion| *suggestion == data_file).unwrap();
+        searcher.pick_completion_by_index(index).unwrap();
+        assert_eq!(searcher.data.borrow().input.repr(),"read_table \"data/table.csv\" ");
+        test.run_until_stalled();
+        let default_value = suggestion::Literal::default_value("Format.Csv");
+        assert!(list().contains(&Suggestion::Literal(default_value)));
+        assert!(list().contains(&Suggestion::Completion(csv)));
+        assert!(list().contains(&Suggestion::Completion(json)));
+    }
+
     #[wasm_bindgen_test]
     fn non_picked_function_arg_suggestions() {
         let mut fixture = Fixture::new_custom(|data,client| {
@@ -1090,7 +1604,6 @@ pub mod test {
             data.expect_completion(client,None,Some("Number"),&[]);
             data.expect_completion(client,None,Some("Number"),&[]);
             data.expect_completion(client,None,Some("Number"),&[]);
-            data.expect_completion(client,None,None,&[1,2,3,4,9]);
         });
         let Fixture{searcher,..} = &mut fixture;
 
@@ -1150,12 +1663,16 @@ pub mod test {
     #[wasm_bindgen_test]
     fn loading_list() {
         let Fixture{mut test,searcher,entry1,entry9,..} = Fixture::new_custom(|data,client| {
-            data.expect_completion(client,None,None,&[1,5,9]);
+            data.selected_node = true;
+            data.expect_completion(client,Some("Number"),None,&[1,5,9]);
         });
 
         let mut subscriber = searcher.subscribe();
         searcher.reload_list();
         assert!(searcher.suggestions().is_loading());
+        let this_node_id = searcher.this_arg.deref().as_ref().unwrap().id;
+        let update       = value_update_with_type(this_node_id,"Number");
+        searcher.graph.computed_value_info_registry().apply_updates(vec![update]);
         test.run_until_stalled();
         let expected_list = vec![Suggestion::Completion(entry1),Suggestion::Completion(entry9)];
         assert_eq!(searcher.suggestions().list().unwrap().to_suggestion_vec(), expected_list);
@@ -1163,6 +1680,118 @@ pub mod test {
         assert_eq!(notification, Some(Notification::NewSuggestionList));
     }
 
+    #[wasm_bindgen_test]
+    fn loading_list_from_database() {
+        let Fixture{mut test,searcher,entry1,entry2,entry3,entry4,entry9,..} = Fixture::new();
+        let mut subscriber = searcher.subscribe();
+        // The entries from the edited module go first.
+        let expected_list = vec![entry1,entry2,entry3,entry9,entry4];
+        let expected_list = expected_list.into_iter().map(Suggestion::Completion).collect_vec();
+        assert_eq!(searcher.suggestions_from_database().to_suggestion_vec(),expected_list);
+        searcher.set_input("test".to_string()).unwrap();
+        assert!(!searcher.suggestions().is_loading());
+        test.run_until_stalled();
+        let list = searcher.suggestions().list().unwrap().to_suggestion_vec();
+        for suggestion in &expected_list {
+            assert!(list.contains(suggestion));
+        }
+        let notification = subscriber.next().boxed_local().expect_ready();
+        assert_eq!(notification, Some(Notification::NewSuggestionList));
+
+        searcher.set_input("testF".to_string()).unwrap();
+        let list = searcher.suggestions().list().unwrap().to_suggestion_vec();
+        assert_eq!(searcher.suggestions().list().unwrap().matching_count(),2);
+        assert!(list[..2].iter().all(|s| s.caption().starts_with("testFunction")));
+    }
+
+    #[wasm_bindgen_test]
+    fn browsing_categories() {
+        let Fixture{mut test,searcher,entry1,entry2,entry3,entry4,entry9,..} = Fixture::new();
+        let mut subscriber = searcher.subscribe();
+        let category       = |path:&[&str]| {
+            let path = path.iter().map(|segment| ImString::new(*segment)).collect_vec();
+            let name = path.last().unwrap().clone_ref();
+            Suggestion::Category(suggestion::Category {name,path:Rc::new(path)})
+        };
+        let project_name = crate::test::mock::data::PROJECT_NAME;
+        let module_name  = crate::test::mock::data::MODULE_NAME;
+        let list         = || searcher.suggestions().list().unwrap().to_suggestion_vec();
+        searcher.reload_list();
+        assert!(searcher.is_browsing());
+        assert_eq!(list(),vec![category(&[project_name]),category(&["Test"])]);
+
+        searcher.enter_category_by_index(0).unwrap();
+        assert_eq!(searcher.browse_path(),vec![ImString::new(project_name)]);
+        assert_eq!(list(),vec![category(&[project_name,module_name])]);
+        searcher.enter_category_by_index(0).unwrap();
+        let expected_list = vec![entry1,entry2,entry3,entry9];
+        let expected_list = expected_list.into_iter().map(Suggestion::Completion).collect_vec();
+        assert_eq!(list(),expected_list);
+        assert!(searcher.enter_category_by_index(0).is_err());
+        test.run_until_stalled();
+        let notification = subscriber.next().boxed_local().expect_ready();
+        assert_eq!(notification, Some(Notification::NewSuggestionList));
+
+        // Picking an entry in the category works as picking a completion.
+        searcher.pick_completion_by_index(0).unwrap();
+        assert!(!searcher.is_browsing());
+        searcher.set_input("".to_string()).unwrap();
+        assert!(searcher.is_browsing());
+
+        searcher.leave_category();
+        searcher.leave_category();
+        assert!(searcher.browse_path().is_empty());
+        searcher.enter_category_by_index(1).unwrap();
+        searcher.enter_category_by_index(0).unwrap();
+        assert_eq!(list(),vec![Suggestion::Completion(entry4)]);
+
+        // Typing a pattern leaves the browse mode.
+        searcher.set_input("testF".to_string()).unwrap();
+        assert!(!searcher.is_browsing());
+        assert_eq!(searcher.suggestions().list().unwrap().matching_count(),2);
+    }
+
+    #[wasm_bindgen_test]
+    fn ranking_suggestions_by_usage() {
+        let Fixture{mut test,searcher,entry1,entry9,..} = Fixture::new();
+        searcher.set_input("testF".to_string()).unwrap();
+        let list = searcher.suggestions().list().unwrap().to_suggestion_vec();
+        assert_eq!(list[..2], [Suggestion::Completion(entry1.clone_ref()),
+            Suggestion::Completion(entry9.clone_ref())]);
+
+        searcher.pick_completion(entry9.clone_ref()).unwrap();
+        test.run_until_stalled();
+        assert_eq!(searcher.database.pick_count(&entry9),1);
+        assert_eq!(searcher.database.pick_count(&entry1),0);
+
+        searcher.set_input("testF".to_string()).unwrap();
+        let list = searcher.suggestions().list().unwrap().to_suggestion_vec();
+        assert_eq!(list[..2], [Suggestion::Completion(entry9),Suggestion::Completion(entry1)]);
+    }
+
+    #[wasm_bindgen_test]
+    fn suggesting_actions() {
+        let Fixture{searcher,..} = Fixture::new();
+        let executed = Rc::new(Cell::new(0));
+        let action   = suggestion::Action::new("collapse selected nodes",None,
+            f!([executed]() executed.set(executed.get() + 1)));
+        searcher.set_actions(vec![action.clone_ref()]);
+        searcher.set_input("collapse".to_string()).unwrap();
+        let list = searcher.suggestions().list().unwrap().to_suggestion_vec();
+        assert_eq!(searcher.suggestions().list().unwrap().matching_count(),1);
+        assert_eq!(list[0],Suggestion::Action(action));
+        assert!(searcher.pick_completion_by_index(0).is_err());
+        if let Suggestion::Action(action) = searcher.suggestion_by_index(0).unwrap() {
+            action.execute();
+        }
+        assert_eq!(executed.get(),1);
+
+        // The actions are not suggested once some code is inserted.
+        searcher.set_input("testFunction1 ".to_string()).unwrap();
+        let list = searcher.suggestions().list().unwrap().to_suggestion_vec();
+        assert!(list.iter().all(|suggestion| matches!(suggestion, Suggestion::Completion(_))));
+    }
+
     #[wasm_bindgen_test]
     fn parsed_input() {
         let parser = Parser::new_or_panic();
@@ -1235,14 +1864,7 @@ pub mod test {
 
     #[wasm_bindgen_test]
     fn picked_completions_list_maintaining() {
-        let Fixture{test:_test,searcher,entry1,entry2,..} = Fixture::new_custom(|data,client| {
-            data.expect_completion(client,None,None,&[]);
-            data.expect_completion(client,None,None,&[]);
-            data.expect_completion(client,None,None,&[]);
-            data.expect_completion(client,None,None,&[]);
-            data.expect_completion(client,None,None,&[]);
-            data.expect_completion(client,None,None,&[]);
-        });
+        let Fixture{test:_test,searcher,entry1,entry2,..} = Fixture::new();
         let frags_borrow = || Ref::map(searcher.data.borrow(),|d| &d.fragments_added_by_picking);
 
         // Picking first suggestion.
@@ -1340,10 +1962,9 @@ pub mod test {
         ];
 
         for case in cases.into_iter() {
-            let mut fixture = Fixture::new_custom(|data, client| {
+            let mut fixture = Fixture::new_custom(|data,_client| {
                 data.selected_node = true;
-                data.change_main_body(case.line);
-                data.expect_completion(client,None,None,&[]);
+                data.change_main_body(&[case.line]);
             });
             (case.run)(&mut fixture);
             fixture.searcher.commit_node().unwrap();
@@ -1391,6 +2012,106 @@ pub mod test {
         assert_eq!(module.ast().repr(), expected_code);
     }
 
+    #[wasm_bindgen_test]
+    fn inserting_node_into_connection() {
+        let fixture = Fixture::new_custom(|data,_| {
+            data.selected_node = true;
+            data.change_main_body(&["a = 2 + 2","print a"]);
+        });
+        let Fixture{test:_test,mut searcher,entry3,..} = fixture;
+        let (connection,) = searcher.graph.connections().unwrap().connections.expect_tuple();
+        let parser        = Parser::new_or_panic();
+        let picked_method = FragmentAddedByPickingSuggestion {
+            id                : CompletedFragmentId::Function,
+            picked_suggestion : entry3,
+        };
+        with(searcher.data.borrow_mut(), |mut data| {
+            data.fragments_added_by_picking.push(picked_method);
+            data.input = ParsedInput::new("a.testMethod1".to_string(),&parser).unwrap();
+        });
+        searcher.mode = Immutable(Mode::InsertIntoConnection {connection,position:None});
+        searcher.commit_node().unwrap();
+
+        let expected = "main =\n    a = 2 + 2\n    operator1 = a.testMethod1\n    print operator1";
+        let updated_def = searcher.graph.graph().definition().unwrap().item;
+        assert_eq!(updated_def.ast.repr(),expected);
+    }
+
+    #[wasm_bindgen_test]
+    fn inserting_node_into_connection_through_empty_port() {
+        let fixture = Fixture::new_custom(|data,_| {
+            data.selected_node = true;
+            data.change_main_body(&["a = 2 + 2","print a"]);
+        });
+        let Fixture{test:_test,mut searcher,..} = fixture;
+        let (connection,) = searcher.graph.connections().unwrap().connections.expect_tuple();
+        let parser        = Parser::new_or_panic();
+        with(searcher.data.borrow_mut(), |mut data| {
+            data.input = ParsedInput::new("2 + 2".to_string(),&parser).unwrap();
+        });
+        searcher.mode = Immutable(Mode::InsertIntoConnection {connection,position:None});
+        searcher.commit_node().unwrap();
+
+        let expected    = "main =\n    a = 2 + 2\n    sum1 = a + 2 + 2\n    print sum1";
+        let updated_def = searcher.graph.graph().definition().unwrap().item;
+        assert_eq!(updated_def.ast.repr(),expected);
+    }
+
+    #[wasm_bindgen_test]
+    fn inserting_node_into_connection_through_infix_target() {
+        let fixture = Fixture::new_custom(|data,_| {
+            data.selected_node = true;
+            data.change_main_body(&["a = 2 + 2","print a"]);
+        });
+        let Fixture{test:_test,mut searcher,..} = fixture;
+        let (connection,) = searcher.graph.connections().unwrap().connections.expect_tuple();
+        let parser        = Parser::new_or_panic();
+        with(searcher.data.borrow_mut(), |mut data| {
+            data.input = ParsedInput::new("_ + 2".to_string(),&parser).unwrap();
+        });
+        searcher.mode = Immutable(Mode::InsertIntoConnection {connection,position:None});
+        searcher.commit_node().unwrap();
+
+        let expected    = "main =\n    a = 2 + 2\n    sum1 = a + 2\n    print sum1";
+        let updated_def = searcher.graph.graph().definition().unwrap().item;
+        assert_eq!(updated_def.ast.repr(),expected);
+    }
+
+    #[wasm_bindgen_test]
+    fn inserting_node_without_empty_port_into_connection() {
+        let fixture = Fixture::new_custom(|data,_| {
+            data.selected_node = true;
+            data.change_main_body(&["a = 2 + 2","print a"]);
+        });
+        let Fixture{test:_test,mut searcher,..} = fixture;
+        let (connection,) = searcher.graph.connections().unwrap().connections.expect_tuple();
+        let parser        = Parser::new_or_panic();
+        with(searcher.data.borrow_mut(), |mut data| {
+            data.input = ParsedInput::new("5".to_string(),&parser).unwrap();
+        });
+        searcher.mode = Immutable(Mode::InsertIntoConnection {connection,position:None});
+        searcher.commit_node().unwrap();
+
+        // The original connection is left intact.
+        let expected    = "main =\n    a = 2 + 2\n    print a\n    5";
+        let updated_def = searcher.graph.graph().definition().unwrap().item;
+        assert_eq!(updated_def.ast.repr(),expected);
+    }
+
+    #[wasm_bindgen_test]
+    fn this_node_from_pattern_subpart() {
+        let fixture = Fixture::new_custom(|data,_| {
+            data.change_main_body(&["Point x y = get_pos","print x"]);
+        });
+        let Fixture{test:_test,searcher,..} = fixture;
+        let graph         = searcher.graph.graph();
+        let (connection,) = graph.connections(&graph).unwrap().connections.expect_tuple();
+        let this          = ThisNode::from_source(&connection.source,&graph).unwrap();
+        assert_eq!(this.id,connection.source.node);
+        assert_eq!(this.var,"x");
+        assert!(!this.needs_to_introduce_pattern);
+    }
+
     #[wasm_bindgen_test]
     fn initialized_data_when_editing_node() {
         let Fixture{test:_test,searcher,entry4,..} = Fixture::new();
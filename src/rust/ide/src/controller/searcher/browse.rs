@@ -0,0 +1,118 @@
+//! The Searcher's browse mode, where the Suggestion Database entries are grouped into a tree of
+//! categories by their modules and types.
+
+use crate::prelude::*;
+
+use crate::controller::searcher::suggestion::Category;
+use crate::controller::searcher::suggestion::Completion;
+use crate::controller::searcher::Suggestion;
+use crate::model::suggestion_database::Entry;
+
+
+
+// ==================
+// === Categories ===
+// ==================
+
+/// Get the path of the category containing the entry: the segments of the entry's module, followed
+/// by the entry's `self_type` if it is a type other than the module itself.
+pub fn category_path_of(entry:&Entry) -> Vec<ImString> {
+    let module_name  = entry.module.to_string();
+    let is_type      = |self_type:&&String| {
+        **self_type != module_name && self_type.as_str() != entry.module.name().as_str()
+    };
+    let module_path  = entry.module.segments().map(|segment| ImString::new(segment.as_str()));
+    let type_segment = entry.self_type.as_ref().filter(is_type).map(ImString::new);
+    module_path.chain(type_segment).collect()
+}
+
+/// Get the content of the category with given path: its direct subcategories in alphabetical
+/// order, followed by the entries placed directly in it.
+pub fn category_content
+(entries:impl IntoIterator<Item=Completion>, path:&[ImString]) -> Vec<Suggestion> {
+    let mut subcategories = Vec::<ImString>::new();
+    let mut direct        = Vec::new();
+    for entry in entries {
+        let entry_path = category_path_of(&entry);
+        if entry_path.len() >= path.len() && entry_path[..path.len()] == *path {
+            match entry_path.get(path.len()) {
+                Some(name) => subcategories.push(name.clone_ref()),
+                None       => direct.push(entry),
+            }
+        }
+    }
+    subcategories.sort_by(|lhs,rhs| lhs.as_str().cmp(rhs.as_str()));
+    subcategories.dedup();
+    let categories = subcategories.into_iter().map(|name| {
+        let path = path.iter().cloned().chain(std::iter::once(name.clone_ref())).collect();
+        Suggestion::Category(Category {name,path:Rc::new(path)})
+    });
+    categories.chain(direct.into_iter().map(Suggestion::Completion)).collect()
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::double_representation::module::QualifiedName;
+    use crate::model::suggestion_database::EntryKind;
+    use crate::model::suggestion_database::Scope;
+
+    fn entry(module:&str, self_type:Option<&str>, name:&str) -> Completion {
+        Rc::new(Entry {
+            name          : name.to_string(),
+            kind          : EntryKind::Method,
+            module        : QualifiedName::from_text(module).unwrap(),
+            arguments     : vec![],
+            return_type   : "Any".to_string(),
+            documentation : None,
+            self_type     : self_type.map(ToString::to_string),
+            scope         : Scope::Everywhere,
+        })
+    }
+
+    fn path(segments:&[&str]) -> Vec<ImString> {
+        segments.iter().map(|segment| ImString::new(*segment)).collect()
+    }
+
+    fn category(segments:&[&str]) -> Suggestion {
+        let path = path(segments);
+        let name = path.last().unwrap().clone_ref();
+        Suggestion::Category(Category {name,path:Rc::new(path)})
+    }
+
+    #[test]
+    fn grouping_entries_into_categories() {
+        let module_method = entry("Base.Data.Text",Some("Base.Data.Text"),"split");
+        let type_method   = entry("Base.Data.Text",Some("Text"),"length");
+        let other_method  = entry("Base.Data.Vector",Some("Vector"),"at");
+        let short_method  = entry("Base.Main",Some("Main"),"print");
+        let entries       = vec![module_method.clone_ref(),type_method.clone_ref(),
+            other_method,short_method.clone_ref()];
+
+        assert_eq!(category_path_of(&module_method),path(&["Base","Data","Text"]));
+        assert_eq!(category_path_of(&type_method),path(&["Base","Data","Text","Text"]));
+        assert_eq!(category_path_of(&short_method),path(&["Base","Main"]));
+
+        let root = category_content(entries.clone(),&[]);
+        assert_eq!(root,vec![category(&["Base"])]);
+        let base = category_content(entries.clone(),&path(&["Base"]));
+        assert_eq!(base,vec![category(&["Base","Data"]),category(&["Base","Main"])]);
+        let data = category_content(entries.clone(),&path(&["Base","Data"]));
+        let expected_data = vec![category(&["Base","Data","Text"]),
+            category(&["Base","Data","Vector"])];
+        assert_eq!(data,expected_data);
+        let text = category_content(entries.clone(),&path(&["Base","Data","Text"]));
+        let expected_text = vec![category(&["Base","Data","Text","Text"]),
+            Suggestion::Completion(module_method)];
+        assert_eq!(text,expected_text);
+        let text_type = category_content(entries,&path(&["Base","Data","Text","Text"]));
+        assert_eq!(text_type,vec![Suggestion::Completion(type_method)]);
+    }
+}
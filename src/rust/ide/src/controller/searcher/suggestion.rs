@@ -10,23 +10,215 @@ use crate::prelude::*;
 /// Suggestion for input completion: possible functions, arguments, etc.
 pub type Completion = Rc<model::suggestion_database::Entry>;
 
+/// A command which can be executed by picking it in the Searcher, like the Graph Editor's
+/// "collapse selected nodes".
+#[derive(Clone,CloneRef,Derivative)]
+#[derivative(Debug)]
+pub struct Action {
+    /// The action's name, displayed in the suggestion list.
+    pub caption  : ImString,
+    /// The description of the keyboard shortcut executing this action, if there is any.
+    pub shortcut : Immutable<Option<ImString>>,
+    #[derivative(Debug="ignore")]
+    execute      : Rc<dyn Fn()>,
+}
+
+impl Action {
+    /// Create an action which calls `execute` when picked.
+    pub fn new
+    (caption:impl Into<ImString>, shortcut:Option<ImString>, execute:impl Fn() + 'static) -> Self {
+        let caption  = caption.into();
+        let shortcut = Immutable(shortcut);
+        let execute  = Rc::new(execute);
+        Action {caption,shortcut,execute}
+    }
+
+    /// Execute the action.
+    pub fn execute(&self) {
+        (self.execute)()
+    }
+}
+
+impl PartialEq for Action {
+    fn eq(&self, other:&Self) -> bool {
+        Rc::ptr_eq(&self.execute,&other.execute)
+    }
+}
+
+impl Eq for Action {}
+
+/// A category of the Searcher's browse mode, grouping the entries by their module or type.
+#[derive(Clone,CloneRef,Debug,Eq,PartialEq)]
+pub struct Category {
+    /// The category name: the last segment of its path.
+    pub name : ImString,
+    /// The full path of the category, starting from the project name.
+    pub path : Rc<Vec<ImString>>,
+}
+
+/// The kind of the literal value suggested in the Searcher.
+#[derive(Clone,CloneRef,Copy,Debug,Eq,PartialEq)]
+pub enum LiteralKind {
+    /// A path of a file in the project.
+    FilePath,
+    /// The default value of the argument being filled.
+    DefaultValue,
+}
+
+/// A literal value suggested for the argument being filled.
+#[derive(Clone,CloneRef,Debug,Eq,PartialEq)]
+pub struct Literal {
+    /// The code inserted when the literal is picked.
+    pub code : ImString,
+    /// What the literal is.
+    pub kind : LiteralKind,
+}
+
+impl Literal {
+    /// Create a literal of the file path, relative to the project's root directory. The quotes and
+    /// backslashes in the path are escaped.
+    pub fn file_path(segments:&[String]) -> Self {
+        let path = segments.join("/");
+        let path = path.replace('\\',"\\\\").replace('"',"\\\"");
+        let code = ImString::new(format!("\"{}\"",path));
+        Literal {code,kind:LiteralKind::FilePath}
+    }
+
+    /// Create a literal of the argument's default value.
+    pub fn default_value(code:impl Into<ImString>) -> Self {
+        let code = code.into();
+        Literal {code,kind:LiteralKind::DefaultValue}
+    }
+}
+
 /// A single suggestion on the Searcher suggestion list.
 #[derive(Clone,CloneRef,Debug,Eq,PartialEq)]
 pub enum Suggestion {
     /// Suggestion for input completion: possible functions, arguments, etc.
-    Completion(Completion)
-    // In future, other suggestion types will be added (like suggestions of actions, etc.).
+    Completion(Completion),
+    /// Suggestion of an action to be executed instead of inserting any code.
+    Action(Action),
+    /// A category which may be expanded in the browse mode.
+    Category(Category),
+    /// A literal value which may be inserted as the argument being filled.
+    Literal(Literal),
 }
 
 impl Suggestion {
     /// The suggestion caption (suggested function name, or action name, etc.).
     pub fn caption(&self) -> String {
         match self {
-            Self::Completion(completion) => completion.code_to_insert(None)
+            Self::Completion(completion) => completion.code_to_insert(None),
+            Self::Action(action)         => action.caption.to_string(),
+            Self::Category(category)     => category.name.to_string(),
+            Self::Literal(literal)       => literal.code.to_string(),
+        }
+    }
+
+    /// The HTML documentation of the suggestion, displayed in the documentation panel when the
+    /// suggestion is highlighted in the Searcher.
+    ///
+    /// For completions, it contains the signature, the return type, the defining module and the
+    /// entry's documentation.
+    pub fn documentation_html(&self) -> String {
+        match self {
+            Self::Completion(completion) => completion_documentation_html(completion),
+            Self::Action(action)         => {
+                let caption  = escape_html(&action.caption);
+                let shortcut = action.shortcut.deref().as_ref().map(|shortcut| {
+                    let shortcut = escape_html(shortcut);
+                    iformat!("<p>Shortcut: <code>{shortcut}</code></p>")
+                });
+                let shortcut = shortcut.unwrap_or_default();
+                iformat!("<h3>{caption}</h3><p>Graph Editor action.</p>{shortcut}")
+            }
+            Self::Category(category) => {
+                let path = escape_html(&category.path.iter().join("."));
+                iformat!("<h3>{path}</h3>")
+            }
+            Self::Literal(literal) => {
+                let code        = escape_html(&literal.code);
+                let description = match literal.kind {
+                    LiteralKind::FilePath     => "A file in the project.",
+                    LiteralKind::DefaultValue => "The default value of the argument.",
+                };
+                iformat!("<h3><code>{code}</code></h3><p>{description}</p>")
+            }
         }
     }
 }
 
+fn completion_documentation_html(completion:&Completion) -> String {
+    let name      = escape_html(&completion.name);
+    let arguments = completion.arguments.iter().map(|argument| {
+        let laziness = if argument.is_suspended { "~" } else { "" };
+        let name     = escape_html(&argument.name);
+        let typ      = escape_html(&argument.repr_type);
+        let default  = argument.default_value.as_ref().map(|value| {
+            let value = escape_html(value);
+            iformat!(" = {value}")
+        }).unwrap_or_default();
+        iformat!("<li><code>{laziness}{name} : {typ}{default}</code></li>")
+    }).join("");
+    let arguments = if arguments.is_empty() { default() } else {
+        iformat!("<ul class=\"arguments\">{arguments}</ul>")
+    };
+    let return_type   = escape_html(&completion.return_type);
+    let module        = escape_html(&completion.module.to_string());
+    let documentation = completion.documentation.clone().unwrap_or_else(|| {
+        "<p>No documentation available</p>".to_string()
+    });
+    iformat!("<div class=\"signature\"><h3>{name}</h3>{arguments}\
+        <p>Returns <code>{return_type}</code></p>\
+        <p>Defined in <code>{module}</code></p></div>{documentation}")
+}
+
+/// Escape the characters having special meaning in HTML.
+fn escape_html(text:&str) -> String {
+    text.replace('&',"&amp;").replace('<',"&lt;").replace('>',"&gt;").replace('"',"&quot;")
+}
+
+
+
+// =================
+// === Relevance ===
+// =================
+
+/// The weight of the logarithm of the pick count in the entry's score.
+const PICK_COUNT_WEIGHT:f32 = 0.5;
+/// The score bonus for a method defined for the `this` argument's type.
+const SELF_TYPE_MATCH_BONUS:f32 = 1.0;
+/// The score bonus for a suggestion returning the type expected by the argument being filled.
+const RETURN_TYPE_MATCH_BONUS:f32 = 0.5;
+/// The score bonus for a suggestion defined in the currently edited module.
+const LOCAL_MODULE_BONUS:f32 = 0.25;
+
+/// The information about the suggestion's relevance in the current context, independent of the
+/// filtering pattern.
+#[derive(Clone,Copy,Debug,Default,PartialEq)]
+pub struct Relevance {
+    /// How many times the user picked the suggestion in this project.
+    pub pick_count          : usize,
+    /// Whether the suggestion is a method of the `this` argument's type.
+    pub self_type_matches   : bool,
+    /// Whether the suggestion returns the type expected in the edited place.
+    pub return_type_matches : bool,
+    /// Whether the suggestion is defined in the currently edited module.
+    pub is_local            : bool,
+}
+
+impl Relevance {
+    /// The score added to the pattern matching score. The pick count has a logarithmic weight, so
+    /// the frequently used suggestions do not dominate the better matching ones.
+    pub fn score(&self) -> f32 {
+        let bonus = |condition:bool, bonus:f32| if condition { bonus } else { 0.0 };
+        let usage = PICK_COUNT_WEIGHT * (self.pick_count as f32).ln_1p();
+        usage + bonus(self.self_type_matches,SELF_TYPE_MATCH_BONUS)
+            + bonus(self.return_type_matches,RETURN_TYPE_MATCH_BONUS)
+            + bonus(self.is_local,LOCAL_MODULE_BONUS)
+    }
+}
+
 
 
 // ==================
@@ -46,34 +238,74 @@ pub enum MatchInfo {
 #[derive(Clone,Debug)]
 pub struct ListEntry {
     pub match_info : MatchInfo,
+    pub relevance  : Relevance,
     pub suggestion : Suggestion,
 }
 
 impl ListEntry {
     /// Update the current match info according to the new filtering pattern.
     pub fn update_matching_info(&mut self, pattern:impl Str) {
-        let matches     = fuzzly::matches(self.suggestion.caption(),pattern.as_ref());
-        let subsequence = matches.and_option_from(|| {
-            let metric = fuzzly::metric::default();
-            fuzzly::find_best_subsequence(self.suggestion.caption(),pattern,metric)
-        });
+        let caption     = self.suggestion.caption();
+        let metric      = fuzzly::metric::default();
+        let subsequence = fuzzly::find_best_subsequence(caption,pattern,metric);
+        self.set_matching_subsequence(subsequence);
+    }
+
+    /// Update the current match info according to the new filtering pattern with some typos fixed.
+    /// The `variants` are the pattern's variants generated by `fuzzly::typo::variants`.
+    pub fn update_matching_info_with_typos
+    (&mut self, pattern:impl Str, variants:&[fuzzly::typo::Variant]) {
+        let caption     = self.suggestion.caption();
+        let metric      = fuzzly::metric::default();
+        let subsequence = fuzzly::find_best_subsequence_with_variants
+            (caption,pattern,variants,metric);
+        self.set_matching_subsequence(subsequence);
+    }
+
+    fn set_matching_subsequence(&mut self, subsequence:Option<fuzzly::Subsequence>) {
         self.match_info = match subsequence {
             Some(subsequence) => MatchInfo::Matches {subsequence},
             None              => MatchInfo::DoesNotMatch,
         };
     }
 
-    /// Compare how two entries matches filtering pattern.
+    /// The entry's score: the pattern matching score increased by the entry's relevance. Returns
+    /// `None` if the entry does not match the pattern.
+    pub fn score(&self) -> Option<f32> {
+        match &self.match_info {
+            MatchInfo::DoesNotMatch          => None,
+            MatchInfo::Matches {subsequence} => Some(subsequence.score + self.relevance.score()),
+        }
+    }
+
+    /// The byte ranges of the entry's caption which match the filtering pattern, merged where
+    /// adjacent. Used to highlight the matched parts of the caption.
+    pub fn matched_byte_ranges(&self) -> Vec<Range<usize>> {
+        match &self.match_info {
+            MatchInfo::DoesNotMatch          => default(),
+            MatchInfo::Matches {subsequence} => {
+                let caption     = self.suggestion.caption();
+                let byte_offset = |char_index| {
+                    let char_offset = caption.char_indices().nth(char_index);
+                    char_offset.map_or(caption.len(), |(byte_offset,_)| byte_offset)
+                };
+                let ranges = subsequence.char_ranges().into_iter();
+                ranges.map(|range| byte_offset(range.start)..byte_offset(range.end)).collect()
+            }
+        }
+    }
+
+    /// Compare how two entries matches filtering pattern, taking their relevance into account.
     ///
-    /// The "greater" entry matches better.
+    /// The "greater" entry matches better. NaN scores are assumed to be the lowest ones.
     pub fn compare_match_scores(&self, rhs:&ListEntry) -> std::cmp::Ordering {
-        use MatchInfo::*;
         use std::cmp::Ordering::*;
-        match (&self.match_info,&rhs.match_info) {
-            (DoesNotMatch             ,DoesNotMatch             ) => Equal,
-            (DoesNotMatch             ,Matches {..}             ) => Less,
-            (Matches {..}             ,DoesNotMatch             ) => Greater,
-            (Matches {subsequence:lhs},Matches {subsequence:rhs}) => lhs.compare_scores(rhs),
+        let valid_score = |entry:&ListEntry| entry.score().map(|score| (!score.is_nan(),score));
+        match (valid_score(self),valid_score(rhs)) {
+            (None,None)           => Equal,
+            (None,Some(_))        => Less,
+            (Some(_),None)        => Greater,
+            (Some(lhs),Some(rhs)) => lhs.partial_cmp(&rhs).unwrap_or(Equal),
         }
     }
 }
@@ -82,7 +314,8 @@ impl From<Suggestion> for ListEntry {
     fn from(suggestion:Suggestion) -> Self {
         let subsequence = default();
         let match_info  = MatchInfo::Matches {subsequence};
-        ListEntry {match_info,suggestion}
+        let relevance   = default();
+        ListEntry {match_info,relevance,suggestion}
     }
 }
 
@@ -131,11 +364,22 @@ impl List {
     /// Update the list filtering.
     ///
     /// The "matching score" of each entry is recalculated against the given pattern and the entries
-    /// are re-ordered, so the best matches will go first.
+    /// are re-ordered, so the best matches will go first. If no entry matches the pattern exactly,
+    /// the entries are matched with the pattern's typos fixed; such matches are penalized by the
+    /// metric's `TypoPenalty`.
     pub fn update_filtering(&self, pattern:impl Str) {
+        let pattern         = pattern.as_ref();
         let mut entries_mut = self.entries.borrow_mut();
         for entry in entries_mut.iter_mut() {
-            entry.update_matching_info(pattern.as_ref());
+            entry.update_matching_info(pattern);
+        }
+        let any_matches = entries_mut.iter().any(|e| e.match_info != MatchInfo::DoesNotMatch);
+        if !any_matches {
+            let max_typos = fuzzly::typo::max_typos(pattern);
+            let variants  = fuzzly::typo::variants(pattern,max_typos);
+            for entry in entries_mut.iter_mut() {
+                entry.update_matching_info_with_typos(pattern,&variants);
+            }
         }
         entries_mut.sort_by(|l,r| l.compare_match_scores(r).reverse());
         let not_matching       = |e:&&ListEntry| e.match_info == MatchInfo::DoesNotMatch;
@@ -144,6 +388,16 @@ impl List {
         self.matching.set(0..matches_end);
     }
 
+    /// Update the relevance of each entry and re-order the list accordingly.
+    pub fn update_relevance(&self, relevance:impl Fn(&Suggestion) -> Relevance) {
+        let mut entries_mut = self.entries.borrow_mut();
+        for entry in entries_mut.iter_mut() {
+            entry.relevance = relevance(&entry.suggestion);
+        }
+        let matches_end = self.matching.get().end;
+        entries_mut[..matches_end].sort_by(|l,r| l.compare_match_scores(r).reverse());
+    }
+
     /// Length of the suggestion list.
     pub fn len(&self) -> usize { self.entries.borrow().len() }
 
@@ -187,3 +441,102 @@ where IntoIter : IntoIterator<Item=Suggestion> {
         Self::from_suggestions(suggestions)
     }
 }
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::double_representation::module::QualifiedName;
+    use crate::model::suggestion_database::Argument;
+    use crate::model::suggestion_database::Entry;
+    use crate::model::suggestion_database::EntryKind;
+    use crate::model::suggestion_database::Scope;
+
+    #[test]
+    fn documentation_of_completion() {
+        let argument = |name:&str, repr_type:&str, default_value:Option<&str>, is_suspended| {
+            Argument {
+                name          : name.to_string(),
+                repr_type     : repr_type.to_string(),
+                default_value : default_value.map(ToString::to_string),
+                is_suspended,
+            }
+        };
+        let entry = Entry {
+            name          : "if_then".to_string(),
+            kind          : EntryKind::Method,
+            module        : QualifiedName::from_text("Base.Data.Boolean").unwrap(),
+            arguments     : vec![
+                argument("this","Boolean",None,false),
+                argument("action","Any",None,true),
+                argument("limit","Vector<Number>",Some("10"),false),
+            ],
+            return_type   : "Any".to_string(),
+            documentation : Some("<p>Runs the action.</p>".to_string()),
+            self_type     : Some("Boolean".to_string()),
+            scope         : Scope::Everywhere,
+        };
+        let html = Suggestion::Completion(Rc::new(entry)).documentation_html();
+        assert!(html.contains("<h3>if_then</h3>"));
+        assert!(html.contains("<li><code>this : Boolean</code></li>"));
+        assert!(html.contains("<li><code>~action : Any</code></li>"));
+        assert!(html.contains("<li><code>limit : Vector&lt;Number&gt; = 10</code></li>"));
+        assert!(html.contains("<p>Returns <code>Any</code></p>"));
+        assert!(html.contains("<p>Defined in <code>Base.Data.Boolean</code></p>"));
+        assert!(html.ends_with("<p>Runs the action.</p>"));
+    }
+
+    #[test]
+    fn filtering_with_typos() {
+        let action   = |caption:&str| Suggestion::Action(Action::new(caption,None,|| {}));
+        let list     = List::from_suggestions(vec![action("label"),action("tab"),action("table")]);
+        let matching = |list:&List| {
+            let entries = list.iter().take(list.matching_count());
+            entries.map(|entry| entry.suggestion.caption().to_string()).collect_vec()
+        };
+        list.update_filtering("tab");
+        assert_eq!(matching(&list).len(),2);
+        assert!(!matching(&list).contains(&"label".to_string()));
+
+        list.update_filtering("tabel");
+        assert_eq!(matching(&list),vec!["table".to_string(),"label".to_string()]);
+    }
+
+    #[test]
+    fn filtering_with_typos_among_exact_matches() {
+        let action   = |caption:&str| Suggestion::Action(Action::new(caption,None,|| {}));
+        let captions = vec!["tab_element","total_label","table"];
+        let list     = List::from_suggestions(captions.into_iter().map(action));
+        list.update_filtering("tabel");
+        let entries  = list.iter().take(list.matching_count());
+        let matching = entries.map(|entry| entry.suggestion.caption().to_string()).collect_vec();
+        // The typos are fixed only if no entry matches exactly.
+        assert_eq!(matching.len(),2);
+        assert!(!matching.contains(&"table".to_string()));
+    }
+
+    #[test]
+    fn escaping_file_path_literals() {
+        let segments = vec!["data".to_string(),"a \"quoted\" \\ name.csv".to_string()];
+        let literal  = Literal::file_path(&segments);
+        assert_eq!(literal.code.as_str(),r#""data/a \"quoted\" \\ name.csv""#);
+    }
+
+    #[test]
+    fn matched_byte_ranges() {
+        let action = Suggestion::Action(Action::new("zażółć gęślą",None,|| {}));
+        let list   = List::from_suggestions(vec![action]);
+        list.update_filtering("zżół");
+        let entry = list.get_cloned(0).unwrap();
+        assert_eq!(entry.matched_byte_ranges(),vec![0..1,2..8]);
+        list.update_filtering("");
+        let entry = list.get_cloned(0).unwrap();
+        assert!(entry.matched_byte_ranges().is_empty());
+    }
+}
@@ -135,9 +135,10 @@ impl Handle {
     async fn map_module_notification
     (notification:model::module::Notification) -> Option<Notification> {
         match notification {
-            model::module::Notification::Invalidate      |
-            model::module::Notification::CodeChanged{..} => Some(Notification::Invalidate),
-            model::module::Notification::MetadataChanged => None,
+            model::module::Notification::Invalidate        |
+            model::module::Notification::CodeChanged{..}   => Some(Notification::Invalidate),
+            model::module::Notification::MetadataChanged   |
+            model::module::Notification::ExternalChange(_) => None,
         }
     }
 }
@@ -6,8 +6,10 @@
 use crate::prelude::*;
 
 use crate::constants::VISUALIZATION_DIRECTORY;
+use crate::notification;
 
 use enso_protocol::language_server;
+use flo_stream::Subscriber;
 use ide_view::graph_editor::data;
 use ide_view::graph_editor::component::visualization::definition;
 use ide_view::graph_editor::component::visualization;
@@ -67,6 +69,45 @@ pub struct EmbeddedVisualizations {
 
 
 
+// ====================
+// === Notification ===
+// ====================
+
+/// Notification about the change of available visualizations.
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum Notification {
+    /// Some files in the project's visualization directory were added, removed or modified.
+    FilesChanged,
+}
+
+
+
+// ===================
+// === FileWatcher ===
+// ===================
+
+/// Notifies the visualization controller's subscribers about changes of the visualization files.
+///
+/// Unlike the controller, it does not keep the Language Server connection alive, so it may be
+/// passed to the Language Server's event handlers.
+#[derive(Clone,CloneRef,Debug,Default)]
+pub struct FileWatcher {
+    notifications : notification::Publisher<Notification>,
+}
+
+impl FileWatcher {
+    /// Handle the Language Server's notification about a changed file. The subscribers are
+    /// notified if the file is in the project's visualization directory.
+    pub fn handle_file_event(&self, event:&language_server::FileEvent) {
+        let directory = event.path.segments.first().map(String::as_str);
+        if directory == Some(VISUALIZATION_DIRECTORY) {
+            self.notifications.notify(Notification::FilesChanged);
+        }
+    }
+}
+
+
+
 // ==============
 // === Handle ===
 // ==============
@@ -76,7 +117,8 @@ pub struct EmbeddedVisualizations {
 #[derive(Debug,Clone,CloneRef)]
 pub struct Handle {
     language_server_rpc     : Rc<language_server::Connection>,
-    embedded_visualizations : Rc<RefCell<EmbeddedVisualizations>>
+    embedded_visualizations : Rc<RefCell<EmbeddedVisualizations>>,
+    file_watcher            : FileWatcher,
 }
 
 impl Handle {
@@ -85,7 +127,18 @@ impl Handle {
     ( language_server_rpc     : Rc<language_server::Connection>
     , embedded_visualizations : EmbeddedVisualizations) -> Self {
         let embedded_visualizations = Rc::new(RefCell::new(embedded_visualizations));
-        Self {language_server_rpc,embedded_visualizations}
+        let file_watcher            = default();
+        Self {language_server_rpc,embedded_visualizations,file_watcher}
+    }
+
+    /// Get the watcher which should be informed about the changes of the project's files.
+    pub fn file_watcher(&self) -> FileWatcher {
+        self.file_watcher.clone_ref()
+    }
+
+    /// Subscribe to the notifications about the change of available visualizations.
+    pub fn subscribe(&self) -> Subscriber<Notification> {
+        self.file_watcher.notifications.subscribe()
     }
 
     async fn list_project_specific_visualizations
@@ -455,6 +455,25 @@ impl Info {
         Ok(())
     }
 
+    /// Remove the method definition with the given name from the module.
+    ///
+    /// The blank line separating the method from its neighbour (if present) is removed as well, so
+    /// this reverts the `add_method` operation.
+    pub fn remove_method(&mut self, name:&definition::Crumb) -> FallibleResult<()> {
+        let index = locate_line_with(&self.ast,name)?.line_index;
+        self.remove_line(index)?;
+        let lines        = &self.ast.lines;
+        let is_blank     = |index:usize| lines.get(index).map_or(false,|line| line.elem.is_none());
+        let blank_after  = is_blank(index);
+        let blank_before = index > 0 && is_blank(index - 1);
+        if blank_after {
+            self.remove_line(index)?;
+        } else if blank_before {
+            self.remove_line(index - 1)?;
+        }
+        Ok(())
+    }
+
     #[cfg(test)]
     pub fn expect_code(&self,expected_code:impl AsRef<str>) {
         assert_eq!(self.ast.repr(),expected_code.as_ref());
@@ -841,4 +860,16 @@ main = here.method1 10"#;
         //  due to the way that parser assigns blank lines to the former block rather than module.
         //  If anyone will care, we might revisit this after the parser 2.0 rewrite.
     }
+
+    #[wasm_bindgen_test]
+    fn remove_method() {
+        let parser = parser::Parser::new_or_panic();
+        let code   = "foo a = a + 1\n\nbar = 5\n\nmain = here.bar";
+        let mut module = Info::from(parser.parse_module(code,default()).unwrap());
+        module.remove_method(&DefinitionName::new_plain("bar")).unwrap();
+        module.expect_code("foo a = a + 1\n\nmain = here.bar");
+        module.remove_method(&DefinitionName::new_plain("main")).unwrap();
+        module.expect_code("foo a = a + 1");
+        assert!(module.remove_method(&DefinitionName::new_plain("bar")).is_err());
+    }
 }
@@ -1,5 +1,8 @@
 //! Module contains refactorings implemented on the IDE side.
 
 pub mod collapse;
+pub mod expand;
+pub mod rename;
 
 pub use collapse::collapse;
+pub use expand::expand;
@@ -5,11 +5,13 @@
 use crate::prelude::*;
 
 use crate::constants::keywords::HERE;
+use crate::double_representation::alias_analysis;
 use crate::double_representation::connection::Connection;
 use crate::double_representation::connection::Endpoint;
 use crate::double_representation::definition::DefinitionInfo;
 use crate::double_representation::definition;
 use crate::double_representation::identifier::Identifier;
+use crate::double_representation::identifier::generate_name;
 use crate::double_representation::node;
 use crate::double_representation::node::NodeInfo;
 use crate::double_representation::graph::GraphInfo;
@@ -21,6 +23,16 @@ use std::collections::BTreeSet;
 
 
 
+// =================
+// === Constants ===
+// =================
+
+/// The base of the name for the variable storing all values returned from the extracted method,
+/// when there is more than one.
+pub const RESULT_VARIABLE_BASE:&str = "result";
+
+
+
 // ====================
 // === Collapse API ===
 // ====================
@@ -33,8 +45,10 @@ use std::collections::BTreeSet;
 /// method definition. In place of them a new node shall be placed that invokes the method.
 ///
 /// Any connections incoming into the extracted nodes shall be translated into the method arguments.
-/// Any connections leaving the extracted nodes shall be treated as function outputs. Currently only
-/// one output is supported, so an extracted function can return at most one unique identifier.
+/// Any connections leaving the extracted nodes shall be treated as function outputs. If there is
+/// more than one output, the extracted function returns a vector of their values. In such case the
+/// collapsed node binds the vector to a new variable and each output is extracted from it by a
+/// separate accessor node, like `c = result1.at 0`.
 pub fn collapse
 ( graph          : &GraphInfo
 , selected_nodes : impl IntoIterator<Item=node::Id>
@@ -82,12 +96,6 @@ pub struct CannotConstructCollapsedNode;
 #[derive(Clone,Debug,Fail)]
 pub struct EndpointIdentifierCannotBeResolved(Endpoint);
 
-#[allow(missing_docs)]
-#[derive(Clone,Debug,Fail)]
-#[fail(display="Currently collapsing nodes is supported only when there would be at most one \
-output from the collapsed function. Found more than one output: `{}` and `{}`.",_0,_1)]
-pub struct MultipleOutputIdentifiers(String,String);
-
 
 
 // ===================
@@ -139,9 +147,11 @@ impl GraphHelper {
         let mut new_lines          = Vec::new();
         for line in updated_definition.block_lines()? {
             match line_rewriter(&line)? {
-                LineDisposition::Keep         => new_lines.push(line),
-                LineDisposition::Remove       => {},
-                LineDisposition::Replace(ast) => new_lines.push(BlockLine::new(Some(ast)))
+                LineDisposition::Keep          => new_lines.push(line),
+                LineDisposition::Remove        => {},
+                LineDisposition::Replace(asts) => {
+                    new_lines.extend(asts.into_iter().map(|ast| BlockLine::new(Some(ast))))
+                }
             }
         };
         updated_definition.set_block_lines(new_lines)?;
@@ -160,10 +170,10 @@ impl GraphHelper {
 pub struct Extracted {
     /// Identifiers used in the collapsed nodes from the outside scope.
     inputs : Vec<Identifier>,
-    /// The identifier from the extracted nodes that is used outside.
-    /// Currently we allow at most one, to be revisited in the future.
-    output : Option<Identifier>,
-    /// The node that introduces output variable.
+    /// The identifiers from the extracted nodes that are used outside, in the order of the lines
+    /// introducing them.
+    outputs : Vec<Identifier>,
+    /// The first node that introduces an output variable.
     output_node : Option<node::Id>,
     /// Nodes that are being collapsed and extracted into a separate method.
     extracted_nodes : Vec<NodeInfo>,
@@ -180,9 +190,8 @@ impl Extracted {
             extracted_nodes_set.contains(&node.id())
         }).cloned().collect();
 
-        let mut output_node = None;
-        let mut inputs      = Vec::new();
-        let mut output      = None;
+        let mut inputs  = Vec::new();
+        let mut outputs = Vec::new();
         for connection in graph.info.connections() {
             let starts_inside = extracted_nodes_set.contains(&connection.source.node);
             let ends_inside   = extracted_nodes_set.contains(&connection.destination.node);
@@ -190,22 +199,18 @@ impl Extracted {
             if !starts_inside && ends_inside {
                 inputs.push(identifier)
             } else if starts_inside && !ends_inside {
-                match output {
-                    Some(previous_identifier) if identifier != previous_identifier => {
-                        let ident1 = identifier.to_string();
-                        let ident2 = previous_identifier.to_string();
-                        return Err(MultipleOutputIdentifiers(ident1,ident2).into())
-                    }
-                    Some(_) => {} // Ignore duplicate usage of the same identifier.
-                    None    => {
-                        output      = Some(identifier);
-                        output_node = Some(connection.source.node)
-                    }
+                let is_duplicate = outputs.iter().any(|(output,_)| *output == identifier);
+                if !is_duplicate {
+                    outputs.push((identifier,connection.source.node));
                 }
             }
         };
+        let line_index = |id:&node::Id| extracted_nodes.iter().position(|node| node.id() == *id);
+        outputs.sort_by_key(|(_,node)| line_index(node));
+        let output_node = outputs.first().map(|(_,node)| *node);
+        let outputs     = outputs.into_iter().map(|(identifier,_)| identifier).collect();
 
-        Ok(Self {extracted_nodes_set,extracted_nodes,inputs,output,output_node})
+        Ok(Self {extracted_nodes_set,extracted_nodes,inputs,outputs,output_node})
     }
 
     /// Check if the given node belongs to the selection (i.e. is extracted into a new method).
@@ -215,21 +220,29 @@ impl Extracted {
 
     /// Generate AST of a line that needs to be appended to the extracted nodes' Asts.
     /// None if there is no such need.
-    pub fn return_line(&self) -> Option<Ast> {
-        // To return value we just utter its identifier.
-        self.output.clone().map(Into::into)
+    pub fn return_line(&self, parser:&Parser) -> FallibleResult<Option<Ast>> {
+        match self.outputs.as_slice() {
+            [] => Ok(None),
+            // To return value we just utter its identifier.
+            [output] => Ok(Some(output.clone().into())),
+            // Multiple values are returned as a vector.
+            outputs => {
+                let elements = outputs.iter().map(|output| output.name()).join(", ");
+                Ok(Some(parser.parse_line(iformat!("[{elements}]"))?))
+            }
+        }
     }
 
     /// Generate the description for the new method's definition with the extracted nodes.
-    pub fn generate(&self, name:Identifier) -> definition::ToAdd {
+    pub fn generate(&self, name:Identifier, parser:&Parser) -> FallibleResult<definition::ToAdd> {
         let name                     = definition::DefinitionName::new_plain(name);
         let inputs                   = self.inputs.iter().collect::<BTreeSet<_>>();
-        let return_line              = self.return_line();
+        let return_line              = self.return_line(parser)?;
         let mut selected_nodes_iter  = self.extracted_nodes.iter().map(|node| node.ast().clone());
         let body_head                = selected_nodes_iter.next().unwrap();
         let body_tail                = selected_nodes_iter.chain(return_line).map(Some).collect();
         let explicit_parameter_names = inputs.iter().map(|input| input.name().into()).collect();
-        definition::ToAdd {name,explicit_parameter_names,body_head,body_tail}
+        Ok(definition::ToAdd {name,explicit_parameter_names,body_head,body_tail})
     }
 }
 
@@ -246,7 +259,7 @@ impl Extracted {
 pub enum LineDisposition {
     Keep,
     Remove,
-    Replace(Ast)
+    Replace(Vec<Ast>)
 }
 
 /// Helper type that stores some common data used for collapsing algorithm and implements its logic.
@@ -262,6 +275,9 @@ pub struct Collapser {
     parser : Parser,
     /// Identifier of the node to be introduced as a result of collapsing.
     collapsed_node : node::Id,
+    /// The variable storing the vector of outputs returned by the extracted method. Present only
+    /// if there is more than one output.
+    result_variable : Option<Identifier>,
 }
 
 impl Collapser {
@@ -270,17 +286,26 @@ impl Collapser {
     pub fn new
     (graph:GraphInfo, selected_nodes:impl IntoIterator<Item=node::Id>, parser:Parser)
     -> FallibleResult<Self> {
-        let graph          = GraphHelper::new(graph);
-        let extracted      = Extracted::new(&graph,selected_nodes)?;
-        let last_selected  = extracted.extracted_nodes.iter().last().ok_or(NoNodesSelected)?.id();
-        let replaced_node  = extracted.output_node.unwrap_or(last_selected);
-        let collapsed_node = node::Id::new_v4();
+        let graph           = GraphHelper::new(graph);
+        let extracted       = Extracted::new(&graph,selected_nodes)?;
+        let last_selected   = extracted.extracted_nodes.iter().last().ok_or(NoNodesSelected)?.id();
+        let replaced_node   = extracted.output_node.unwrap_or(last_selected);
+        let collapsed_node  = node::Id::new_v4();
+        let result_variable = if extracted.outputs.len() > 1 {
+            let body       = graph.info.source.body();
+            let used_names = alias_analysis::analyze_crumbable(body.item).all_identifiers();
+            let used_names = used_names.into_iter().map(|name| name.item);
+            Some(generate_name(RESULT_VARIABLE_BASE,used_names)?)
+        } else {
+            None
+        };
         Ok(Collapser {
             graph,
             extracted,
             replaced_node,
             parser,
             collapsed_node,
+            result_variable,
         })
     }
 
@@ -300,8 +325,9 @@ impl Collapser {
     /// Assign to a line from refactored definition one of 3 dispositions:
     /// 1) Lines that are kept intact -- not belonging to selected nodes;
     /// 2) Lines that are extracted and removed -- all selected nodes, except:
-    /// 3) Line that introduces output of the extracted function (if present at all) -> its
-    ///    expression shall be replaced with a call to the extracted function.
+    /// 3) Line that introduces the first output of the extracted function (if present at all) ->
+    ///    its expression shall be replaced with a call to the extracted function. If there are
+    ///    multiple outputs, the call is followed by the lines extracting each of them.
     ///    If there is no usage of the extracted function output, its invocation should be placed
     ///    in place of the last extracted line.
     pub fn rewrite_line
@@ -319,10 +345,25 @@ impl Collapser {
             let no_node_err  = failure::Error::from(CannotConstructCollapsedNode);
             let mut new_node = NodeInfo::new_expression(expression.clone_ref()).ok_or(no_node_err)?;
             new_node.set_id(self.collapsed_node);
-            if let Some(output_var) = self.extracted.output.clone() {
-                new_node.set_pattern(output_var.into())
+            let mut lines = Vec::new();
+            match (&self.result_variable,self.extracted.outputs.as_slice()) {
+                (Some(result),outputs) => {
+                    new_node.set_pattern(result.clone().into());
+                    lines.push(new_node.ast().clone_ref());
+                    for (index,output) in outputs.iter().enumerate() {
+                        let result = result.name();
+                        let output = output.name();
+                        let line   = iformat!("{output} = {result}.at {index}");
+                        lines.push(self.parser.parse_line(line)?);
+                    }
+                }
+                (None,[output]) => {
+                    new_node.set_pattern(output.clone().into());
+                    lines.push(new_node.ast().clone_ref());
+                }
+                (None,_) => lines.push(new_node.ast().clone_ref()),
             }
-            Ok(LineDisposition::Replace(new_node.ast().clone_ref()))
+            Ok(LineDisposition::Replace(lines))
         } else {
             Ok(LineDisposition::Remove)
         }
@@ -330,7 +371,7 @@ impl Collapser {
 
     /// Run the collapsing refactoring on this input.
     pub fn collapse(&self,name:Identifier) -> FallibleResult<Collapsed> {
-        let new_method         = self.extracted.generate(name);
+        let new_method         = self.extracted.generate(name,&self.parser)?;
         let updated_definition = self.graph.rewrite_definition(|line| {
             self.rewrite_line(line,&new_method)
         })?;
@@ -478,5 +519,28 @@ mod tests {
     c = here.custom_new
     c + c + 10";
         case.run(&parser);
+
+        // Check that when there are multiple outputs:
+        // 1) the generated method returns a vector with their values, in the order of lines;
+        // 2) the invocation is bound to a new variable, from which the outputs are extracted.
+        case.initial_method_code = r"custom_old =
+    a = 1
+    b = 2
+    c = A + B
+    d = a + b
+    c + d";
+        case.extracted_lines    = 1..4;
+        case.expected_generated = r"custom_new a =
+    b = 2
+    c = A + B
+    d = a + b
+    [c, d]";
+        case.expected_refactored = r"custom_old =
+    a = 1
+    result1 = here.custom_new a
+    c = result1.at 0
+    d = result1.at 1
+    c + d";
+        case.run(&parser);
     }
 }
@@ -0,0 +1,372 @@
+//! Module with logic for node expanding, the inverse of the node collapsing.
+//!
+//! See the [`expand`] function for details.
+
+use crate::prelude::*;
+
+use crate::constants::keywords::HERE;
+use crate::double_representation::alias_analysis;
+use crate::double_representation::definition::DefinitionInfo;
+use crate::double_representation::definition::DefinitionName;
+use crate::double_representation::graph::GraphInfo;
+use crate::double_representation::identifier::NormalizedName;
+use crate::double_representation::identifier::generate_name;
+use crate::double_representation::module;
+use crate::double_representation::node;
+use crate::double_representation::node::NodeInfo;
+
+use ast::BlockLine;
+use ast::known;
+
+
+
+// ==================
+// === Expand API ===
+// ==================
+
+// === Entry point ===
+
+/// Run the "expand node" refactoring. Generates output describing how to apply the refactoring.
+///
+/// "Expanding node" means replacing a node that calls a method with the lines of the method's
+/// body. It is an inverse of the "collapse nodes" refactoring.
+///
+/// The method's parameters are substituted with the call arguments. The arguments that are not
+/// plain identifiers are bound to new variables first. All variables introduced by the inlined
+/// lines get fresh names not present in `unavailable_names`, so they won't collide with the
+/// identifiers used in the graph. The last line of the method's body replaces the expanded node,
+/// keeping its ID and pattern.
+pub fn expand
+( graph             : &GraphInfo
+, node_id           : node::Id
+, method            : &DefinitionInfo
+, unavailable_names : impl IntoIterator<Item=NormalizedName>
+) -> FallibleResult<Expanded> {
+    Expander::new(graph.clone(),node_id,unavailable_names)?.expand(method)
+}
+
+
+// === Expanded ===
+
+/// Result of running node expand algorithm. Describes update to the refactored definition.
+#[derive(Clone,Debug)]
+pub struct Expanded {
+    /// New contents of the refactored definition.
+    pub updated_definition : DefinitionInfo,
+    /// Nodes placed in the refactored definition in place of the expanded node.
+    pub expanded_nodes : Vec<ExpandedNode>,
+}
+
+/// A node introduced by the node expanding.
+#[derive(Clone,Copy,Debug)]
+pub struct ExpandedNode {
+    /// Identifier of the node in the refactored definition.
+    pub id : node::Id,
+    /// Identifier of the node in the inlined method's body that this node was created from.
+    /// `None` for nodes binding the call arguments.
+    pub source : Option<node::Id>,
+}
+
+
+// === Errors ===
+
+#[allow(missing_docs)]
+#[derive(Clone,Debug,Fail)]
+#[fail(display="The expression `{}` is not a call to a method defined in this module.",_0)]
+pub struct NotAMethodCall(String);
+
+#[allow(missing_docs)]
+#[derive(Clone,Debug,Fail)]
+#[fail(display="Cannot inline the method `{}`: expected {} arguments, found {}.",name,expected,
+found)]
+pub struct ArgumentCountMismatch {
+    name     : String,
+    expected : usize,
+    found    : usize,
+}
+
+#[allow(missing_docs)]
+#[derive(Clone,Debug,Fail)]
+#[fail(display="Cannot inline the method with a non-variable parameter `{}`.",_0)]
+pub struct UnsupportedParameter(String);
+
+#[allow(missing_docs)]
+#[derive(Clone,Debug,Fail)]
+#[fail(display="Cannot inline the method `{}`, as its body does not end with a value.",_0)]
+pub struct NoReturnValue(String);
+
+#[allow(missing_docs)]
+#[derive(Clone,Copy,Debug,Fail)]
+#[fail(display="Internal refactoring error: Cannot generate expanded node description.")]
+pub struct CannotConstructExpandedNode;
+
+
+
+// ==================
+// === MethodCall ===
+// ==================
+
+/// Description of the node's expression calling a method defined in the same module, like
+/// `here.foo a b` or `foo a b`.
+#[derive(Clone,Debug)]
+pub struct MethodCall {
+    /// The name of the called method.
+    pub name : DefinitionName,
+    /// The arguments given to the method, left-to-right.
+    pub args : Vec<Ast>,
+}
+
+impl MethodCall {
+    /// Try interpreting the given expression as a method call.
+    pub fn new(expression:&Ast) -> Option<Self> {
+        let chain = ast::prefix::Chain::from_ast_non_strict(expression);
+        let func  = match ast::opr::to_access(&chain.func) {
+            Some(access) if ast::identifier::name(&access.larg) == Some(HERE) => access.rarg,
+            Some(_)                                                          => return None,
+            None                                                             => chain.func,
+        };
+        let name = known::Var::try_from(&func).ok()?.name.clone();
+        let name = DefinitionName::new_plain(name);
+        let args = chain.args.into_iter().map(|arg| arg.sast.wrapped).collect();
+        Some(MethodCall {name,args})
+    }
+
+    /// Like `new`, but fails with the error describing the expression if it is not a method call.
+    pub fn try_new(expression:&Ast) -> FallibleResult<Self> {
+        Self::new(expression).ok_or_else(|| NotAMethodCall(expression.repr()).into())
+    }
+}
+
+
+
+// ================
+// === Expander ===
+// ================
+
+/// Helper type that stores some common data used for expanding algorithm and implements its logic.
+#[derive(Clone,Debug)]
+pub struct Expander {
+    /// The graph of definition where the node expanding takes place.
+    graph : GraphInfo,
+    /// The node being expanded.
+    node : NodeInfo,
+    /// The call expression of the expanded node.
+    call : MethodCall,
+    /// Names that must not be introduced by the inlined lines.
+    unavailable_names : Vec<NormalizedName>,
+    /// The identifiers to be substituted in the inlined lines: parameters with their arguments and
+    /// the renamed variables with their new names.
+    substitutions : HashMap<NormalizedName,Ast>,
+}
+
+impl Expander {
+    /// Does some early pre-processing and gathers common data used in various parts of the
+    /// refactoring algorithm.
+    pub fn new
+    (graph:GraphInfo, node_id:node::Id, unavailable_names:impl IntoIterator<Item=NormalizedName>)
+    -> FallibleResult<Self> {
+        let node              = graph.find_node(node_id).ok_or(node::IdNotFound {id:node_id})?;
+        let call              = MethodCall::try_new(node.expression())?;
+        let unavailable_names = unavailable_names.into_iter().collect();
+        let substitutions     = default();
+        Ok(Expander {graph,node,call,unavailable_names,substitutions})
+    }
+
+    /// Generate a new variable name, not colliding with any of unavailable names.
+    fn fresh_name(&mut self, old_name:&str) -> FallibleResult<Ast> {
+        let base = old_name.trim_end_matches(|c:char| c.is_ascii_digit());
+        let name = generate_name(base,self.unavailable_names.iter().cloned())?;
+        self.unavailable_names.push(name.normalized());
+        Ok(Ast::from(name.as_var()?))
+    }
+
+    /// Prepare substitutions for the method's parameters. Returns the lines binding the arguments
+    /// that cannot be substituted directly.
+    fn bind_arguments(&mut self, method:&DefinitionInfo) -> FallibleResult<Vec<NodeInfo>> {
+        let expected = method.args.len();
+        let found    = self.call.args.len();
+        if expected != found {
+            let name = method.name.item.to_string();
+            return Err(ArgumentCountMismatch {name,expected,found}.into())
+        }
+        let mut bindings = Vec::new();
+        for (parameter,argument) in method.args.iter().zip(self.call.args.clone()) {
+            let err       = || UnsupportedParameter(parameter.item.repr());
+            let parameter = ast::identifier::name(&parameter.item).ok_or_else(err)?;
+            let value     = if known::Var::try_from(&argument).is_ok() {
+                argument
+            } else {
+                let variable    = self.fresh_name(parameter)?;
+                let binding     = NodeInfo::new_expression(argument);
+                let mut binding = binding.ok_or(CannotConstructExpandedNode)?;
+                binding.set_id(node::Id::new_v4());
+                binding.set_pattern(variable.clone_ref());
+                bindings.push(binding);
+                variable
+            };
+            self.substitutions.insert(NormalizedName::new(parameter),value);
+        }
+        Ok(bindings)
+    }
+
+    /// Substitute the parameters and renamed variables in the given method's line and give fresh
+    /// names to the variables it introduces.
+    fn inline_line(&mut self, line:&NodeInfo) -> FallibleResult<NodeInfo> {
+        let usage   = alias_analysis::analyze_node(line);
+        let mut ast = line.ast().clone_ref();
+        for used in usage.used {
+            if let Some(substitute) = self.substitutions.get(&used.item) {
+                ast = ast.set_traversing(&used.crumbs,substitute.clone_ref())?;
+            }
+        }
+        for introduced in usage.introduced {
+            let old_name = ast.get_traversing(&introduced.crumbs)?.repr();
+            let new_name = self.fresh_name(&old_name)?;
+            ast = ast.set_traversing(&introduced.crumbs,new_name.clone_ref())?;
+            self.substitutions.insert(introduced.item,new_name);
+        }
+        NodeInfo::from_line_ast(&ast).ok_or_else(|| CannotConstructExpandedNode.into())
+    }
+
+    /// Generate the nodes replacing the expanded node, paired with the ids of their sources in
+    /// the method's body.
+    fn expanded_nodes
+    (&mut self, method:&DefinitionInfo) -> FallibleResult<Vec<(NodeInfo,Option<node::Id>)>> {
+        let method_name = method.name.item.to_string();
+        let mut body    = GraphInfo::from_definition(method.clone()).nodes();
+        let last_line   = body.pop().ok_or_else(|| NoReturnValue(method_name.clone()))?;
+        let bindings    = self.bind_arguments(method)?;
+        let mut nodes   = bindings.into_iter().map(|binding| (binding,None)).collect_vec();
+        for line in &body {
+            let mut inlined = self.inline_line(line)?;
+            inlined.set_id(node::Id::new_v4());
+            nodes.push((inlined,Some(line.id())));
+        }
+        let mut result = self.inline_line(&last_line)?;
+        match (result.pattern().is_some(),self.node.pattern()) {
+            (true,Some(_)) => return Err(NoReturnValue(method_name).into()),
+            (true,None)    => result.set_id(node::Id::new_v4()),
+            (false,pattern) => {
+                if let Some(pattern) = pattern {
+                    result.set_pattern(pattern.clone_ref());
+                }
+                result.set_id(self.node.id());
+            }
+        }
+        nodes.push((result,Some(last_line.id())));
+        Ok(nodes)
+    }
+
+    /// Run the expanding refactoring for the given called method's definition.
+    pub fn expand(&mut self, method:&DefinitionInfo) -> FallibleResult<Expanded> {
+        let nodes     = self.expanded_nodes(method)?;
+        let mut lines = self.graph.source.block_lines()?;
+        let index     = node::index_in_lines(&lines,self.node.id())?;
+        let new_lines = nodes.iter().map(|(node,_)| BlockLine::new(Some(node.ast().clone_ref())));
+        lines.splice(index..=index,new_lines);
+        let mut updated_definition = self.graph.source.clone();
+        updated_definition.set_block_lines(lines)?;
+        let expanded_nodes = nodes.iter().map(|(node,source)| {
+            ExpandedNode {id:node.id(),source:*source}
+        }).collect();
+        Ok(Expanded {updated_definition,expanded_nodes})
+    }
+}
+
+
+
+// =================
+// === Utilities ===
+// =================
+
+/// Check if the module's method with the given name is referred to anywhere in the module, except
+/// its own definition.
+pub fn is_method_used(ast:&known::Module, name:&DefinitionName) -> bool {
+    let definition_line = module::locate_line_with(ast,name).ok().map(|crumb| crumb.line_index);
+    let is_usage        = |ast:&Ast| ast::identifier::name(ast) == Some(name.name.item.as_str());
+    ast.lines.iter().enumerate().any(|(index,line)| {
+        let is_definition = Some(index) == definition_line;
+        let mut subtrees  = line.elem.iter().flat_map(|ast| ast.iter_recursive());
+        !is_definition && subtrees.any(is_usage)
+    })
+}
+
+
+
+// ============
+// === Test ===
+// ============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::double_representation::graph;
+
+    use parser::Parser;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    struct Case {
+        code              : &'static str,
+        expanded_line     : usize,
+        expected_expanded : &'static str,
+    }
+
+    impl Case {
+        fn run(&self, parser:&Parser) {
+            let ast        = parser.parse_module(self.code,default()).unwrap();
+            let main       = module::locate_child(&ast,&DefinitionName::new_plain("main")).unwrap();
+            let graph      = graph::GraphInfo::from_definition(main.item.clone());
+            let node       = graph.nodes()[self.expanded_line].clone();
+            let call       = MethodCall::new(node.expression()).unwrap();
+            let method     = module::locate_child(&ast,&call.name).unwrap();
+            let used_names = alias_analysis::analyze_crumbable(main.item.body().item);
+            let used_names = used_names.all_identifiers().into_iter().map(|name| name.item);
+            let expanded   = expand(&graph,node.id(),&method.item,used_names).unwrap();
+            assert_eq!(expanded.updated_definition.ast.repr(),self.expected_expanded);
+            let last = expanded.expanded_nodes.last().unwrap();
+            assert_eq!(last.id,node.id());
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn test_expand() {
+        let parser = Parser::new_or_panic();
+        let code   = r"foo a b =
+    sum = a + b
+    sum * 2
+
+main =
+    a = 1
+    sum = 2
+    x = here.foo sum 3
+    x + a";
+        let expected_expanded = r"main =
+    a = 1
+    sum = 2
+    b1 = 3
+    sum1 = sum + b1
+    x = sum1 * 2
+    x + a";
+        Case {code,expanded_line:2,expected_expanded}.run(&parser);
+
+        let code = r"inc a = a + 1
+
+main =
+    x = 5
+    inc x";
+        let expected_expanded = r"main =
+    x = 5
+    x + 1";
+        Case {code,expanded_line:1,expected_expanded}.run(&parser);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_method_usage() {
+        let parser = Parser::new_or_panic();
+        let code   = "foo a = a + 1\n\nbar = 5\n\nmain =\n    here.bar";
+        let ast    = parser.parse_module(code,default()).unwrap();
+        assert!(!is_method_used(&ast,&DefinitionName::new_plain("foo")));
+        assert!(is_method_used(&ast,&DefinitionName::new_plain("bar")));
+    }
+}
@@ -0,0 +1,275 @@
+//! Module with logic for renaming variables and methods.
+//!
+//! See the [`rename_variable`] and [`rename_method`] functions for details.
+
+use crate::prelude::*;
+
+use crate::constants::keywords::HERE;
+use crate::double_representation::alias_analysis;
+use crate::double_representation::definition::DefinitionInfo;
+use crate::double_representation::definition::DefinitionName;
+use crate::double_representation::graph::GraphInfo;
+use crate::double_representation::identifier::Identifier;
+use crate::double_representation::identifier::NormalizedName;
+use crate::double_representation::module;
+use crate::double_representation::node;
+use crate::double_representation::node::NodeInfo;
+
+use ast::crumbs::Crumb;
+use ast::crumbs::Crumbable;
+use ast::crumbs::InfixCrumb;
+use ast::known;
+
+
+
+// ==============
+// === Errors ===
+// ==============
+
+#[allow(missing_docs)]
+#[derive(Clone,Debug,Fail)]
+#[fail(display="`{}` is not a valid variable name.",_0)]
+pub struct NotAVariableName(String);
+
+#[allow(missing_docs)]
+#[derive(Clone,Debug,Fail)]
+#[fail(display="The name `{}` is already used in this scope.",_0)]
+pub struct NameAlreadyUsed(pub String);
+
+#[allow(missing_docs)]
+#[derive(Clone,Copy,Debug,Fail)]
+#[fail(display="The pattern of node {} is not a single variable, so it cannot be renamed.",node)]
+pub struct PatternIsNotVariable {
+    pub node : node::Id,
+}
+
+
+
+// =========================
+// === Variable Renaming ===
+// =========================
+
+/// Rename the variable introduced by the pattern of the given node to `new_name`.
+///
+/// Both the pattern and all usages of the variable in the following nodes are updated, so the
+/// connections in the graph are kept. Fails if the node's pattern is not a single variable, if
+/// `new_name` is not a valid variable name or if it is already used in the graph, as introducing
+/// it could shadow another variable or change what the existing usages refer to.
+pub fn rename_variable
+(graph:&GraphInfo, node_id:node::Id, new_name:&str) -> FallibleResult<DefinitionInfo> {
+    let new_var    = variable(new_name)?;
+    let mut lines  = graph.source.block_lines()?;
+    let node_index = node::index_in_lines(&lines,node_id)?;
+    let node       = lines[node_index].elem.as_ref().and_then(NodeInfo::from_line_ast);
+    let pattern    = node.as_ref().and_then(|node| node.pattern());
+    let pattern    = pattern.and_then(|pattern| known::Var::try_from(pattern).ok());
+    let old_name   = pattern.ok_or(PatternIsNotVariable {node:node_id})?;
+    let old_name   = NormalizedName::new(&old_name.name);
+    if old_name == NormalizedName::new(new_name) {
+        return Ok(graph.source.clone())
+    }
+    ensure_name_not_used(graph,new_name)?;
+
+    let mut is_in_scope = true;
+    for (index,line) in lines.iter_mut().enumerate().skip(node_index) {
+        let node = match line.elem.as_ref().and_then(NodeInfo::from_line_ast) {
+            Some(node) => node,
+            None       => continue,
+        };
+        let usage      = alias_analysis::analyze_node(&node);
+        let introduces = usage.introduced.iter().any(|name| name.item == old_name);
+        let mut ast    = node.ast().clone_ref();
+        if index == node_index {
+            for introduced in usage.introduced.iter().filter(|name| name.item == old_name) {
+                ast = ast.set_traversing(&introduced.crumbs,new_var.clone_ref())?;
+            }
+        } else if is_in_scope {
+            for used in usage.used.iter().filter(|name| name.item == old_name) {
+                ast = ast.set_traversing(&used.crumbs,new_var.clone_ref())?;
+            }
+            // Another definition of the same name shadows the renamed one.
+            is_in_scope = !introduces;
+        }
+        line.elem = Some(ast);
+    }
+    let mut updated_definition = graph.source.clone();
+    updated_definition.set_block_lines(lines)?;
+    Ok(updated_definition)
+}
+
+/// Create the variable Ast with the given name. Fails if the name is not a valid variable name.
+fn variable(name:&str) -> FallibleResult<Ast> {
+    let identifier = Identifier::from_text(name)?;
+    let ast        = Ast::from(identifier);
+    if known::Var::try_from(&ast).is_ok() {
+        Ok(ast)
+    } else {
+        Err(NotAVariableName(name.to_owned()).into())
+    }
+}
+
+/// Check that the name is not introduced nor used in the graph, including its parameters.
+fn ensure_name_not_used(graph:&GraphInfo, name:&str) -> FallibleResult<()> {
+    let name           = NormalizedName::new(name);
+    let usage          = alias_analysis::analyze_crumbable(graph.source.body().item);
+    let mut used       = usage.all_identifiers().into_iter().map(|located| located.item);
+    let mut parameters = graph.source.args.iter().filter_map(|arg| {
+        NormalizedName::try_from_ast(&arg.item)
+    });
+    if used.any(|used| used == name) || parameters.any(|param| param == name) {
+        Err(NameAlreadyUsed(name.to_string()).into())
+    } else {
+        Ok(())
+    }
+}
+
+
+
+// =======================
+// === Method Renaming ===
+// =======================
+
+/// Rename the method defined in the module and update all its calls in the module, i.e. the
+/// `here.<name>` expressions.
+///
+/// Fails if the method cannot be found or if `new_name` is not a valid variable name. Checking that
+/// the new name does not collide with other methods is the caller's responsibility.
+pub fn rename_method
+(ast:&known::Module, name:&DefinitionName, new_name:&str) -> FallibleResult<known::Module> {
+    let new_var     = variable(new_name)?;
+    let definition  = module::locate_child(ast,name)?;
+    let found_name  = &definition.item.name;
+    let name_crumbs = definition.crumbs.iter().chain(&found_name.crumbs);
+    let name_crumbs = name_crumbs.chain(&found_name.item.name.crumbs).cloned().collect_vec();
+    let module_ast  = Ast::from(ast.clone_ref());
+    let renamed     = module_ast.set_traversing(&name_crumbs,new_var.clone_ref())?;
+    let old_name    = name.name.item.as_str();
+    let renamed     = rename_method_calls(&renamed,HERE,old_name,&new_var)?.unwrap_or(renamed);
+    Ok(known::Module::try_new(renamed)?)
+}
+
+/// Update the calls of the method renamed with [`rename_method`] in another module, which imports
+/// the method's module. The calls there are the `<ModuleName>.<name>` expressions, where
+/// `module_name` is the last segment of the method module's qualified name.
+///
+/// Returns `None` if the module does not call the method.
+pub fn rename_method_usages
+(ast:&known::Module, module_name:&str, name:&str, new_name:&str)
+-> FallibleResult<Option<known::Module>> {
+    let new_var = variable(new_name)?;
+    let renamed = rename_method_calls(ast.ast(),module_name,name,&new_var)?;
+    Ok(renamed.map(known::Module::try_new).transpose()?)
+}
+
+/// Replace the calls of a method named `name` on `this` in the subtree with `new_name`. Returns
+/// `None` if there were no such calls.
+fn rename_method_calls
+(ast:&Ast, this:&str, name:&str, new_name:&Ast) -> FallibleResult<Option<Ast>> {
+    if let Some(access) = ast::opr::to_access(ast) {
+        let is_call_on_this = ast::identifier::name(&access.larg) == Some(this);
+        if is_call_on_this && ast::identifier::name(&access.rarg) == Some(name) {
+            let crumb = Crumb::from(InfixCrumb::RightOperand);
+            return Ok(Some(ast.set(&crumb,new_name.clone_ref())?))
+        }
+    }
+    let mut result = None;
+    for (crumb,child) in ast.enumerate() {
+        if let Some(renamed_child) = rename_method_calls(child,this,name,new_name)? {
+            let updated = result.as_ref().unwrap_or(ast);
+            result      = Some(updated.set(&crumb,renamed_child)?);
+        }
+    }
+    Ok(result)
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::double_representation::graph;
+
+    use parser::Parser;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn main_graph(parser:&Parser, code:&str) -> GraphInfo {
+        let ast  = parser.parse_module(code,default()).unwrap();
+        let main = module::locate_child(&ast,&DefinitionName::new_plain("main")).unwrap();
+        graph::GraphInfo::from_definition(main.item.clone())
+    }
+
+    #[wasm_bindgen_test]
+    fn renaming_variable() {
+        let parser   = Parser::new_or_panic();
+        let code     = r"main arg =
+    a = 5
+    b = a + (x -> a + x)
+    c = b + a
+    print c";
+        let expected = r"main arg =
+    foo = 5
+    b = foo + (x -> foo + x)
+    c = b + foo
+    print c";
+        let graph    = main_graph(&parser,code);
+        let nodes    = graph.nodes();
+        let a        = nodes[0].id();
+        let result   = rename_variable(&graph,a,"foo").unwrap();
+        assert_eq!(result.ast.repr(),expected);
+
+        assert!(rename_variable(&graph,a,"a").is_ok());
+        assert!(rename_variable(&graph,a,"c").is_err());
+        assert!(rename_variable(&graph,a,"print").is_err());
+        assert!(rename_variable(&graph,a,"arg").is_err());
+        assert!(rename_variable(&graph,a,"Foo").is_err());
+        assert!(rename_variable(&graph,nodes[3].id(),"foo").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn renaming_variable_shadowed_in_lambda() {
+        let parser   = Parser::new_or_panic();
+        let code     = "main =\n    a = 5\n    b = (a -> a + 1)\n    a + b";
+        let graph    = main_graph(&parser,code);
+        let result   = rename_variable(&graph,graph.nodes()[0].id(),"foo").unwrap();
+        let expected = "main =\n    foo = 5\n    b = (a -> a + 1)\n    foo + b";
+        assert_eq!(result.ast.repr(),expected);
+    }
+
+    #[wasm_bindgen_test]
+    fn renaming_redefined_variable() {
+        let parser   = Parser::new_or_panic();
+        let code     = "main =\n    a = 5\n    b = a\n    a = 6\n    c = a";
+        let graph    = main_graph(&parser,code);
+        let result   = rename_variable(&graph,graph.nodes()[2].id(),"foo").unwrap();
+        let expected = "main =\n    a = 5\n    b = a\n    foo = 6\n    c = foo";
+        assert_eq!(result.ast.repr(),expected);
+    }
+
+    #[wasm_bindgen_test]
+    fn renaming_method() {
+        let parser   = Parser::new_or_panic();
+        let code     = "foo a = a + 1\n\nmain =\n    x = here.foo 5\n    here.foo x";
+        let ast      = parser.parse_module(code,default()).unwrap();
+        let name     = DefinitionName::new_plain("foo");
+        let result   = rename_method(&ast,&name,"bar").unwrap();
+        let expected = "bar a = a + 1\n\nmain =\n    x = here.bar 5\n    here.bar x";
+        assert_eq!(result.repr(),expected);
+        assert!(rename_method(&ast,&DefinitionName::new_plain("baz"),"bar").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn renaming_method_usages() {
+        let parser   = Parser::new_or_panic();
+        let code     = "import Project.Foo\n\nmain =\n    x = Foo.foo 5\n    Bar.foo (here.foo x)";
+        let ast      = parser.parse_module(code,default()).unwrap();
+        let result   = rename_method_usages(&ast,"Foo","foo","bar").unwrap().unwrap();
+        let expected = "import Project.Foo\n\nmain =\n    x = Foo.bar 5\n    Bar.foo (here.foo x)";
+        assert_eq!(result.repr(),expected);
+        assert!(rename_method_usages(&ast,"Foo","baz","bar").unwrap().is_none());
+    }
+}
@@ -2,9 +2,12 @@
 
 use crate::prelude::*;
 
+use crate::transport::Reconnect;
 use crate::transport::web::ConnectingError;
+use crate::transport::web::ReconnectingWebSocket;
 use crate::transport::web::WebSocket;
 use crate::view::project::ProjectView;
+use crate::view::project_browser::ProjectBrowserView;
 use crate::config;
 
 use enso_protocol::binary;
@@ -12,6 +15,7 @@ use enso_protocol::language_server;
 use enso_protocol::project_manager;
 use enso_protocol::project_manager::ProjectMetadata;
 use enso_protocol::project_manager::ProjectName;
+use ensogl::application::Application;
 use uuid::Uuid;
 
 
@@ -27,6 +31,11 @@ pub struct ProjectNotFound {
     name : String
 }
 
+/// Error raised when a project is requested to be opened while another one is being switched to.
+#[derive(Clone,Copy,Debug,Fail)]
+#[fail(display="Another project is being opened.")]
+pub struct ProjectSwitchInProgress;
+
 
 
 // ===========
@@ -34,9 +43,113 @@ pub struct ProjectNotFound {
 // ===========
 
 /// The IDE structure containing its configuration and its components instances.
+///
+/// The IDE displays a single project at once. The project may be switched at runtime using the
+/// project browser, which tears down the current project view and model.
 #[derive(Debug)]
 pub struct Ide {
-    project_view : ProjectView
+    logger          : Logger,
+    application     : Application,
+    project_browser : controller::ProjectBrowser,
+    browser_view    : RefCell<Option<ProjectBrowserView>>,
+    project_view    : RefCell<Option<ProjectView>>,
+    is_switching    : Cell<bool>,
+}
+
+impl Ide {
+    /// Create the IDE with no project opened.
+    pub fn new(parent:impl AnyLogger, project_manager:Rc<dyn project_manager::API>) -> Rc<Self> {
+        let logger          = Logger::sub(parent,"Ide");
+        let application     = ProjectView::setup_application();
+        let project_browser = controller::ProjectBrowser::new(&logger,project_manager);
+        let browser_view    = default();
+        let project_view    = default();
+        let is_switching    = default();
+        let ide = Rc::new(Ide {logger,application,project_browser,browser_view,project_view,
+            is_switching});
+        let weak         = Rc::downgrade(&ide);
+        let open_project = Rc::new(move |project:ProjectMetadata| {
+            if let Some(ide) = weak.upgrade() {
+                executor::global::spawn(async move {
+                    if let Err(err) = ide.switch_project(project).await {
+                        error!(ide.logger,"Failed to switch the project: {err}");
+                    }
+                });
+            }
+        });
+        let browser_view = ProjectBrowserView::new(&ide.logger,&ide.application,
+            ide.project_browser.clone_ref(),open_project);
+        *ide.browser_view.borrow_mut() = Some(browser_view);
+        ide
+    }
+
+    /// Open the project, replacing the currently opened one. Does nothing if the project is
+    /// already opened.
+    ///
+    /// The current project view and model are dropped and the project is closed before the new one
+    /// is opened, so the Project Manager can shut its Language Server down. If the new project
+    /// fails to open, the previous one is opened again.
+    ///
+    /// Fails with `ProjectSwitchInProgress` if called before the previous switch finished.
+    pub async fn switch_project(&self, project:ProjectMetadata) -> FallibleResult<()> {
+        if self.is_switching.replace(true) {
+            return Err(ProjectSwitchInProgress.into())
+        }
+        let result = self.replace_project(project).await;
+        self.is_switching.set(false);
+        result
+    }
+
+    async fn replace_project(&self, project:ProjectMetadata) -> FallibleResult<()> {
+        let opened_id = self.project_view.borrow().as_ref().map(|view| view.model().id());
+        if opened_id == Some(project.id) {
+            return Ok(())
+        }
+        let old_view = self.project_view.borrow_mut().take();
+        let previous = old_view.as_ref().map(|view| {
+            let model       = view.model();
+            let id          = model.id();
+            let name        = ProjectName::new(model.name().to_string());
+            let last_opened = default();
+            ProjectMetadata {id,name,last_opened}
+        });
+        drop(old_view);
+        let result = match &previous {
+            Some(previous) => self.close_project(previous.id).await,
+            None           => Ok(()),
+        };
+        let result = match result {
+            Ok(()) => self.open_project(project).await,
+            err    => err,
+        };
+        if let (Err(err),Some(previous)) = (&result,previous) {
+            warning!(self.logger,"Failed to open the project: {err}. Reopening the previous one.");
+            if let Err(err) = self.open_project(previous).await {
+                error!(self.logger,"Failed to reopen the previous project: {err}");
+            }
+        }
+        result
+    }
+
+    async fn close_project(&self, id:Uuid) -> FallibleResult<()> {
+        self.set_opened_project(None);
+        self.project_browser.close_project(id).await
+    }
+
+    async fn open_project(&self, project:ProjectMetadata) -> FallibleResult<()> {
+        let id    = project.id;
+        let model = self.project_browser.open_project(project).await?;
+        let view  = ProjectView::new(&self.logger,model,&self.application).await?;
+        *self.project_view.borrow_mut() = Some(view);
+        self.set_opened_project(Some(id));
+        Ok(())
+    }
+
+    fn set_opened_project(&self, id:Option<Uuid>) {
+        if let Some(browser_view) = self.browser_view.borrow().as_ref() {
+            browser_view.set_opened_project(id);
+        }
+    }
 }
 
 
@@ -91,8 +204,8 @@ impl IdeInitializer {
         let client_id     = Uuid::new_v4();
         let json_ws       = new_opened_ws(logger.clone_ref(), json_endpoint).await?;
         let binary_ws     = new_opened_ws(logger.clone_ref(), binary_endpoint).await?;
-        let client_json   = language_server::Client::new(json_ws);
-        let client_binary = binary::Client::new(logger,binary_ws);
+        let client_json   = language_server::Client::new(json_ws.clone_ref());
+        let client_binary = binary::Client::new(logger,binary_ws.clone_ref());
         crate::executor::global::spawn(client_json.runner());
         crate::executor::global::spawn(client_binary.runner());
         let connection_json   = language_server::Connection::new(client_json,client_id).await?;
@@ -101,6 +214,8 @@ impl IdeInitializer {
         let ProjectName(name) = project_metadata.name;
         let project           = model::project::Synchronized::from_connections(logger,
             project_manager,connection_json,connection_binary,project_id,name).await?;
+        let transports:Vec<Rc<dyn Reconnect>> = vec![Rc::new(json_ws),Rc::new(binary_ws)];
+        project.connection_recovery.set_transports(transports);
         Ok(Rc::new(project))
     }
 
@@ -112,7 +227,7 @@ impl IdeInitializer {
     , name            : &str
     ) -> FallibleResult<ProjectMetadata> {
         info!(logger, "Creating a new project named '{name}'.");
-        let id          = project_manager.create_project(&name.to_string()).await?.project_id;
+        let id          = project_manager.create_project(&name.to_string(),&None).await?.project_id;
         let name        = name.to_string();
         let name        = ProjectName::new(name);
         let last_opened = default();
@@ -168,20 +283,22 @@ impl IdeInitializer {
         Ok(Self::setup_project_manager(transport))
     }
 
-    /// Initialize the project view, including the controller it uses.
-    pub async fn initialize_project_view
+    /// Initialize the IDE and open the project given in the configuration, creating it if it does
+    /// not exist yet.
+    pub async fn initialize_ide
     ( &self
     , config          : &config::Startup
     , project_manager : project_manager::Client
-    ) -> FallibleResult<ProjectView> {
+    ) -> FallibleResult<Rc<Ide>> {
         let logger           = &self.logger;
         let project_name     = config.project_name.to_string();
         let project_metadata = Self::get_project_or_create_new
             (logger,&project_manager,&project_name).await?;
         let project_manager = Rc::new(project_manager);
-        let project         = Self::open_project(logger,project_manager,project_metadata).await?;
-        // ensogl::system::web::sleep(std::time::Duration::from_secs(10)).await;
-        Ok(ProjectView::new(logger,project).await?)
+        let ide             = Ide::new(logger,project_manager);
+        ensogl::system::web::sleep(std::time::Duration::from_secs(10)).await;
+        ide.switch_project(project_metadata).await?;
+        Ok(ide)
     }
 
     /// This function initializes the project manager, creates the project view and forget IDE
@@ -196,10 +313,9 @@ impl IdeInitializer {
             //      in case of setup failure.
             let project_manager = self.initialize_project_manager(&config).await;
             let project_manager = project_manager.expect("Failed to initialize Project Manager.");
-            let project_view    = self.initialize_project_view(&config,project_manager).await;
-            let project_view    = project_view.expect("Failed to setup initial project view.");
+            let ide             = self.initialize_ide(&config,project_manager).await;
+            let ide             = ide.expect("Failed to setup initial project view.");
             self.logger.info("Setup done.");
-            let ide = Ide{project_view};
             std::mem::forget(ide);
             std::mem::forget(executor);
         });
@@ -220,8 +336,11 @@ pub fn setup_global_executor() -> executor::web::EventLoopExecutor {
 }
 
 /// Creates a new websocket transport and waits until the connection is properly opened.
+///
+/// The returned transport is able to reconnect to the same address, once the connection is lost.
 pub async fn new_opened_ws
-(logger:Logger, address:project_manager::IpWithSocket) -> Result<WebSocket,ConnectingError> {
+(logger:Logger, address:project_manager::IpWithSocket)
+-> Result<ReconnectingWebSocket,ConnectingError> {
     let endpoint = format!("ws://{}:{}", address.host, address.port);
-    WebSocket::new_opened(logger,endpoint).await
+    ReconnectingWebSocket::new_opened(logger,endpoint).await
 }
@@ -154,6 +154,58 @@ impl ComputedValueInfoRegistry {
 
 
 
+// =======================
+// === ExecutionStatus ===
+// =======================
+
+/// Keeps the information whether the last execution of the context failed. Receives the
+/// `executionContext/executionFailed` notifications from the Language Server and emits
+/// notifications when the status changes.
+///
+/// The `None` value means that the context is not known to be failed.
+#[derive(Clone,Default,Derivative)]
+#[derivative(Debug)]
+pub struct ExecutionStatus {
+    failure : RefCell<Option<String>>,
+    /// A publisher that emits the new failure message (or `None` when the failure has been
+    /// cleared) every time the status changes.
+    #[derivative(Debug="ignore")]
+    updates : Publisher<Option<String>>,
+}
+
+impl ExecutionStatus {
+    /// Get the error message of the last execution failure, if the context is failed.
+    pub fn failure(&self) -> Option<String> {
+        self.failure.borrow().clone()
+    }
+
+    /// Check if the last execution of the context failed.
+    pub fn is_failed(&self) -> bool {
+        self.failure.borrow().is_some()
+    }
+
+    /// Mark the context as failed with the given error message.
+    pub fn set_failed(&self, message:impl Into<String>) {
+        let message = message.into();
+        self.failure.replace(Some(message.clone()));
+        self.updates.notify(Some(message));
+    }
+
+    /// Mark the context as not failed. Does nothing if the context was not failed.
+    pub fn clear(&self) {
+        if self.failure.replace(None).is_some() {
+            self.updates.notify(None);
+        }
+    }
+
+    /// Subscribe to notifications about changes of the status.
+    pub fn subscribe(&self) -> Subscriber<Option<String>> {
+        self.updates.subscribe()
+    }
+}
+
+
+
 // ===============================
 // === VisualizationUpdateData ===
 // ===============================
@@ -288,6 +340,9 @@ pub trait API : Debug {
     /// Get the registry of computed values.
     fn computed_value_info_registry(&self) -> &Rc<ComputedValueInfoRegistry>;
 
+    /// Get the status of the last execution, telling if it failed.
+    fn execution_status(&self) -> &Rc<ExecutionStatus>;
+
     /// Get all items on stack.
     fn stack_items<'a>(&'a self) -> Box<dyn Iterator<Item=LocalCall> + 'a>;
 
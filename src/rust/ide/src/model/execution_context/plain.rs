@@ -4,6 +4,7 @@ use crate::prelude::*;
 
 use crate::model::execution_context::AttachedVisualization;
 use crate::model::execution_context::ComputedValueInfoRegistry;
+use crate::model::execution_context::ExecutionStatus;
 use crate::model::execution_context::LocalCall;
 use crate::model::execution_context::Visualization;
 use crate::model::execution_context::VisualizationId;
@@ -54,6 +55,8 @@ pub struct ExecutionContext {
     visualizations: RefCell<HashMap<VisualizationId,AttachedVisualization>>,
     /// Storage for information about computed values (like their types).
     pub computed_value_info_registry:Rc<ComputedValueInfoRegistry>,
+    /// The information whether the last execution of this context failed.
+    pub execution_status:Rc<ExecutionStatus>,
 }
 
 impl ExecutionContext {
@@ -63,7 +66,8 @@ impl ExecutionContext {
         let stack                        = default();
         let visualizations               = default();
         let computed_value_info_registry = default();
-        Self {logger,entry_point,stack,visualizations, computed_value_info_registry }
+        let execution_status             = default();
+        Self {logger,entry_point,stack,visualizations,computed_value_info_registry,execution_status}
     }
 
     /// Push a new stack item to execution context.
@@ -72,6 +76,7 @@ impl ExecutionContext {
     pub fn push(&self, stack_item:LocalCall)  {
         self.stack.borrow_mut().push(stack_item);
         self.computed_value_info_registry.clear();
+        self.execution_status.clear();
     }
 
     /// Pop the last stack item from this context. It returns error when only root call remains.
@@ -80,6 +85,7 @@ impl ExecutionContext {
     pub fn pop(&self) -> FallibleResult<LocalCall> {
         let ret = self.stack.borrow_mut().pop().ok_or_else(PopOnEmptyStack)?;
         self.computed_value_info_registry.clear();
+        self.execution_status.clear();
         Ok(ret)
     }
 
@@ -136,6 +142,10 @@ impl model::execution_context::API for ExecutionContext {
         &self.computed_value_info_registry
     }
 
+    fn execution_status(&self) -> &Rc<ExecutionStatus> {
+        &self.execution_status
+    }
+
     fn stack_items<'a>(&'a self) -> Box<dyn Iterator<Item=LocalCall> + 'a> {
         let stack_size = self.stack.borrow().len();
         Box::new((0..stack_size).filter_map(move |i| self.stack.borrow().get(i).cloned()))
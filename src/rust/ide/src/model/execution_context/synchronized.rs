@@ -3,12 +3,15 @@
 use crate::prelude::*;
 
 use crate::model::execution_context::ComputedValueInfoRegistry;
+use crate::model::execution_context::ExecutionStatus;
 use crate::model::execution_context::LocalCall;
 use crate::model::execution_context::Visualization;
 use crate::model::execution_context::VisualizationUpdateData;
 use crate::model::execution_context::VisualizationId;
+use crate::model::traits::*;
 
 use enso_protocol::language_server;
+use enso_protocol::language_server::ExecutionFailed;
 use enso_protocol::language_server::ExpressionValuesComputed;
 
 
@@ -20,7 +23,7 @@ use enso_protocol::language_server::ExpressionValuesComputed;
 /// from LS once dropped.
 #[derive(Debug)]
 pub struct ExecutionContext {
-    id              : model::execution_context::Id,
+    id              : Cell<model::execution_context::Id>,
     model           : model::execution_context::Plain,
     language_server : Rc<language_server::Connection>,
     logger          : Logger,
@@ -29,7 +32,7 @@ pub struct ExecutionContext {
 impl ExecutionContext {
     /// The unique identifier of this execution context.
     pub fn id(&self) -> model::execution_context::Id {
-        self.id
+        self.id.get()
     }
 
     /// Create new ExecutionContext. It will be created in LanguageServer and the ExplicitCall
@@ -49,14 +52,16 @@ impl ExecutionContext {
             let logger = Logger::sub(&parent,iformat!{"ExecutionContext {id}"});
             let model  = model::execution_context::Plain::new(&logger,root_definition);
             info!(logger, "Created. Id: {id}.");
+            let id   = Cell::new(id);
             let this = Self {id,model,language_server,logger };
-            this.push_root_frame().await?;
+            this.push_root_frame(this.id()).await?;
             info!(this.logger, "Pushed root frame.");
             Ok(this)
         }
     }
 
-    fn push_root_frame(&self) -> impl Future<Output=FallibleResult<()>> {
+    fn push_root_frame
+    (&self, id:model::execution_context::Id) -> impl Future<Output=FallibleResult<()>> {
         let method_pointer                   = self.model.entry_point.clone();
         let this_argument_expression         = default();
         let positional_arguments_expressions = default();
@@ -64,7 +69,7 @@ impl ExecutionContext {
         let call = language_server::ExplicitCall {method_pointer,this_argument_expression,
             positional_arguments_expressions};
         let frame  = language_server::StackItem::ExplicitCall(call);
-        let result = self.language_server.push_to_execution_context(&self.id,&frame);
+        let result = self.language_server.push_to_execution_context(&id,&frame);
         result.map(|res| res.map_err(|err| err.into()))
     }
 
@@ -75,7 +80,7 @@ impl ExecutionContext {
     async fn detach_visualization_inner
     (&self, vis:Visualization) -> FallibleResult<Visualization> {
         let vis_id = vis.id;
-        let exe_id = self.id;
+        let exe_id = self.id();
         let ast_id = vis.ast_id;
         let ls     = self.language_server.clone_ref();
         let logger = self.logger.clone_ref();
@@ -87,10 +92,61 @@ impl ExecutionContext {
         Ok(vis)
     }
 
+    /// Create this context anew in the Language Server, restoring its call stack and attached
+    /// visualizations. Used after the connection with the Language Server has been re-established.
+    ///
+    /// The context obtains a new id once it is fully restored. The context previously registered
+    /// under the old id is destroyed, if the Language Server still knows it. If the restoring
+    /// fails, the partially restored context is destroyed and the old id is kept.
+    pub async fn recreate(&self) -> FallibleResult<()> {
+        let old_id = self.id();
+        info!(self.logger, "Recreating context {old_id}.");
+        if let Err(err) = self.language_server.destroy_execution_context(&old_id).await {
+            info!(self.logger, "Could not destroy the old context {old_id}: {err}.");
+        }
+        let new_id = self.language_server.create_execution_context().await?.context_id;
+        if let Err(err) = self.restore_state(new_id).await {
+            if let Err(err) = self.language_server.destroy_execution_context(&new_id).await {
+                info!(self.logger, "Could not destroy the partially recreated context: {err}.");
+            }
+            return Err(err)
+        }
+        self.id.set(new_id);
+        self.model.execution_status.clear();
+        info!(self.logger, "Recreated as {new_id}.");
+        Ok(())
+    }
+
+    /// Push the call stack and attach the visualizations of this context to the context with
+    /// given id in the Language Server.
+    async fn restore_state(&self, id:model::execution_context::Id) -> FallibleResult<()> {
+        self.push_root_frame(id).await?;
+        let stack_items = self.model.stack_items().collect_vec();
+        for stack_item in stack_items {
+            let expression_id = stack_item.call;
+            let call          = language_server::LocalCall{expression_id};
+            let frame         = language_server::StackItem::LocalCall(call);
+            self.language_server.push_to_execution_context(&id,&frame).await?;
+        }
+        for vis in self.model.all_visualizations_info() {
+            let config = vis.config(id);
+            self.language_server.attach_visualisation(&vis.id,&vis.ast_id,&config).await?;
+        }
+        Ok(())
+    }
+
     /// Handles the update about expressions being computed.
     pub fn handle_expression_values_computed
     (&self, notification:ExpressionValuesComputed) -> FallibleResult<()> {
         self.model.computed_value_info_registry.apply_updates(notification.updates);
+        self.model.execution_status.clear();
+        Ok(())
+    }
+
+    /// Handles the notification about the execution of this context being failed.
+    pub fn handle_execution_failed(&self, notification:ExecutionFailed) -> FallibleResult<()> {
+        let message = notification.message;
+        self.model.execution_status.set_failed(message);
         Ok(())
     }
 }
@@ -117,6 +173,10 @@ impl model::execution_context::API for ExecutionContext {
         &self.model.computed_value_info_registry()
     }
 
+    fn execution_status(&self) -> &Rc<ExecutionStatus> {
+        self.model.execution_status()
+    }
+
     fn stack_items<'a>(&'a self) -> Box<dyn Iterator<Item=LocalCall> + 'a> {
         self.model.stack_items()
     }
@@ -126,7 +186,7 @@ impl model::execution_context::API for ExecutionContext {
             let expression_id = stack_item.call;
             let call          = language_server::LocalCall{expression_id};
             let frame         = language_server::StackItem::LocalCall(call);
-            self.language_server.push_to_execution_context(&self.id,&frame).await?;
+            self.language_server.push_to_execution_context(&self.id(),&frame).await?;
             self.model.push(stack_item);
             Ok(())
         }.boxed_local()
@@ -137,7 +197,7 @@ impl model::execution_context::API for ExecutionContext {
             // We do pop first, because we want to call any ls method if the operation is impossible
             // in the plain model.
             let frame  = self.model.pop()?;
-            let result = self.language_server.pop_from_execution_context(&self.id).await;
+            let result = self.language_server.pop_from_execution_context(&self.id()).await;
             if let Err(err) = result {
                 self.model.push(frame);
                 Err(err.into())
@@ -154,7 +214,7 @@ impl model::execution_context::API for ExecutionContext {
         //  We must register our visualization in the model first, because Language server can send
         //  us visualization updates through the binary socket before confirming that visualization
         //  has been successfully attached.
-        let config = vis.config(self.id);
+        let config = vis.config(self.id());
         let stream = self.model.attach_visualization(vis.clone());
         async move {
             let result = self.language_server.attach_visualisation(&vis.id,&vis.ast_id,&config).await;
@@ -184,7 +244,7 @@ impl model::execution_context::API for ExecutionContext {
 
 impl Drop for ExecutionContext {
     fn drop(&mut self) {
-        let id     = self.id;
+        let id     = self.id();
         let ls     = self.language_server.clone_ref();
         let logger = self.logger.clone_ref();
         executor::global::spawn(async move {
@@ -209,7 +269,6 @@ pub mod test {
     use crate::executor::test_utils::TestWithLocalPoolExecutor;
     use crate::model::execution_context::plain::test::MockData;
     use crate::model::module::QualifiedName;
-    use crate::model::traits::*;
 
     use enso_protocol::language_server::CapabilityRegistration;
     use enso_protocol::language_server::response::CreateExecutionContext;
@@ -245,7 +304,7 @@ pub mod test {
         }
 
         /// What is expected server's response to a successful creation of this context.
-        fn expected_creation_response(data:&MockData) -> CreateExecutionContext {
+        pub fn expected_creation_response(data:&MockData) -> CreateExecutionContext {
             let context_id = data.context_id;
             let can_modify =
                 CapabilityRegistration::create_can_modify_execution_context(context_id);
@@ -299,7 +358,7 @@ pub mod test {
     #[test]
     fn creating_context() {
         let f = Fixture::new();
-        assert_eq!(f.data.context_id, f.context.id);
+        assert_eq!(f.data.context_id, f.context.id());
         let name_in_data      = f.data.module_qualified_name();
         let name_in_ctx_model = QualifiedName::try_from(&f.context.model.entry_point);
         assert_eq!(name_in_data, name_in_ctx_model.unwrap());
@@ -371,6 +430,76 @@ pub mod test {
         });
     }
 
+    #[test]
+    fn recreating_context() {
+        let expression_id = model::execution_context::ExpressionId::new_v4();
+        let vis           = Visualization {
+            id                   : model::execution_context::VisualizationId::new_v4(),
+            ast_id               : model::execution_context::ExpressionId::new_v4(),
+            expression           : "".to_string(),
+            visualisation_module : MockData::new().module_qualified_name(),
+        };
+        let new_data = MockData {
+            context_id : model::execution_context::Id::new_v4(),
+            ..MockData::new()
+        };
+        let new_id = new_data.context_id;
+        let Fixture{data,mut test,context} = Fixture::new_customized(|ls,data| {
+            let old_id     = data.context_id;
+            let call_frame = language_server::LocalCall{expression_id};
+            let stack_item = language_server::StackItem::LocalCall(call_frame);
+            let vis_id     = vis.id;
+            let ast_id     = vis.ast_id;
+            let old_config = vis.config(old_id);
+            let new_config = vis.config(new_id);
+            expect_call!(ls.push_to_execution_context(old_id,stack_item.clone()) => Ok(()));
+            expect_call!(ls.attach_visualisation(vis_id,ast_id,old_config) => Ok(()));
+            Fixture::mock_create_push_destroy_calls(&new_data,ls);
+            expect_call!(ls.push_to_execution_context(new_id,stack_item) => Ok(()));
+            expect_call!(ls.attach_visualisation(vis_id,ast_id,new_config) => Ok(()));
+        });
+        test.run_task(async move {
+            let item = LocalCall {
+                call       : expression_id,
+                definition : data.main_method_pointer(),
+            };
+            context.push(item.clone()).await.unwrap();
+            let _ = context.attach_visualization(vis.clone()).await.unwrap();
+            context.recreate().await.unwrap();
+            assert_eq!(context.id(), new_id);
+            assert_eq!((item,), context.model.stack_items().expect_tuple());
+            assert_eq!(vec![vis.id], context.active_visualizations());
+        });
+    }
+
+    #[test]
+    fn failing_to_recreate_context() {
+        let new_data = MockData {
+            context_id : model::execution_context::Id::new_v4(),
+            ..MockData::new()
+        };
+        let new_id = new_data.context_id;
+        let Fixture{data,mut test,context} = Fixture::new_customized(|ls,data| {
+            let old_id     = data.context_id;
+            let response   = Fixture::expected_creation_response(&new_data);
+            let root_frame = language_server::ExplicitCall {
+                method_pointer                   : data.main_method_pointer(),
+                this_argument_expression         : None,
+                positional_arguments_expressions : vec![]
+            };
+            let stack_item = language_server::StackItem::ExplicitCall(root_frame);
+            let error      = json_rpc::error::RpcError::LostConnection;
+            expect_call!(ls.create_execution_context() => Ok(response));
+            expect_call!(ls.push_to_execution_context(new_id,stack_item) => Err(error));
+            expect_call!(ls.destroy_execution_context(new_id) => Ok(()));
+            expect_call!(ls.destroy_execution_context(old_id) => Ok(()));
+        });
+        test.run_task(async move {
+            assert!(context.recreate().await.is_err());
+            assert_eq!(context.id(), data.context_id);
+        });
+    }
+
     // TODO [mwu]
     //   The test below has been disabled as shaky, see https://github.com/enso-org/ide/issues/637
     #[ignore]
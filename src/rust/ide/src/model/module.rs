@@ -2,6 +2,7 @@
 
 pub mod plain;
 pub mod synchronized;
+pub mod undo_redo;
 
 pub use double_representation::module::Id;
 pub use double_representation::module::QualifiedName;
@@ -35,6 +36,16 @@ use serde::Deserialize;
 #[fail(display="Node with ID {} was not found in metadata.", _0)]
 pub struct NodeMetadataNotFound(pub ast::Id);
 
+/// Failure for missing note.
+#[derive(Debug,Clone,Copy,Fail)]
+#[fail(display="Note with ID {} was not found in metadata.", _0)]
+pub struct NoteNotFound(pub NoteId);
+
+/// Failure for missing node group.
+#[derive(Debug,Clone,Copy,Fail)]
+#[fail(display="Node group with ID {} was not found in metadata.", _0)]
+pub struct GroupNotFound(pub GroupId);
+
 /// Failed attempt to tread a file path as a module path.
 #[derive(Clone,Debug,Fail)]
 #[fail(display = "The path `{}` is not a valid module path. {}",path,issue)]
@@ -179,6 +190,15 @@ impl Path {
         ReferentName::new(self.file_path.file_stem().unwrap()).unwrap()
     }
 
+    /// Get the path of the module with given name, placed in the same directory as this module.
+    pub fn with_module_name(&self, name:&ReferentName) -> Path {
+        let mut file_path = self.file_path().clone();
+        if let Some(file_name) = file_path.segments.last_mut() {
+            *file_name = Self::module_filename(name);
+        }
+        Path {file_path:Rc::new(file_path)}
+    }
+
     /// Create a module path consisting of a single segment, based on a given module name.
     /// The `default` is used for a root id.
     pub fn from_mock_module_name(name:impl Str) -> Self {
@@ -268,6 +288,20 @@ pub enum Notification {
     },
     /// The metadata (e.g. some node's position) has been changed.
     MetadataChanged,
+    /// The module's file has been changed by another program in a way the user should know about.
+    ExternalChange(ExternalChange),
+}
+
+/// The change of the module's file made by another program.
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum ExternalChange {
+    /// The file matches the module again.
+    Resolved,
+    /// The file was modified while the module had local changes. The local changes are kept and
+    /// will overwrite the file.
+    Conflict,
+    /// The file was removed. It will be recreated once the module is saved.
+    Removed,
 }
 
 
@@ -290,6 +324,15 @@ pub struct Metadata {
 
 impl parser::api::Metadata for Metadata {}
 
+impl Metadata {
+    /// Rename the method intended by the module's nodes, see
+    /// [`NodeMetadata::rename_intended_method`]. Returns `true` if any node intended the method.
+    pub fn rename_intended_method(&mut self, method:&MethodId, new_name:&str) -> bool {
+        let nodes = self.ide.node.values_mut();
+        nodes.fold(false,|renamed,node| node.rename_intended_method(method,new_name) || renamed)
+    }
+}
+
 impl Default for Metadata {
     fn default() -> Self {
         Metadata {
@@ -305,7 +348,13 @@ impl Default for Metadata {
 #[derive(Debug,Clone,Default,Deserialize,Serialize)]
 pub struct IdeMetadata {
     /// Metadata that belongs to nodes.
-    node : HashMap<ast::Id,NodeMetadata>
+    node : HashMap<ast::Id,NodeMetadata>,
+    /// The free-floating annotation notes placed in module's graphs.
+    #[serde(default)]
+    note : HashMap<NoteId,Note>,
+    /// The titled frames grouping nodes in module's graphs.
+    #[serde(default)]
+    group : HashMap<GroupId,Group>,
 }
 
 /// Metadata of specific node.
@@ -318,6 +367,58 @@ pub struct NodeMetadata {
     ///
     /// The methods may be defined for different types, so the name alone don't specify them.
     pub intended_method:Option<MethodId>,
+    /// A comment attached to the node by user, displayed next to it.
+    #[serde(default)]
+    pub comment:Option<String>,
+}
+
+impl NodeMetadata {
+    /// Update the intended method after the method was renamed. Returns `true` if the node intended
+    /// the renamed method.
+    pub fn rename_intended_method(&mut self, method:&MethodId, new_name:&str) -> bool {
+        match &mut self.intended_method {
+            Some(intended) if intended == method => {
+                intended.name = new_name.to_owned();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Identifier of a note.
+pub type NoteId = uuid::Uuid;
+
+/// A free-floating annotation note placed in the graph.
+#[derive(Clone,Debug,Default,Deserialize,PartialEq,Serialize)]
+pub struct Note {
+    /// The graph containing the note, stored as a displayed `double_representation::graph::Id`.
+    pub graph    : String,
+    /// The text of the note.
+    pub text     : String,
+    /// Position in x,y coordinates.
+    pub position : Position,
+}
+
+/// Identifier of a node group.
+pub type GroupId = uuid::Uuid;
+
+/// A titled frame drawn around the set of nodes in the graph.
+///
+/// The group is purely visual: collapsing it hides its nodes without changing the code.
+#[derive(Clone,Debug,Default,Deserialize,PartialEq,Serialize)]
+pub struct Group {
+    /// The graph containing the group, stored as a displayed `double_representation::graph::Id`.
+    pub graph     : String,
+    /// The title displayed above the frame.
+    pub title     : String,
+    /// The index of the frame's color in the group color palette.
+    pub color     : usize,
+    /// The nodes belonging to the group. The membership is lost when the node's id changes.
+    pub nodes     : Vec<ast::Id>,
+    /// Whether the group is collapsed, hiding its nodes.
+    #[serde(default)]
+    pub collapsed : bool,
 }
 
 /// Used for storing node position.
@@ -359,6 +460,13 @@ impl Add for Position {
     }
 }
 
+impl std::ops::Sub for Position {
+    type Output = Position;
+    fn sub(self, rhs:Self) -> Self::Output {
+        Position {vector:self.vector-rhs.vector}
+    }
+}
+
 impl std::ops::AddAssign for Position {
     fn add_assign(&mut self, rhs:Self) {
         self.vector += rhs.vector
@@ -418,6 +526,12 @@ pub trait API:Debug {
     /// Returns metadata for given node, if present.
     fn node_metadata(&self, id:ast::Id) -> FallibleResult<NodeMetadata>;
 
+    /// Returns all the notes placed in the module's graphs.
+    fn notes(&self) -> HashMap<NoteId,Note>;
+
+    /// Returns all the node groups defined in the module's graphs.
+    fn groups(&self) -> HashMap<GroupId,Group>;
+
 
 // === Setters ===
 
@@ -447,6 +561,32 @@ pub trait API:Debug {
     /// getting and setting metadata for the same node.
     fn with_node_metadata(&self, id:ast::Id, fun:Box<dyn FnOnce(&mut NodeMetadata) + '_>);
 
+    /// Sets the note with given id, adding it if not present.
+    fn set_note(&self, id:NoteId, note:Note);
+
+    /// Removes the note with given id and returns it.
+    fn remove_note(&self, id:NoteId) -> FallibleResult<Note>;
+
+    /// Sets the node group with given id, adding it if not present.
+    fn set_group(&self, id:GroupId, group:Group);
+
+    /// Removes the node group with given id and returns it.
+    fn remove_group(&self, id:GroupId) -> FallibleResult<Group>;
+
+
+// === Undo/Redo ===
+
+    /// Open a transaction grouping all the following changes of the module into a single undo
+    /// step. The transaction is open as long as the returned handle is alive. If a transaction is
+    /// already open, it is returned instead of creating a new one.
+    fn open_transaction(&self, name:&str) -> Rc<undo_redo::Transaction>;
+
+    /// Revert the last change (or transaction) made in the module.
+    fn undo(&self) -> FallibleResult<()>;
+
+    /// Reapply the last change reverted by `undo`.
+    fn redo(&self) -> FallibleResult<()>;
+
 
 // === Utils ===
 
@@ -463,6 +603,20 @@ pub type Plain = plain::Module;
 /// Module Model which synchronizes all changes with Language Server.
 pub type Synchronized = synchronized::Module;
 
+/// Update the intended methods of the module's nodes after the method was renamed, see
+/// [`NodeMetadata::rename_intended_method`].
+pub fn rename_intended_method(module:&dyn API, method:&MethodId, new_name:&str) {
+    let ids = module.ast().ast().iter_recursive().filter_map(|ast| ast.id).collect_vec();
+    for id in ids {
+        let intended = module.node_metadata(id).ok().and_then(|data| data.intended_method);
+        if intended.contains(method) {
+            module.with_node_metadata(id,Box::new(|data| {
+                data.rename_intended_method(method,new_name);
+            }));
+        }
+    }
+}
+
 
 // ============
 // === Test ===
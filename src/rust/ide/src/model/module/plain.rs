@@ -4,7 +4,15 @@ use crate::prelude::*;
 
 use parser::api::{ParsedSourceFile, SourceFile};
 use crate::model::module::{Metadata, NodeMetadata, NodeMetadataNotFound, Path};
+use crate::model::module::ExternalChange;
+use crate::model::module::Group;
+use crate::model::module::GroupId;
+use crate::model::module::GroupNotFound;
+use crate::model::module::Note;
+use crate::model::module::NoteId;
+use crate::model::module::NoteNotFound;
 use crate::model::module::Notification;
+use crate::model::module::undo_redo;
 use crate::notification;
 use flo_stream::Subscriber;
 use crate::double_representation::definition::DefinitionInfo;
@@ -22,6 +30,7 @@ pub struct Module {
     path          : Path,
     content       : RefCell<Content>,
     notifications : notification::Publisher<Notification>,
+    history       : undo_redo::History,
 }
 
 impl Module {
@@ -31,8 +40,25 @@ impl Module {
             path,
             content       : RefCell::new(ParsedSourceFile{ast,metadata}),
             notifications : default(),
+            history       : default(),
         }
     }
+
+    /// Store the current content in the undo history before making a change named `name`.
+    fn record_change(&self, name:&str) {
+        self.history.record(name,&self.content.borrow());
+    }
+
+    /// Notify the subscribers about the module's file being changed by another program.
+    pub fn notify_external_change(&self, change:ExternalChange) {
+        self.notifications.notify(Notification::ExternalChange(change));
+    }
+
+    /// Replace the whole content with one restored from the history.
+    fn restore(&self, content:Content) {
+        *self.content.borrow_mut() = content;
+        self.notifications.notify(Notification::Invalidate);
+    }
 }
 
 impl model::module::API for Module {
@@ -63,12 +89,22 @@ impl model::module::API for Module {
         data.ok_or_else(|| NodeMetadataNotFound(id).into())
     }
 
+    fn notes(&self) -> HashMap<NoteId,Note> {
+        self.content.borrow().metadata.ide.note.clone()
+    }
+
+    fn groups(&self) -> HashMap<GroupId,Group> {
+        self.content.borrow().metadata.ide.group.clone()
+    }
+
     fn update_whole(&self, content:Content) {
+        self.record_change("Update module");
         *self.content.borrow_mut() = content;
         self.notifications.notify(Notification::Invalidate);
     }
 
     fn update_ast(&self, ast:ast::known::Module) {
+        self.record_change("Update code");
         self.content.borrow_mut().ast  = ast;
         self.notifications.notify(Notification::Invalidate);
     }
@@ -79,30 +115,83 @@ impl model::module::API for Module {
         let replaced_location = TextLocation::convert_range(&code,&change.replaced);
         change.apply(&mut code);
         let new_ast = parser.parse(code,new_id_map)?.try_into()?;
+        self.record_change("Edit code");
         self.content.borrow_mut().ast = new_ast;
         self.notifications.notify(Notification::CodeChanged {change,replaced_location});
         Ok(())
     }
 
     fn set_node_metadata(&self, id:ast::Id, data:NodeMetadata) {
+        self.record_change("Set node metadata");
         self.content.borrow_mut().metadata.ide.node.insert(id, data);
         self.notifications.notify(Notification::MetadataChanged);
     }
 
     fn remove_node_metadata(&self, id:ast::Id) -> FallibleResult<NodeMetadata> {
-        let lookup = self.content.borrow_mut().metadata.ide.node.remove(&id);
-        let data   = lookup.ok_or_else(|| NodeMetadataNotFound(id))?;
+        let content = self.content.borrow().clone();
+        let lookup  = self.content.borrow_mut().metadata.ide.node.remove(&id);
+        let data    = lookup.ok_or_else(|| NodeMetadataNotFound(id))?;
+        self.history.record("Remove node metadata",&content);
         self.notifications.notify(Notification::MetadataChanged);
         Ok(data)
     }
 
     fn with_node_metadata(&self, id:ast::Id, fun:Box<dyn FnOnce(&mut NodeMetadata) + '_>) {
+        self.record_change("Modify node metadata");
         let lookup   = self.content.borrow_mut().metadata.ide.node.remove(&id);
         let mut data = lookup.unwrap_or_default();
         fun(&mut data);
         self.content.borrow_mut().metadata.ide.node.insert(id, data);
         self.notifications.notify(Notification::MetadataChanged);
     }
+
+    fn set_note(&self, id:NoteId, note:Note) {
+        self.record_change("Set note");
+        self.content.borrow_mut().metadata.ide.note.insert(id,note);
+        self.notifications.notify(Notification::MetadataChanged);
+    }
+
+    fn remove_note(&self, id:NoteId) -> FallibleResult<Note> {
+        let content = self.content.borrow().clone();
+        let lookup  = self.content.borrow_mut().metadata.ide.note.remove(&id);
+        let note    = lookup.ok_or_else(|| NoteNotFound(id))?;
+        self.history.record("Remove note",&content);
+        self.notifications.notify(Notification::MetadataChanged);
+        Ok(note)
+    }
+
+    fn set_group(&self, id:GroupId, group:Group) {
+        self.record_change("Set node group");
+        self.content.borrow_mut().metadata.ide.group.insert(id,group);
+        self.notifications.notify(Notification::MetadataChanged);
+    }
+
+    fn remove_group(&self, id:GroupId) -> FallibleResult<Group> {
+        let content = self.content.borrow().clone();
+        let lookup  = self.content.borrow_mut().metadata.ide.group.remove(&id);
+        let group   = lookup.ok_or_else(|| GroupNotFound(id))?;
+        self.history.record("Remove node group",&content);
+        self.notifications.notify(Notification::MetadataChanged);
+        Ok(group)
+    }
+
+    fn open_transaction(&self, name:&str) -> Rc<undo_redo::Transaction> {
+        self.history.open_transaction(name)
+    }
+
+    fn undo(&self) -> FallibleResult<()> {
+        let current  = self.content.borrow().clone();
+        let restored = self.history.undo(current)?;
+        self.restore(restored);
+        Ok(())
+    }
+
+    fn redo(&self) -> FallibleResult<()> {
+        let current  = self.content.borrow().clone();
+        let restored = self.history.redo(current)?;
+        self.restore(restored);
+        Ok(())
+    }
 }
 
 
@@ -206,4 +295,97 @@ mod test {
         }));
         assert_eq!(Some(new_pos), module.node_metadata(id).unwrap().position);
     }
+
+    #[wasm_bindgen_test]
+    fn undoing_and_redoing_changes() {
+        let _test    = TestWithLocalPoolExecutor::set_up();
+        let module   = model::module::test::plain_from_code("2 + 2");
+        let parser   = Parser::new_or_panic();
+        let id       = Uuid::new_v4();
+        let position = Some(Position::new(1.0, 2.0));
+        assert!(module.undo().is_err());
+
+        let transaction = module.open_transaction("Edit and move");
+        let change      = TextChange {
+            replaced: text::Index::new(2)..text::Index::new(5),
+            inserted: "- abc".to_string(),
+        };
+        module.apply_code_change(change,&parser,default()).unwrap();
+        module.set_node_metadata(id,NodeMetadata {position,..default()});
+        drop(transaction);
+        module.with_node_metadata(id,Box::new(|md| md.position = None));
+
+        module.undo().unwrap();
+        assert_eq!("2 - abc",module.ast().repr());
+        assert_eq!(position,module.node_metadata(id).unwrap().position);
+        module.undo().unwrap();
+        assert_eq!("2 + 2",module.ast().repr());
+        assert!(module.node_metadata(id).is_err());
+        assert!(module.undo().is_err());
+
+        module.redo().unwrap();
+        assert_eq!("2 - abc",module.ast().repr());
+        assert_eq!(position,module.node_metadata(id).unwrap().position);
+        module.redo().unwrap();
+        assert_eq!(None,module.node_metadata(id).unwrap().position);
+        assert!(module.redo().is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn handling_notes_and_comments() {
+        let _test   = TestWithLocalPoolExecutor::set_up();
+        let module  = model::module::test::plain_from_code("2 + 2");
+        let parser  = Parser::new_or_panic();
+        let node_id = Uuid::new_v4();
+        let note_id = Uuid::new_v4();
+        let comment = Some("Adds two numbers.".to_string());
+        let note    = Note {
+            graph    : "main".into(),
+            text     : "The graph computing the answer.".into(),
+            position : Position::new(1.0,2.0),
+        };
+        assert!(module.notes().is_empty());
+        assert!(module.remove_note(note_id).is_err());
+        module.set_note(note_id,note.clone());
+        module.with_node_metadata(node_id,Box::new(|md| md.comment = comment.clone()));
+        assert_eq!(module.notes().get(&note_id),Some(&note));
+
+        let serialized = module.serialized_content().unwrap().content;
+        let restored   = parser.parse_with_metadata::<Metadata>(serialized).unwrap();
+        assert_eq!(restored.metadata.ide.note.get(&note_id),Some(&note));
+        assert_eq!(restored.metadata.ide.node.get(&node_id).unwrap().comment,comment);
+
+        assert_eq!(module.remove_note(note_id).unwrap(),note);
+        assert!(module.notes().is_empty());
+        module.undo().unwrap();
+        assert_eq!(module.notes().get(&note_id),Some(&note));
+    }
+
+    #[wasm_bindgen_test]
+    fn handling_node_groups() {
+        let _test    = TestWithLocalPoolExecutor::set_up();
+        let module   = model::module::test::plain_from_code("2 + 2");
+        let parser   = Parser::new_or_panic();
+        let group_id = Uuid::new_v4();
+        let group    = Group {
+            graph     : "main".into(),
+            title     : "load".into(),
+            color     : 2,
+            nodes     : vec![Uuid::new_v4(),Uuid::new_v4()],
+            collapsed : true,
+        };
+        assert!(module.groups().is_empty());
+        assert!(module.remove_group(group_id).is_err());
+        module.set_group(group_id,group.clone());
+        assert_eq!(module.groups().get(&group_id),Some(&group));
+
+        let serialized = module.serialized_content().unwrap().content;
+        let restored   = parser.parse_with_metadata::<Metadata>(serialized).unwrap();
+        assert_eq!(restored.metadata.ide.group.get(&group_id),Some(&group));
+
+        assert_eq!(module.remove_group(group_id).unwrap(),group);
+        assert!(module.groups().is_empty());
+        module.undo().unwrap();
+        assert_eq!(module.groups().get(&group_id),Some(&group));
+    }
 }
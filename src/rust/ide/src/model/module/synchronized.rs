@@ -4,19 +4,31 @@ use crate::prelude::*;
 
 use crate::double_representation::definition::DefinitionInfo;
 use crate::double_representation::graph::Id;
+use crate::double_representation::text::apply_code_change_to_id_map;
 use crate::model::module::API;
 use crate::model::module::Content;
+use crate::model::module::ExternalChange;
+use crate::model::module::Group;
+use crate::model::module::GroupId;
+use crate::model::module::Metadata;
 use crate::model::module::Notification;
 use crate::model::module::NodeMetadata;
+use crate::model::module::Note;
+use crate::model::module::NoteId;
 use crate::model::module::Path;
+use crate::model::module::undo_redo;
 
+use ast::HasIdMap;
 use ast::IdMap;
 use data::text::TextChange;
 use data::text::TextLocation;
 use enso_protocol::types::Sha3_224;
 use enso_protocol::language_server;
+use enso_protocol::language_server::FileEventKind;
 use enso_protocol::language_server::TextEdit;
 use flo_stream::Subscriber;
+use futures::channel::mpsc::UnboundedReceiver;
+use futures::channel::mpsc::UnboundedSender;
 use parser::api::SourceFile;
 use parser::Parser;
 
@@ -83,6 +95,35 @@ impl LanguageServerContent {
 
 
 
+/// The events processed by the module's `runner`.
+#[derive(Clone,Debug)]
+enum RunnerEvent {
+    /// The module model has changed.
+    Notification(Notification),
+    /// The module has been opened again in the Language Server, which has the given content.
+    Reopened(ContentSummary),
+}
+
+
+
+// ======================
+// === Reconciliation ===
+// ======================
+
+/// The outcome of reconciling the module with its file changed by another program.
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum Reconciliation {
+    /// The module's code already matches the file.
+    UpToDate,
+    /// The external changes of the file were applied to the module.
+    Applied,
+    /// Both the file and the module were changed. The module's changes are kept, and will
+    /// overwrite the file once saved.
+    Conflict,
+}
+
+
+
 // ===========================
 // === Synchronized Module ===
 // ===========================
@@ -99,6 +140,11 @@ pub struct Module {
     model           : model::module::Plain,
     language_server : Rc<language_server::Connection>,
     logger          : Logger,
+    /// Passes the Language Server content summary of the reopened file to the `runner`.
+    reopened        : UnboundedSender<ContentSummary>,
+    /// The module's code as last read from the file. Allows telling the external changes of the
+    /// file apart from the local ones.
+    file_code       : RefCell<String>,
 }
 
 
@@ -125,12 +171,30 @@ impl Module {
         let source  = parser.parse_with_metadata(opened.content)?;
         let digest  = opened.current_version;
         let summary = ContentSummary {digest,end_of_file};
-        let model   = model::module::Plain::new(path,source.ast,source.metadata);
-        let this    = Rc::new(Module {model,language_server,logger});
-        executor::global::spawn(Self::runner(this.clone_ref(),summary));
+        let file_code = RefCell::new(source.ast.repr());
+        let model     = model::module::Plain::new(path,source.ast,source.metadata);
+        let (reopened,reopened_receiver) = futures::channel::mpsc::unbounded();
+        let this = Rc::new(Module {model,language_server,logger,reopened,file_code});
+        executor::global::spawn(Self::runner(this.clone_ref(),summary,reopened_receiver));
         Ok(this)
     }
 
+    /// Open the module in the Language Server again, e.g. after the connection has been
+    /// re-established.
+    ///
+    /// The module state held by this model is considered authoritative: the file content in the
+    /// Language Server is replaced with it.
+    pub async fn reopen(&self) -> FallibleResult<()> {
+        let file_path = self.path().file_path().clone();
+        info!(self.logger, "Reopening module {file_path}");
+        let opened      = self.language_server.client.open_text_file(&file_path).await?;
+        let end_of_file = TextLocation::at_document_end(&opened.content);
+        let digest      = opened.current_version;
+        let summary     = ContentSummary {digest,end_of_file};
+        utils::channel::emit(&self.reopened,summary);
+        Ok(())
+    }
+
     /// Create a module mock.
     pub fn mock(model:model::module::Plain) -> Rc<Self> {
         let logger = Logger::new(iformat!("Mocked Module {model.path()}"));
@@ -138,7 +202,65 @@ impl Module {
         client.expect.close_text_file(|_| Ok(()));
         // We don't expect any other call, because we don't execute `runner()`.
         let language_server = language_server::Connection::new_mock_rc(client);
-        Rc::new(Module{model,language_server,logger})
+        let (reopened,_)    = futures::channel::mpsc::unbounded();
+        let file_code       = RefCell::new(model.ast().repr());
+        Rc::new(Module{model,language_server,logger,reopened,file_code})
+    }
+
+    /// Read the module's file again and apply the changes made to it by another program.
+    ///
+    /// The file's code is compared with the one read previously: if only the file has changed, the
+    /// difference is applied to the module as a regular code change. If the module has been changed
+    /// locally as well, the local version is kept and `Reconciliation::Conflict` is returned.
+    pub async fn reload_from_file(&self, parser:&Parser) -> FallibleResult<Reconciliation> {
+        let file_path  = self.path().file_path().clone();
+        let response   = self.language_server.client.read_file(&file_path).await?;
+        let source     = parser.parse_with_metadata::<Metadata>(response.contents)?;
+        let new_code   = source.ast.repr();
+        let local_code = self.ast().repr();
+        let known_code = self.file_code.replace(new_code.clone());
+        if new_code == local_code || new_code == known_code {
+            Ok(Reconciliation::UpToDate)
+        } else if local_code != known_code {
+            Ok(Reconciliation::Conflict)
+        } else {
+            info!(self.logger,"Applying the external changes of the file {file_path}.");
+            let change     = TextChange::from_diff(&local_code,&new_code);
+            let mut id_map = self.ast().id_map();
+            apply_code_change_to_id_map(&mut id_map,&change,&local_code);
+            self.apply_code_change(change,parser,id_map)?;
+            Ok(Reconciliation::Applied)
+        }
+    }
+
+    /// Handle the Language Server's notification about the module's file being changed.
+    ///
+    /// The conflicting modification and the removal of the file are reported to the subscribers
+    /// as `Notification::ExternalChange`, so they can be shown to the user.
+    pub async fn handle_file_event(&self, kind:FileEventKind, parser:&Parser) {
+        let file_path = self.path().file_path();
+        let change    = match kind {
+            FileEventKind::Modified => match self.reload_from_file(parser).await {
+                Ok(Reconciliation::Conflict) => {
+                    error!(self.logger,"The file {file_path} has been modified by another \
+                        program, while having unsaved changes. The external changes will be \
+                        overwritten.");
+                    ExternalChange::Conflict
+                }
+                Ok(_)    => ExternalChange::Resolved,
+                Err(err) => {
+                    error!(self.logger,"Failed to reload the file {file_path}: {err}");
+                    return
+                }
+            },
+            FileEventKind::Removed => {
+                warning!(self.logger,"The file {file_path} has been removed by another program. \
+                    It will be recreated once the module is saved.");
+                ExternalChange::Removed
+            }
+            FileEventKind::Added => ExternalChange::Resolved,
+        };
+        self.model.notify_external_change(change);
     }
 }
 
@@ -167,6 +289,14 @@ impl API for Module {
         self.model.node_metadata(id)
     }
 
+    fn notes(&self) -> HashMap<NoteId,Note> {
+        self.model.notes()
+    }
+
+    fn groups(&self) -> HashMap<GroupId,Group> {
+        self.model.groups()
+    }
+
     fn update_whole(&self, content:Content) {
         self.model.update_whole(content)
     }
@@ -191,6 +321,34 @@ impl API for Module {
     fn with_node_metadata(&self, id:ast::Id, fun:Box<dyn FnOnce(&mut NodeMetadata) + '_>) {
         self.model.with_node_metadata(id,fun)
     }
+
+    fn set_note(&self, id:NoteId, note:Note) {
+        self.model.set_note(id,note)
+    }
+
+    fn remove_note(&self, id:NoteId) -> FallibleResult<Note> {
+        self.model.remove_note(id)
+    }
+
+    fn set_group(&self, id:GroupId, group:Group) {
+        self.model.set_group(id,group)
+    }
+
+    fn remove_group(&self, id:GroupId) -> FallibleResult<Group> {
+        self.model.remove_group(id)
+    }
+
+    fn open_transaction(&self, name:&str) -> Rc<undo_redo::Transaction> {
+        self.model.open_transaction(name)
+    }
+
+    fn undo(&self) -> FallibleResult<()> {
+        self.model.undo()
+    }
+
+    fn redo(&self) -> FallibleResult<()> {
+        self.model.redo()
+    }
 }
 
 
@@ -199,22 +357,37 @@ impl API for Module {
 impl Module {
     /// The asynchronous task scheduled during struct creation which listens for all module changes
     /// and send proper updates to Language Server.
-    async fn runner(self:Rc<Self>, initial_ls_content: ContentSummary) {
+    ///
+    /// When the module is reopened, the whole content is sent again to the Language Server.
+    async fn runner
+    ( self               : Rc<Self>
+    , initial_ls_content : ContentSummary
+    , reopened           : UnboundedReceiver<ContentSummary>
+    ) {
         let first_invalidation = self.full_invalidation(&initial_ls_content).await;
         let mut ls_content     = self.new_ls_content_info(initial_ls_content, first_invalidation);
-        let mut subscriber     = self.model.subscribe();
+        let notifications      = self.model.subscribe().map(RunnerEvent::Notification);
+        let reopened           = reopened.map(RunnerEvent::Reopened);
+        let mut events         = futures::stream::select(notifications,reopened);
         let weak               = Rc::downgrade(&self);
         drop(self);
 
         loop {
-            let notification = subscriber.next().await;
-            let this = weak.upgrade();
-            match (notification,this) {
-                (Some(notification),Some(this)) => {
+            let event = events.next().await;
+            let this  = weak.upgrade();
+            match (event,this) {
+                // The external changes are not made by the module, so there is nothing to send.
+                (Some(RunnerEvent::Notification(Notification::ExternalChange(_))),Some(_)) => {}
+                (Some(RunnerEvent::Notification(notification)),Some(this)) => {
                     debug!(this.logger,"Processing a notification: {notification:?}");
                     let result = this.handle_notification(&ls_content,notification).await;
                     ls_content = this.new_ls_content_info(ls_content.summary().clone(),result)
                 }
+                (Some(RunnerEvent::Reopened(reopened_content)),Some(this)) => {
+                    debug!(this.logger,"Processing the module reopening: {reopened_content:?}");
+                    let result = this.full_invalidation(&reopened_content).await;
+                    ls_content = this.new_ls_content_info(reopened_content,result)
+                }
                 _ => break,
             }
         }
@@ -264,6 +437,7 @@ impl Module {
                         range : summary.metadata.clone().into(),
                         text  : content.metadata_slice().to_string(),
                     }]).await,
+                Notification::ExternalChange(_) => Ok(summary.clone()),
             },
         }
     }
@@ -487,4 +661,39 @@ pub mod test {
         });
         test.when_stalled(move || *module.borrow_mut() = None);
     }
+
+    #[wasm_bindgen_test]
+    fn reloading_externally_modified_file() {
+        let path            = model::module::Path::from_mock_module_name("TestModule");
+        let file_path       = path.file_path().clone();
+        let initial_content = "main =\n    println \"Hello World!\"";
+        let file_content    = "main =\n    println \"Hello Enso!\"";
+
+        let setup  = LsClientSetup::new(file_path.clone(),initial_content);
+        let client = &setup.client;
+        setup.expect_invalidate(Ok(()));
+        let read_response = language_server::response::Read {contents:file_content.into()};
+        expect_call!(client.read_file(path=file_path) => Ok(read_response));
+        client.expect.apply_text_file_edit(|edit| {
+            // The id map edit goes first, the code change is the last one.
+            assert_eq!(edit.edits.last().unwrap().text,"Enso");
+            Ok(())
+        });
+        let connection                             = setup.finish();
+        let mut test                               = TestWithLocalPoolExecutor::set_up();
+        let module:Rc<RefCell<Option<Rc<Module>>>> = default();
+        let module_ref1                            = module.clone();
+        let module_ref2                            = module.clone();
+        test.run_task(async move {
+            let module = Module::open(path,connection,Parser::new_or_panic()).await.unwrap();
+            *module_ref1.borrow_mut() = Some(module);
+        });
+        test.when_stalled_run_task(async move {
+            let module = module_ref2.borrow().clone().unwrap();
+            let result = module.reload_from_file(&Parser::new_or_panic()).await.unwrap();
+            assert_eq!(result,Reconciliation::Applied);
+            assert_eq!(module.ast().repr(),file_content);
+        });
+        test.when_stalled(move || *module.borrow_mut() = None);
+    }
 }
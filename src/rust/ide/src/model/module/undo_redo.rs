@@ -0,0 +1,231 @@
+//! The undo/redo history of the module's content.
+//!
+//! The history stores snapshots of the module content taken before each change. Changes made
+//! while a `Transaction` is open are grouped into a single history frame, so they can be reverted
+//! with a single undo, e.g. code edit and the metadata edit of the added node.
+
+use crate::prelude::*;
+
+use crate::model::module::Content;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The maximum number of frames kept in the undo stack. The oldest frames are dropped first.
+pub const MAX_HISTORY_SIZE : usize = 100;
+
+
+
+// ==============
+// === Errors ===
+// ==============
+
+#[allow(missing_docs)]
+#[derive(Clone,Copy,Debug,Fail)]
+#[fail(display="There is nothing to undo.")]
+pub struct NothingToUndo;
+
+#[allow(missing_docs)]
+#[derive(Clone,Copy,Debug,Fail)]
+#[fail(display="There is nothing to redo.")]
+pub struct NothingToRedo;
+
+
+
+// =============
+// === Frame ===
+// =============
+
+/// A single step in the module history.
+#[derive(Clone,Debug)]
+pub struct Frame {
+    /// Name describing the change, e.g. "Remove node".
+    pub name    : String,
+    /// The module content as it was before the change.
+    pub content : Content,
+}
+
+
+
+// ===================
+// === Transaction ===
+// ===================
+
+/// A handle to the open transaction. All changes of the module done while the handle is alive are
+/// recorded as a single history frame. The transaction is closed once all handles are dropped.
+#[derive(Debug)]
+pub struct Transaction {
+    name     : String,
+    /// Whether the content snapshot for this transaction has been already recorded.
+    recorded : Cell<bool>,
+}
+
+impl Transaction {
+    /// The name of the transaction.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+
+
+// ===============
+// === History ===
+// ===============
+
+/// The undo/redo history of the module.
+#[derive(Debug,Default)]
+pub struct History {
+    undo_stack  : RefCell<Vec<Frame>>,
+    redo_stack  : RefCell<Vec<Frame>>,
+    transaction : RefCell<Weak<Transaction>>,
+}
+
+impl History {
+    /// Open a new transaction with the given name. If there is a transaction already open, it is
+    /// returned instead, so the nested changes become a part of the outer transaction.
+    pub fn open_transaction(&self, name:impl Into<String>) -> Rc<Transaction> {
+        if let Some(transaction) = self.transaction.borrow().upgrade() {
+            return transaction;
+        }
+        let name        = name.into();
+        let recorded    = default();
+        let transaction = Rc::new(Transaction{name,recorded});
+        *self.transaction.borrow_mut() = Rc::downgrade(&transaction);
+        transaction
+    }
+
+    /// Record the module content before making a change named `name`.
+    ///
+    /// If there is an open transaction which already recorded its snapshot, does nothing. Every
+    /// recorded change clears the redo stack.
+    pub fn record(&self, name:&str, content:&Content) {
+        let transaction = self.transaction.borrow().upgrade();
+        let name = match &transaction {
+            Some(transaction) if transaction.recorded.get() => return,
+            Some(transaction) => {
+                transaction.recorded.set(true);
+                transaction.name.clone()
+            }
+            None => name.to_owned(),
+        };
+        let content = content.clone();
+        with(self.undo_stack.borrow_mut(), |mut stack| {
+            stack.push(Frame{name,content});
+            if stack.len() > MAX_HISTORY_SIZE {
+                stack.remove(0);
+            }
+        });
+        self.redo_stack.borrow_mut().clear();
+    }
+
+    /// Take the last frame from undo stack. The `current` content is stored in the redo stack.
+    ///
+    /// Returns the content that the module should be restored to. Closes the open transaction, if
+    /// any.
+    pub fn undo(&self, current:Content) -> FallibleResult<Content> {
+        *self.transaction.borrow_mut() = default();
+        let frame = self.undo_stack.borrow_mut().pop().ok_or(NothingToUndo)?;
+        let name  = frame.name.clone();
+        self.redo_stack.borrow_mut().push(Frame{name,content:current});
+        Ok(frame.content)
+    }
+
+    /// Take the last frame from redo stack. The `current` content is stored in the undo stack.
+    ///
+    /// Returns the content that the module should be restored to. Closes the open transaction, if
+    /// any.
+    pub fn redo(&self, current:Content) -> FallibleResult<Content> {
+        *self.transaction.borrow_mut() = default();
+        let frame = self.redo_stack.borrow_mut().pop().ok_or(NothingToRedo)?;
+        let name  = frame.name.clone();
+        self.undo_stack.borrow_mut().push(Frame{name,content:current});
+        Ok(frame.content)
+    }
+
+    /// Name of the change that would be reverted by `undo`.
+    pub fn undo_name(&self) -> Option<String> {
+        self.undo_stack.borrow().last().map(|frame| frame.name.clone())
+    }
+
+    /// Name of the change that would be reapplied by `redo`.
+    pub fn redo_name(&self) -> Option<String> {
+        self.redo_stack.borrow().last().map(|frame| frame.name.clone())
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::model::module::Metadata;
+
+    use parser::api::ParsedSourceFile;
+
+    fn content(code:&str) -> Content {
+        let line     = Ast::var(code);
+        let ast      = ast::known::Module::try_new(Ast::one_line_module(line)).unwrap();
+        let metadata = Metadata::default();
+        ParsedSourceFile{ast,metadata}
+    }
+
+    fn code(content:&Content) -> String {
+        content.ast.repr()
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let history = History::default();
+        assert!(history.undo(content("a")).is_err());
+
+        history.record("First",&content("a"));
+        history.record("Second",&content("b"));
+        assert_eq!(history.undo_name(), Some("Second".to_owned()));
+
+        let restored = history.undo(content("c")).unwrap();
+        assert_eq!(code(&restored), "b");
+        assert_eq!(history.redo_name(), Some("Second".to_owned()));
+        let restored = history.undo(restored).unwrap();
+        assert_eq!(code(&restored), "a");
+        assert!(history.undo(restored.clone()).is_err());
+
+        let restored = history.redo(restored).unwrap();
+        assert_eq!(code(&restored), "b");
+
+        // New change should discard the redo stack.
+        history.record("Third",&restored);
+        assert!(history.redo_name().is_none());
+        assert!(history.redo(content("d")).is_err());
+    }
+
+    #[test]
+    fn grouping_changes_in_transaction() {
+        let history = History::default();
+        {
+            let transaction = history.open_transaction("Transaction");
+            let nested      = history.open_transaction("Nested");
+            assert_eq!(nested.name(), "Transaction");
+            history.record("First",&content("a"));
+            history.record("Second",&content("b"));
+            // The transaction stays open as long as any of its handles is alive.
+            drop(transaction);
+            history.record("Third",&content("c"));
+        }
+        history.record("Fourth",&content("d"));
+
+        assert_eq!(history.undo_name(), Some("Fourth".to_owned()));
+        assert_eq!(code(&history.undo(content("e")).unwrap()), "d");
+        assert_eq!(history.undo_name(), Some("Transaction".to_owned()));
+        assert_eq!(code(&history.undo(content("d")).unwrap()), "a");
+        assert!(history.undo_name().is_none());
+    }
+}
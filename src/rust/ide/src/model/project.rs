@@ -8,6 +8,8 @@ pub mod synchronized;
 
 use crate::prelude::*;
 
+use crate::double_representation::identifier::ReferentName;
+
 use enso_protocol::binary;
 use enso_protocol::language_server;
 use mockall::automock;
@@ -23,6 +25,9 @@ use uuid::Uuid;
 /// The API of the Project Model.
 #[automock]
 pub trait API:Debug {
+    /// Project's id, as used by the Project Manager service.
+    fn id(&self) -> Uuid;
+
     /// Project's name
     fn name(&self) -> ImString;
 
@@ -54,6 +59,39 @@ pub trait API:Debug {
     /// Set a new project name.
     fn rename_project<'a>(&'a self, name:String) -> BoxFuture<'a,FallibleResult<()>>;
 
+    /// Create a new, empty module file. Fails if the module already exists.
+    fn create_module<'a>
+    (&'a self, path:crate::model::module::Path) -> BoxFuture<'a,FallibleResult<()>>;
+
+    /// Move the module file to the new path, updating its imports in all project's modules.
+    ///
+    /// Fails if the module is currently opened or if there is already a module under the new path.
+    fn move_module<'a>
+    (&'a self, from:crate::model::module::Path, to:crate::model::module::Path)
+    -> BoxFuture<'a,FallibleResult<()>>;
+
+    /// Delete the module file and remove its imports from all project's modules.
+    ///
+    /// Fails if the module is currently opened.
+    fn delete_module<'a>
+    (&'a self, path:crate::model::module::Path) -> BoxFuture<'a,FallibleResult<()>>;
+
+    /// Rename the module, keeping it in the same directory. See `move_module`.
+    fn rename_module<'a>
+    (&'a self, path:crate::model::module::Path, new_name:&ReferentName)
+    -> BoxFuture<'a,FallibleResult<()>> {
+        let new_path = path.with_module_name(new_name);
+        self.move_module(path,new_path)
+    }
+
+    /// Rename the method defined in the module, updating its calls in this module and in all the
+    /// project's modules importing it, the nodes intending the method and the suggestion database.
+    ///
+    /// Fails if a method with the new name is already defined in the module.
+    fn rename_method<'a>
+    (&'a self, path:crate::model::module::Path, name:String, new_name:String)
+    -> BoxFuture<'a,FallibleResult<()>>;
+
     /// Returns the primary content root id for this project.
     fn content_root_id(&self) -> Uuid {
         self.json_rpc().content_root()
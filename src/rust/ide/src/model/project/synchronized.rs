@@ -2,16 +2,22 @@
 
 use crate::prelude::*;
 
+use crate::constants::SOURCE_DIRECTORY;
+use crate::double_representation::definition::DefinitionName;
+use crate::double_representation::module::ImportInfo;
+use crate::double_representation::refactorings::rename;
 use crate::model::execution_context::VisualizationUpdateData;
 use crate::model::execution_context;
 use crate::model::module;
 use crate::model::SuggestionDatabase;
 use crate::model::traits::*;
+use crate::transport::Reconnect;
 
 use enso_protocol::binary;
 use enso_protocol::binary::message::VisualisationContext;
 use enso_protocol::language_server;
 use enso_protocol::language_server::CapabilityRegistration;
+use enso_protocol::language_server::FileSystemObject;
 use enso_protocol::language_server::MethodPointer;
 use enso_protocol::project_manager;
 use parser::Parser;
@@ -29,6 +35,11 @@ use parser::Parser;
 #[fail(display="No execution context with id {} was found in the registry.", _0)]
 pub struct NoSuchExecutionContext(execution_context::Id);
 
+#[allow(missing_docs)]
+#[derive(Clone,Debug,Fail)]
+#[fail(display="Failed to recreate the execution contexts: {}", _0)]
+pub struct ExecutionContextsNotRecreated(String);
+
 
 // === Aliases ===
 
@@ -75,11 +86,156 @@ impl ExecutionContextsRegistry {
         })
     }
 
+    /// Route the notification about execution failure to the execution context it refers to.
+    pub fn handle_execution_failed
+    (&self, update:language_server::ExecutionFailed) -> FallibleResult<()> {
+        self.with_context(update.context_id, |ctx| ctx.handle_execution_failed(update))
+    }
+
     /// Registers a new ExecutionContext. It will be eligible for receiving future updates routed
     /// through `dispatch_visualization_update`.
     pub fn insert(&self, context:Rc<execution_context::Synchronized>) {
         self.0.borrow_mut().insert(context.id(),context);
     }
+
+    /// Recreate all registered contexts in the Language Server, see
+    /// `execution_context::Synchronized::recreate`. The contexts are registered again under their
+    /// new ids.
+    ///
+    /// Every context is recreated, even if some of them fail. The errors are reported together.
+    pub async fn recreate_all(&self) -> FallibleResult<()> {
+        let contexts   = self.0.borrow().values().collect_vec();
+        let mut errors = Vec::new();
+        for context in contexts {
+            let old_id = context.id();
+            if let Err(err) = context.recreate().await {
+                errors.push(format!("context {}: {}",old_id,err));
+            }
+            if context.id() != old_id {
+                self.0.borrow_mut().remove(&old_id);
+                self.insert(context);
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ExecutionContextsNotRecreated(errors.join("; ")).into())
+        }
+    }
+}
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The delay between receiving the Suggestion Database update and writing the database to the
+/// cache file. The updates received in the meantime are written together.
+const SUGGESTION_DB_CACHE_SAVE_DELAY:std::time::Duration = std::time::Duration::from_secs(10);
+
+
+
+// ==========================
+// === ConnectionRecovery ===
+// ==========================
+
+/// The delay before the second attempt of restoring the connection.
+const INITIAL_RECONNECT_DELAY:std::time::Duration = std::time::Duration::from_millis(500);
+
+/// The maximum delay between the consecutive attempts of restoring the connection.
+const MAX_RECONNECT_DELAY:std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Restores the project's state in the Language Server once the connection has been lost.
+///
+/// The recovery consists of reopening the transports, initializing the protocol on both
+/// connections, acquiring capabilities, reopening all modules and recreating all execution
+/// contexts. Failed attempts are repeated with the exponential backoff.
+#[derive(Debug)]
+pub struct ConnectionRecovery {
+    logger              : Logger,
+    language_server_rpc : Rc<language_server::Connection>,
+    language_server_bin : Rc<binary::Connection>,
+    module_registry     : Rc<model::registry::Registry<module::Path,module::Synchronized>>,
+    execution_contexts  : Rc<ExecutionContextsRegistry>,
+    transports          : RefCell<Vec<Rc<dyn Reconnect>>>,
+    in_progress         : Cell<bool>,
+}
+
+impl ConnectionRecovery {
+    /// Set the transports which will be reconnected when restoring the connection. Without them
+    /// the lost connection cannot be restored.
+    pub fn set_transports(&self, transports:Vec<Rc<dyn Reconnect>>) {
+        *self.transports.borrow_mut() = transports;
+    }
+
+    /// Check if the connection is being restored at the moment.
+    pub fn is_in_progress(&self) -> bool {
+        self.in_progress.get()
+    }
+
+    /// Start restoring the connection in the background. Does nothing if the connection is
+    /// already being restored.
+    pub fn start(self:&Rc<Self>) {
+        if self.transports.borrow().is_empty() {
+            error!(self.logger,"Cannot restore the connection: no reconnectable transports.");
+        } else if !self.in_progress.replace(true) {
+            info!(self.logger,"Restoring the connection with the Language Server.");
+            executor::global::spawn(Self::run(Rc::downgrade(self)));
+        }
+    }
+
+    /// Attempt the recovery until it succeeds or the project is dropped.
+    async fn run(weak:Weak<Self>) {
+        let mut delay = INITIAL_RECONNECT_DELAY;
+        loop {
+            let this = match weak.upgrade() {
+                Some(this) => this,
+                None       => break,
+            };
+            match this.recover().await {
+                Ok(()) => {
+                    info!(this.logger,"The connection with the Language Server has been restored.");
+                    this.in_progress.set(false);
+                    break;
+                }
+                Err(err) => {
+                    let millis = delay.as_millis();
+                    warning!(this.logger,"Failed to restore the connection: {err}. Next attempt in \
+                    {millis} ms.");
+                }
+            }
+            drop(this);
+            ensogl::system::web::sleep(delay).await;
+            delay = std::cmp::min(delay * 2, MAX_RECONNECT_DELAY);
+        }
+    }
+
+    /// A single attempt of restoring the connection and the project's state.
+    async fn recover(&self) -> FallibleResult<()> {
+        let transports = self.transports.borrow().clone();
+        for transport in transports {
+            transport.reconnect().await?;
+        }
+        let client_id = self.language_server_rpc.client_id;
+        self.language_server_rpc.init_protocol_connection(&client_id).await?;
+        let client_id = self.language_server_bin.client_id;
+        self.language_server_bin.init(client_id).await?;
+        let capability = CapabilityRegistration::create_receives_suggestions_database_updates();
+        let method     = &capability.method;
+        let options    = &capability.register_options;
+        self.language_server_rpc.acquire_capability(method,options).await?;
+        let root_id    = self.language_server_rpc.content_root();
+        let root       = language_server::Path::new(root_id,&[] as &[&str]);
+        let capability = CapabilityRegistration::create_receives_tree_updates(root);
+        let method     = &capability.method;
+        let options    = &capability.register_options;
+        self.language_server_rpc.acquire_capability(method,options).await?;
+        for module in self.module_registry.loaded_items() {
+            module.reopen().await?;
+        }
+        self.execution_contexts.recreate_all().await
+    }
 }
 
 
@@ -89,6 +245,19 @@ impl ExecutionContextsRegistry {
 // =============
 
 
+// === Errors ===
+
+#[allow(missing_docs)]
+#[derive(Clone,Debug,Fail)]
+#[fail(display="Module {} already exists.", _0)]
+pub struct ModuleAlreadyExists(language_server::Path);
+
+#[allow(missing_docs)]
+#[derive(Clone,Debug,Fail)]
+#[fail(display="Module {} is opened and cannot be moved nor deleted.", _0)]
+pub struct ModuleIsOpened(language_server::Path);
+
+
 // === Data ===
 
 /// A structure containing the project's unique ID and name.
@@ -123,6 +292,7 @@ pub struct Project {
     pub language_server_bin : Rc<binary::Connection>,
     pub module_registry     : Rc<model::registry::Registry<module::Path,module::Synchronized>>,
     pub execution_contexts  : Rc<ExecutionContextsRegistry>,
+    pub connection_recovery : Rc<ConnectionRecovery>,
     pub visualization       : controller::Visualization,
     pub suggestion_db       : Rc<SuggestionDatabase>,
     pub parser              : Parser,
@@ -145,8 +315,17 @@ impl Project {
         let json_rpc_events         = language_server_rpc.events();
         let embedded_visualizations = default();
         let language_server         = language_server_rpc.clone();
-        let module_registry         = default();
-        let execution_contexts      = default();
+        let module_registry         = Rc::new(model::registry::Registry::default());
+        let execution_contexts      = Rc::new(ExecutionContextsRegistry::default());
+        let connection_recovery     = Rc::new(ConnectionRecovery {
+            logger              : Logger::sub(&logger,"ConnectionRecovery"),
+            language_server_rpc : language_server_rpc.clone_ref(),
+            language_server_bin : language_server_bin.clone_ref(),
+            module_registry     : module_registry.clone_ref(),
+            execution_contexts  : execution_contexts.clone_ref(),
+            transports          : default(),
+            in_progress         : default(),
+        });
         let visualization           = controller::Visualization::new(language_server,embedded_visualizations);
         let name                    = RefCell::new(ImString::new(name.into()));
         let parser                  = Parser::new_or_panic();
@@ -157,7 +336,8 @@ impl Project {
         let data = Rc::new(Data {id,name});
 
         let ret = Project {data,parser,project_manager,language_server_rpc,module_registry,
-            execution_contexts,language_server_bin,logger,visualization,suggestion_db};
+            execution_contexts,connection_recovery,language_server_bin,logger,visualization,
+            suggestion_db};
 
         let binary_handler = ret.binary_event_handler();
         crate::executor::global::spawn(binary_protocol_events.for_each(binary_handler));
@@ -165,7 +345,10 @@ impl Project {
         let json_rpc_handler = ret.json_event_handler();
         crate::executor::global::spawn(json_rpc_events.for_each(json_rpc_handler));
 
+        crate::executor::global::spawn(ret.suggestion_db_cache_saver());
+
         ret.acquire_suggestion_db_updates_capability().await?;
+        ret.acquire_file_tree_updates_capability().await?;
         Ok(ret)
     }
 
@@ -190,6 +373,7 @@ impl Project {
     (&self) -> impl Fn(enso_protocol::binary::Event) -> futures::future::Ready<()> {
         let logger                  = self.logger.clone_ref();
         let weak_execution_contexts = Rc::downgrade(&self.execution_contexts);
+        let weak_recovery           = Rc::downgrade(&self.connection_recovery);
         move |event| {
             debug!(logger, "Received an event from the binary protocol: {event:?}");
             use enso_protocol::binary::client::Event;
@@ -209,9 +393,9 @@ impl Project {
                 }
                 Event::Closed => {
                     error!(logger,"Lost binary connection with the Language Server!");
-                    // TODO [wmu]
-                    //  The problem should be reported to the user and the connection should be
-                    //  reestablished, see https://github.com/luna/ide/issues/145
+                    if let Some(recovery) = weak_recovery.upgrade() {
+                        recovery.start();
+                    }
                 }
                 Event::Error(error) => {
                     error!(logger,"Error emitted by the binary data connection: {error}.");
@@ -235,6 +419,10 @@ impl Project {
         let logger                  = self.logger.clone_ref();
         let weak_execution_contexts = Rc::downgrade(&self.execution_contexts);
         let weak_suggestion_db      = Rc::downgrade(&self.suggestion_db);
+        let weak_recovery           = Rc::downgrade(&self.connection_recovery);
+        let weak_module_registry    = Rc::downgrade(&self.module_registry);
+        let visualization_watcher   = self.visualization.file_watcher();
+        let parser                  = self.parser.clone_ref();
         move |event| {
             debug!(logger, "Received an event from the json-rpc protocol: {event:?}");
             use enso_protocol::language_server::Event;
@@ -255,17 +443,37 @@ impl Project {
                 Event::Notification(Notification::ExecutionFailed(update)) => {
                     error!(logger,"Execution failed in context {update.context_id}. Error: \
                     {update.message}.");
+                    if let Some(execution_contexts) = weak_execution_contexts.upgrade() {
+                        let result = execution_contexts.handle_execution_failed(update);
+                        if let Err(error) = result {
+                            error!(logger,"Failed to handle the execution failed notification: \
+                            {error}.");
+                        }
+                    }
                 }
                 Event::Notification(Notification::SuggestionDatabaseUpdates(update)) => {
                     if let Some(suggestion_db) = weak_suggestion_db.upgrade() {
                         suggestion_db.apply_update_event(update);
                     }
                 }
+                Event::Notification(Notification::FileEvent(event)) => {
+                    visualization_watcher.handle_file_event(&event);
+                    if let Some(module_registry) = weak_module_registry.upgrade() {
+                        let mut opened = module_registry.loaded_items().into_iter();
+                        let module     = opened.find(|module| module.path() == &event.path);
+                        if let Some(module) = module {
+                            let parser = parser.clone_ref();
+                            executor::global::spawn(async move {
+                                module.handle_file_event(event.kind,&parser).await
+                            });
+                        }
+                    }
+                }
                 Event::Closed => {
                     error!(logger,"Lost JSON-RPC connection with the Language Server!");
-                    // TODO [wmu]
-                    //  The problem should be reported to the user and the connection should be
-                    //  reestablished, see https://github.com/luna/ide/issues/145
+                    if let Some(recovery) = weak_recovery.upgrade() {
+                        recovery.start();
+                    }
                 }
                 Event::Error(error) => {
                     error!(logger,"Error emitted by the binary data connection: {error}.");
@@ -276,11 +484,160 @@ impl Project {
         }
     }
 
+    /// Returns a future keeping the Suggestion Database cache file up to date. The database is
+    /// written once loaded, and then after each batch of updates or recorded picks. The future ends
+    /// once the project is dropped.
+    fn suggestion_db_cache_saver(&self) -> impl Future<Output=()> {
+        let logger             = self.logger.clone_ref();
+        let weak_suggestion_db = Rc::downgrade(&self.suggestion_db);
+        let weak_ls            = Rc::downgrade(&self.language_server_rpc);
+        let db_updates         = self.suggestion_db.subscribe().map(drop);
+        let usage_updates      = self.suggestion_db.subscribe_usage();
+        let mut updates        = futures::stream::select(db_updates,usage_updates);
+        async move {
+            loop {
+                match (weak_suggestion_db.upgrade(),weak_ls.upgrade()) {
+                    (Some(suggestion_db),Some(language_server)) => {
+                        if let Err(err) = suggestion_db.save_cache(&language_server).await {
+                            warning!(logger,"Failed to save the Suggestion Database cache: {err}");
+                        }
+                    }
+                    _ => break,
+                }
+                if updates.next().await.is_none() {
+                    break
+                }
+                ensogl::system::web::sleep(SUGGESTION_DB_CACHE_SAVE_DELAY).await;
+            }
+        }
+    }
+
     fn acquire_suggestion_db_updates_capability(&self) -> impl Future<Output=json_rpc::Result<()>> {
         let capability = CapabilityRegistration::create_receives_suggestions_database_updates();
         self.language_server_rpc.acquire_capability(&capability.method,&capability.register_options)
     }
 
+    fn acquire_file_tree_updates_capability(&self) -> impl Future<Output=json_rpc::Result<()>> {
+        let root_id    = self.language_server_rpc.content_root();
+        let root       = language_server::Path::new(root_id,&[] as &[&str]);
+        let capability = CapabilityRegistration::create_receives_tree_updates(root);
+        self.language_server_rpc.acquire_capability(&capability.method,&capability.register_options)
+    }
+
+    /// Get paths of all modules in the project, found by traversing its sources directory.
+    async fn module_paths(&self) -> FallibleResult<Vec<module::Path>> {
+        let root_id         = self.language_server_rpc.content_root();
+        let mut directories = vec![language_server::Path::new(root_id,&[SOURCE_DIRECTORY])];
+        let mut modules     = Vec::new();
+        while let Some(directory) = directories.pop() {
+            for object in self.language_server_rpc.file_list(&directory).await?.paths {
+                match object {
+                    FileSystemObject::Directory{..} => directories.push((&object).into()),
+                    FileSystemObject::File{..}      => {
+                        modules.extend(module::Path::from_file_path((&object).into()).ok())
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(modules)
+    }
+
+    /// Replace the imports of the module `from` with the imports of the module `to` in all
+    /// project's modules. If `to` is `None`, the imports are just removed.
+    ///
+    /// The opened modules are updated through their models, the others have their files rewritten.
+    async fn update_imports
+    (&self, from:&module::QualifiedName, to:Option<&module::QualifiedName>) -> FallibleResult<()> {
+        let old_import = ImportInfo::from_qualified_name(from);
+        let new_import = to.map(ImportInfo::from_qualified_name);
+        let opened     = self.module_registry.loaded_items();
+        for path in self.module_paths().await? {
+            if let Some(module) = opened.iter().find(|module| module.path() == &path) {
+                let mut info = double_representation::module::Info {ast:module.ast()};
+                if replace_import(&self.parser,&mut info,&old_import,new_import.as_ref()) {
+                    module.update_ast(info.ast);
+                }
+            } else {
+                let file_path = path.file_path();
+                let contents  = self.language_server_rpc.read_file(file_path).await?.contents;
+                let mut file  = self.parser.parse_with_metadata::<module::Metadata>(contents)?;
+                let mut info  = double_representation::module::Info {ast:file.ast};
+                if replace_import(&self.parser,&mut info,&old_import,new_import.as_ref()) {
+                    file.ast     = info.ast;
+                    let contents = file.serialize()?.content;
+                    self.language_server_rpc.write_file(file_path,&contents).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Update the calls and intended methods of the renamed module method in all the project's
+    /// modules importing the method's module, except the module defining the method.
+    ///
+    /// The opened modules are updated through their models, the others have their files rewritten.
+    async fn update_method_usages
+    (&self, definer:&module::Path, method:&module::MethodId, new_name:&str) -> FallibleResult<()> {
+        let import      = ImportInfo::from_qualified_name(&method.module);
+        let module_name = method.module.name().to_string();
+        let imports     = |ast:&ast::known::Module| {
+            let info = double_representation::module::Info {ast:ast.clone_ref()};
+            info.iter_imports().any(|imported| imported == import)
+        };
+        let rename_calls = |ast:&ast::known::Module| {
+            rename::rename_method_usages(ast,&module_name,&method.name,new_name)
+        };
+        let opened = self.module_registry.loaded_items();
+        for path in self.module_paths().await?.into_iter().filter(|path| path != definer) {
+            if let Some(module) = opened.iter().find(|module| module.path() == &path) {
+                let ast = module.ast();
+                if imports(&ast) {
+                    let _transaction = module.open_transaction("Rename method");
+                    if let Some(renamed) = rename_calls(&ast)? {
+                        module.update_ast(renamed);
+                    }
+                    module::rename_intended_method(&**module,method,new_name);
+                }
+            } else {
+                let file_path = path.file_path();
+                let contents  = self.language_server_rpc.read_file(file_path).await?.contents;
+                let mut file  = self.parser.parse_with_metadata::<module::Metadata>(contents)?;
+                if imports(&file.ast) {
+                    let renamed_calls = rename_calls(&file.ast)?;
+                    let renamed_nodes = file.metadata.rename_intended_method(method,new_name);
+                    if renamed_calls.is_some() || renamed_nodes {
+                        file.ast     = renamed_calls.unwrap_or(file.ast);
+                        let contents = file.serialize()?.content;
+                        self.language_server_rpc.write_file(file_path,&contents).await?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fail if the module is opened, as its model would no longer be synchronized with the file
+    /// after moving or deleting it.
+    fn ensure_module_not_opened(&self, path:&module::Path) -> FallibleResult<()> {
+        let mut opened = self.module_registry.loaded_items().into_iter();
+        if opened.any(|module| module.path() == path) {
+            Err(ModuleIsOpened(path.file_path().clone()).into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Fail if there is already a file under the module's path.
+    async fn ensure_module_not_exists(&self, path:&module::Path) -> FallibleResult<()> {
+        let file_path = path.file_path();
+        if self.language_server_rpc.file_exists(file_path).await?.exists {
+            Err(ModuleAlreadyExists(file_path.clone()).into())
+        } else {
+            Ok(())
+        }
+    }
+
     fn load_module(&self, path:module::Path)
     -> impl Future<Output=FallibleResult<Rc<module::Synchronized>>> {
         let language_server = self.language_server_rpc.clone_ref();
@@ -289,7 +646,26 @@ impl Project {
     }
 }
 
+/// Replace the import `old` with `new` in the module. If `new` is `None`, the import is just
+/// removed. Returns `false` if the module did not contain the `old` import.
+fn replace_import
+( parser : &Parser
+, module : &mut double_representation::module::Info
+, old    : &ImportInfo
+, new    : Option<&ImportInfo>
+) -> bool {
+    let removed = module.remove_import(old).is_ok();
+    if let Some(new) = new.filter(|_| removed) {
+        module.add_import(parser,new.clone());
+    }
+    removed
+}
+
 impl model::project::API for Project {
+    fn id(&self) -> Uuid {
+        self.data.id
+    }
+
     fn name(&self) -> ImString {
         self.data.name()
     }
@@ -344,6 +720,64 @@ impl model::project::API for Project {
         }.boxed_local()
     }
 
+    fn create_module(&self, path:module::Path) -> BoxFuture<FallibleResult<()>> {
+        async move {
+            self.ensure_module_not_exists(&path).await?;
+            // Module path always ends with the file name, so the object can be created.
+            let object = FileSystemObject::new_file(path.file_path().clone()).unwrap();
+            Ok(self.language_server_rpc.create_file(&object).await?)
+        }.boxed_local()
+    }
+
+    fn move_module(&self, from:module::Path, to:module::Path) -> BoxFuture<FallibleResult<()>> {
+        async move {
+            self.ensure_module_not_opened(&from)?;
+            self.ensure_module_not_exists(&to).await?;
+            self.language_server_rpc.move_file(from.file_path(),to.file_path()).await?;
+            let old_name = self.qualified_module_name(&from);
+            let new_name = self.qualified_module_name(&to);
+            self.update_imports(&old_name,Some(&new_name)).await
+        }.boxed_local()
+    }
+
+    fn delete_module(&self, path:module::Path) -> BoxFuture<FallibleResult<()>> {
+        async move {
+            self.ensure_module_not_opened(&path)?;
+            self.language_server_rpc.delete_file(path.file_path()).await?;
+            let name = self.qualified_module_name(&path);
+            self.update_imports(&name,None).await
+        }.boxed_local()
+    }
+
+    fn rename_method
+    (&self, path:module::Path, name:String, new_name:String) -> BoxFuture<FallibleResult<()>> {
+        async move {
+            let module_name     = self.qualified_module_name(&path);
+            let definer         = self.module(path.clone()).await?;
+            let ast             = definer.ast();
+            let definition      = DefinitionName::new_plain(&name);
+            let new_definition  = DefinitionName::new_plain(&new_name);
+            let defined_in_code = double_representation::module::locate_child(&ast,&new_definition);
+            let defined_in_db   = self.suggestion_db.lookup_module_method(&new_name,&module_name);
+            if defined_in_code.is_ok() || defined_in_db.is_some() {
+                return Err(rename::NameAlreadyUsed(new_name).into())
+            }
+            let method = self.suggestion_db.lookup_module_method(&name,&module_name);
+            let method = method.and_then(|entry| entry.method_id()).unwrap_or_else(|| {
+                let defined_on_type = module_name.name().to_string();
+                module::MethodId {module:module_name.clone(),defined_on_type,name:name.clone()}
+            });
+            info!(self.logger,"Renaming method {name} of {module_name} to {new_name}.");
+            {
+                let _transaction = definer.open_transaction("Rename method");
+                definer.update_ast(rename::rename_method(&ast,&definition,&new_name)?);
+                module::rename_intended_method(&*definer,&method,&new_name);
+            }
+            self.suggestion_db.rename_method(&method,&new_name);
+            self.update_method_usages(&path,&method,&new_name).await
+        }.boxed_local()
+    }
+
     fn content_root_id(&self) -> Uuid {
         self.language_server_rpc.content_root()
     }
@@ -357,7 +791,9 @@ impl model::project::API for Project {
 mod test {
     use super::*;
 
+    use crate::constants::CACHE_DIRECTORY;
     use crate::constants::DEFAULT_PROJECT_NAME;
+    use crate::constants::SUGGESTION_DATABASE_CACHE_FILE;
     use crate::executor::test_utils::TestWithLocalPoolExecutor;
 
     use enso_protocol::types::Sha3_224;
@@ -392,11 +828,24 @@ mod test {
                 entries: vec![],
                 current_version: 0
             };
+            let cache_path = language_server::Path::new(default(),&[CACHE_DIRECTORY,
+                SUGGESTION_DATABASE_CACHE_FILE]);
+            expect_call!(json_client.read_file(path=cache_path.clone())
+                => Err(json_rpc::error::RpcError::LostConnection));
             expect_call!(json_client.get_suggestions_database() => Ok(initial_suggestions_db));
+            json_client.expect.write_file(move |path,_| {
+                assert_eq!(*path,cache_path);
+                Ok(())
+            });
             let capability_reg = CapabilityRegistration::create_receives_suggestions_database_updates();
             let method         = capability_reg.method;
             let options        = capability_reg.register_options;
             expect_call!(json_client.acquire_capability(method,options) => Ok(()));
+            let root           = language_server::Path::new(default(),&[] as &[&str]);
+            let capability_reg = CapabilityRegistration::create_receives_tree_updates(root);
+            let method         = capability_reg.method;
+            let options        = capability_reg.register_options;
+            expect_call!(json_client.acquire_capability(method,options) => Ok(()));
 
             setup_mock_json(&mut json_client);
             setup_mock_binary(&mut binary_client);
@@ -433,15 +882,254 @@ mod test {
     }
 
     fn mock_calls_for_opening_text_file
+    (client:&language_server::MockClient, path:language_server::Path, content:&str) {
+        mock_calls_for_reopening_text_file(client,path.clone(),content);
+        expect_call!(client.close_text_file(path) => Ok(()));
+    }
+
+    fn mock_calls_for_reopening_text_file
     (client:&language_server::MockClient, path:language_server::Path, content:&str) {
         let content          = content.to_string();
         let current_version  = Sha3_224::new(content.as_bytes());
         let write_capability = CapabilityRegistration::create_can_edit_text_file(path.clone());
         let write_capability = Some(write_capability);
         let open_response    = response::OpenTextFile {content,current_version,write_capability};
-        expect_call!(client.open_text_file(path=path.clone()) => Ok(open_response));
+        expect_call!(client.open_text_file(path=path) => Ok(open_response));
         client.expect.apply_text_file_edit(|_| Ok(()));
-        expect_call!(client.close_text_file(path) => Ok(()));
+    }
+
+    /// Set up the calls initializing the protocol and acquiring the capabilities again, made when
+    /// the connection is restored.
+    fn mock_calls_for_restoring_connection(client:&language_server::MockClient) {
+        let client_id     = Uuid::default();
+        let content_roots = vec![default()];
+        let init_response = response::InitProtocolConnection {content_roots};
+        expect_call!(client.init_protocol_connection(client_id) => Ok(init_response));
+        let capability = CapabilityRegistration::create_receives_suggestions_database_updates();
+        let method     = capability.method;
+        let options    = capability.register_options;
+        expect_call!(client.acquire_capability(method,options) => Ok(()));
+        let root       = language_server::Path::new(default(),&[] as &[&str]);
+        let capability = CapabilityRegistration::create_receives_tree_updates(root);
+        let method     = capability.method;
+        let options    = capability.register_options;
+        expect_call!(client.acquire_capability(method,options) => Ok(()));
+    }
+
+    fn mock_binary_init(client:&mut enso_protocol::binary::MockClient) {
+        client.expect_init().times(1).return_once(|_| {
+            futures::future::ready(Ok(())).boxed_local()
+        });
+    }
+
+    fn set_reconnecting_transport(project:&Project) {
+        let mut transport = crate::transport::MockReconnect::new();
+        transport.expect_reconnect().times(1).return_once(|| {
+            futures::future::ready(Ok(())).boxed_local()
+        });
+        project.connection_recovery.set_transports(vec![Rc::new(transport)]);
+    }
+
+    #[wasm_bindgen_test]
+    fn moving_module() {
+        let main_path = module::Path::from_mock_module_name("Main");
+        let from      = module::Path::from_mock_module_name("Foo");
+        let to        = module::Path::from_mock_module_name("Bar");
+        let main_code = "import Unnamed.Foo\n\nmain = here.foo";
+        let Fixture{mut test,project,..} = Fixture::new(|ls_json| {
+            let root_id       = from.file_path().root_id;
+            let src_directory = language_server::Path::new(root_id,&[SOURCE_DIRECTORY]);
+            let paths         = vec!
+                [ FileSystemObject::new_file(main_path.file_path().clone()).unwrap()
+                , FileSystemObject::new_file(to.file_path().clone()).unwrap()
+                ];
+            let exists     = response::FileExists {exists:true};
+            let not_exists = response::FileExists {exists:false};
+            expect_call!(ls_json.file_exists(path=main_path.file_path().clone()) => Ok(exists));
+            expect_call!(ls_json.file_exists(path=to.file_path().clone()) => Ok(not_exists));
+            expect_call!(ls_json.move_file(from=from.file_path().clone(),
+                to=to.file_path().clone()) => Ok(()));
+            expect_call!(ls_json.file_list(path=src_directory) => Ok(response::FileList {paths}));
+            let moved_module = response::Read {contents:"foo = 2".to_string()};
+            mock_calls_for_opening_text_file(ls_json,main_path.file_path().clone(),main_code);
+            expect_call!(ls_json.read_file(path=to.file_path().clone()) => Ok(moved_module));
+        }, |_|{});
+
+        test.run_task(async move {
+            let main = project.module(main_path.clone_ref()).await.unwrap();
+            assert!(project.move_module(main_path.clone_ref(),to.clone_ref()).await.is_err());
+            assert!(project.move_module(from.clone_ref(),main_path.clone_ref()).await.is_err());
+            project.move_module(from,to).await.unwrap();
+            module::test::expect_code(&*main,"import Unnamed.Bar\n\nmain = here.foo");
+        });
+    }
+
+    #[wasm_bindgen_test]
+    fn renaming_method() {
+        let main_path = module::Path::from_mock_module_name("Main");
+        let bar_path  = module::Path::from_mock_module_name("Bar");
+        let main_code = "foo = 2\n\nbar = 3\n\nmain = here.foo";
+        let bar_code  = "import Unnamed.Main\n\nmain = Main.foo + 1";
+        let Fixture{mut test,project,..} = Fixture::new(|ls_json| {
+            let root_id       = main_path.file_path().root_id;
+            let src_directory = language_server::Path::new(root_id,&[SOURCE_DIRECTORY]);
+            let paths         = vec!
+                [ FileSystemObject::new_file(main_path.file_path().clone()).unwrap()
+                , FileSystemObject::new_file(bar_path.file_path().clone()).unwrap()
+                ];
+            let bar_module    = response::Read {contents:bar_code.to_string()};
+            let bar_file_path = bar_path.file_path().clone();
+            mock_calls_for_opening_text_file(ls_json,main_path.file_path().clone(),main_code);
+            expect_call!(ls_json.file_list(path=src_directory) => Ok(response::FileList {paths}));
+            expect_call!(ls_json.read_file(path=bar_file_path.clone()) => Ok(bar_module));
+            ls_json.expect.write_file(move |path,contents| {
+                assert_eq!(*path,bar_file_path);
+                assert!(contents.starts_with("import Unnamed.Main\n\nmain = Main.baz + 1"));
+                Ok(())
+            });
+        }, |_|{});
+
+        test.run_task(async move {
+            let main = project.module(main_path.clone_ref()).await.unwrap();
+            let foo  = "foo".to_string();
+            let bar  = "bar".to_string();
+            assert!(project.rename_method(main_path.clone_ref(),foo.clone(),bar).await.is_err());
+            project.rename_method(main_path,foo,"baz".to_string()).await.unwrap();
+            module::test::expect_code(&*main,"baz = 2\n\nbar = 3\n\nmain = here.baz");
+        });
+    }
+
+    #[wasm_bindgen_test]
+    fn reloading_externally_modified_module() {
+        let path = module::Path::from_mock_module_name("Main");
+        let Fixture{mut test,project,json_events_sender,..} = Fixture::new(|ls_json| {
+            let modified = response::Read {contents:"main = 2 + 3".to_string()};
+            mock_calls_for_opening_text_file(ls_json,path.file_path().clone(),"main = 2 + 2");
+            expect_call!(ls_json.read_file(path=path.file_path().clone()) => Ok(modified));
+            ls_json.expect.apply_text_file_edit(|_| Ok(()));
+        }, |_|{});
+
+        let module = test.expect_completion(project.module(path.clone_ref())).unwrap();
+        let kind   = language_server::FileEventKind::Modified;
+        let event  = language_server::FileEvent {path:path.file_path().clone(),kind};
+        let event  = language_server::Notification::FileEvent(event);
+        json_events_sender.unbounded_send(language_server::Event::Notification(event)).unwrap();
+        test.run_until_stalled();
+        module::test::expect_code(&*module,"main = 2 + 3");
+    }
+
+    #[wasm_bindgen_test]
+    fn reporting_removed_module_file() {
+        let path = module::Path::from_mock_module_name("Main");
+        let Fixture{mut test,project,json_events_sender,..} = Fixture::new(|ls_json| {
+            mock_calls_for_opening_text_file(ls_json,path.file_path().clone(),"main = 2 + 2");
+        }, |_|{});
+
+        let module           = test.expect_completion(project.module(path.clone_ref())).unwrap();
+        let mut notification = module.subscribe();
+        let kind             = language_server::FileEventKind::Removed;
+        let event            = language_server::FileEvent {path:path.file_path().clone(),kind};
+        let event            = language_server::Notification::FileEvent(event);
+        json_events_sender.unbounded_send(language_server::Event::Notification(event)).unwrap();
+        test.run_until_stalled();
+        let expected = module::Notification::ExternalChange(module::ExternalChange::Removed);
+        assert_eq!(test.expect_completion(notification.next()),Some(expected));
+        module::test::expect_code(&*module,"main = 2 + 2");
+    }
+
+    #[wasm_bindgen_test]
+    fn restoring_lost_connection() {
+        use execution_context::plain::test::MockData;
+        use execution_context::synchronized::test::Fixture as ExecutionFixture;
+
+        let path          = module::Path::from_mock_module_name("Main");
+        let context_data  = MockData::new();
+        let new_data      = MockData {context_id:Uuid::new_v4(),..MockData::new()};
+        let expression_id = Uuid::new_v4();
+        let vis           = execution_context::Visualization {
+            id                   : Uuid::new_v4(),
+            ast_id               : Uuid::new_v4(),
+            expression           : "".to_string(),
+            visualisation_module : context_data.module_qualified_name(),
+        };
+        let Fixture{mut test,project,json_events_sender,..} = Fixture::new(|client| {
+            let (old_id,new_id) = (context_data.context_id,new_data.context_id);
+            let stack_item      = language_server::StackItem::LocalCall(language_server::LocalCall {
+                expression_id
+            });
+            mock_calls_for_opening_text_file(client,path.file_path().clone(),"main = 2 + 2");
+            ExecutionFixture::mock_create_push_destroy_calls(&context_data,client);
+            expect_call!(client.push_to_execution_context(old_id,stack_item.clone()) => Ok(()));
+            expect_call!(client.attach_visualisation(vis.id,vis.ast_id,vis.config(old_id))
+                => Ok(()));
+
+            mock_calls_for_restoring_connection(client);
+            mock_calls_for_reopening_text_file(client,path.file_path().clone(),"main = 2 + 2");
+            ExecutionFixture::mock_create_push_destroy_calls(&new_data,client);
+            expect_call!(client.push_to_execution_context(new_id,stack_item) => Ok(()));
+            expect_call!(client.attach_visualisation(vis.id,vis.ast_id,vis.config(new_id))
+                => Ok(()));
+            client.require_all_calls();
+        }, mock_binary_init);
+
+        let module    = test.expect_completion(project.module(path.clone_ref())).unwrap();
+        let method    = context_data.main_method_pointer();
+        let execution = test.expect_completion(project.create_execution_context(method)).unwrap();
+        let item      = model::execution_context::LocalCall {
+            call       : expression_id,
+            definition : context_data.main_method_pointer(),
+        };
+        test.expect_completion(execution.push(item.clone())).unwrap();
+        let _updates = test.expect_completion(execution.attach_visualization(vis.clone()));
+        set_reconnecting_transport(&project);
+
+        json_events_sender.unbounded_send(language_server::Event::Closed).unwrap();
+        test.run_until_stalled();
+        assert!(!project.connection_recovery.is_in_progress());
+        let no_op = |_| Ok(());
+        assert!(project.execution_contexts.with_context(context_data.context_id,no_op).is_err());
+        assert!(project.execution_contexts.with_context(new_data.context_id,no_op).is_ok());
+        assert_eq!(execution.stack_items().collect_vec(),vec![item]);
+        assert_eq!(execution.active_visualizations(),vec![vis.id]);
+        module::test::expect_code(&*module,"main = 2 + 2");
+    }
+
+    #[wasm_bindgen_test]
+    fn failing_to_restore_execution_context() {
+        use execution_context::plain::test::MockData;
+        use execution_context::synchronized::test::Fixture as ExecutionFixture;
+
+        let context_data = MockData::new();
+        let new_data     = MockData {context_id:Uuid::new_v4(),..MockData::new()};
+        let Fixture{mut test,project,json_events_sender,..} = Fixture::new(|client| {
+            let (old_id,new_id) = (context_data.context_id,new_data.context_id);
+            let root_frame      = language_server::ExplicitCall {
+                method_pointer                   : context_data.main_method_pointer(),
+                this_argument_expression         : None,
+                positional_arguments_expressions : vec![]
+            };
+            let stack_item = language_server::StackItem::ExplicitCall(root_frame);
+            let creation   = ExecutionFixture::expected_creation_response(&new_data);
+            let error      = json_rpc::error::RpcError::LostConnection;
+            ExecutionFixture::mock_create_push_destroy_calls(&context_data,client);
+            mock_calls_for_restoring_connection(client);
+            expect_call!(client.create_execution_context() => Ok(creation));
+            expect_call!(client.push_to_execution_context(new_id,stack_item) => Err(error));
+            expect_call!(client.destroy_execution_context(new_id) => Ok(()));
+            expect_call!(client.destroy_execution_context(old_id) => Ok(()));
+            client.require_all_calls();
+        }, mock_binary_init);
+
+        let method     = context_data.main_method_pointer();
+        let _execution = test.expect_completion(project.create_execution_context(method)).unwrap();
+        set_reconnecting_transport(&project);
+
+        json_events_sender.unbounded_send(language_server::Event::Closed).unwrap();
+        test.run_until_stalled();
+        assert!(project.connection_recovery.is_in_progress());
+        let no_op = |_| Ok(());
+        assert!(project.execution_contexts.with_context(context_data.context_id,no_op).is_ok());
+        assert!(project.execution_contexts.with_context(new_data.context_id,no_op).is_err());
     }
 
     /// This tests checks mainly if:
@@ -491,4 +1179,44 @@ mod test {
         assert_eq!(value_info.typename, value_update.typename.clone().map(ImString::new));
         assert_eq!(value_info.method_call, value_update.method_pointer);
     }
+
+    #[wasm_bindgen_test]
+    fn execution_failure_handling() {
+        use execution_context::synchronized::test::Fixture as ExecutionFixture;
+        use language_server::Notification::ExecutionFailed;
+        use language_server::Notification::ExpressionValuesComputed;
+        use language_server::Event;
+
+        let context_data = execution_context::plain::test::MockData::new();
+        let Fixture {mut test,project,json_events_sender,..} = Fixture::new(|mock_json_client| {
+            ExecutionFixture::mock_create_push_destroy_calls(&context_data,mock_json_client);
+            mock_json_client.require_all_calls();
+        }, |_| {});
+        let execution = project.create_execution_context(context_data.main_method_pointer());
+        let execution = test.expect_completion(execution).unwrap();
+        let status    = execution.execution_status();
+        assert!(!status.is_failed());
+
+        // Failure of the other context should be ignored.
+        let context_id = execution_context::Id::new_v4();
+        let message    = "Other context failed.".to_string();
+        let failure    = language_server::ExecutionFailed {context_id,message};
+        json_events_sender.unbounded_send(Event::Notification(ExecutionFailed(failure))).unwrap();
+        test.run_until_stalled();
+        assert!(!status.is_failed());
+
+        let context_id = context_data.context_id;
+        let message    = "Stack overflow.".to_string();
+        let failure    = language_server::ExecutionFailed {context_id,message};
+        json_events_sender.unbounded_send(Event::Notification(ExecutionFailed(failure))).unwrap();
+        test.run_until_stalled();
+        assert_eq!(status.failure(), Some("Stack overflow.".to_string()));
+
+        // Computed values mean that the execution is working again.
+        let notification = ExecutionFixture::mock_values_computed_update(&context_data);
+        let event        = Event::Notification(ExpressionValuesComputed(notification));
+        json_events_sender.unbounded_send(event).unwrap();
+        test.run_until_stalled();
+        assert!(!status.is_failed());
+    }
 }
@@ -111,6 +111,16 @@ where K : Clone + Eq + Hash {
         }
     }
 
+    /// Get handles to all items which are currently loaded. Items being in the middle of loading
+    /// are not included.
+    pub fn loaded_items(&self) -> Vec<Rc<V>> {
+        let registry = self.registry.borrow();
+        registry.values().filter_map(|entry| match entry {
+            Entry::Loaded(handle) => Some(handle),
+            Entry::Loading(_)     => None,
+        }).collect()
+    }
+
     async fn get(&self, key:&K) -> Result<Option<Rc<V>>,LoadingError> {
         loop {
             let entry = self.registry.borrow_mut().get(&key);
@@ -1,7 +1,11 @@
 //! The module contains all structures for representing suggestions and their database.
 
+pub mod index;
+
 use crate::prelude::*;
 
+use crate::constants::CACHE_DIRECTORY;
+use crate::constants::SUGGESTION_DATABASE_CACHE_FILE;
 use crate::double_representation::module::QualifiedName;
 use crate::model::module::MethodId;
 use crate::notification;
@@ -13,7 +17,10 @@ use flo_stream::Subscriber;
 use language_server::types::SuggestionsDatabaseVersion;
 use language_server::types::SuggestionDatabaseUpdatesEvent;
 use parser::DocParser;
+use serde::Deserialize;
+use serde::Serialize;
 
+pub use index::Query;
 pub use language_server::types::SuggestionEntryArgument as Argument;
 pub use language_server::types::SuggestionId as EntryId;
 pub use language_server::types::SuggestionsDatabaseUpdate as Update;
@@ -46,7 +53,7 @@ pub struct MissingThisOnMethod(pub String);
 // =============
 
 /// A type of suggestion entry.
-#[derive(Copy,Clone,Debug,Eq,PartialEq)]
+#[derive(Copy,Clone,Debug,Deserialize,Eq,PartialEq,Serialize)]
 #[allow(missing_docs)]
 pub enum EntryKind {
     Atom,Function,Local,Method
@@ -57,7 +64,7 @@ pub enum EntryKind {
 /// Methods are visible "Everywhere", as they are imported on a module level, so they are not
 /// specific to any particular span in the module file.
 /// However local variables and local function have limited visibility.
-#[derive(Clone,Debug,Eq,PartialEq)]
+#[derive(Clone,Debug,Deserialize,Eq,PartialEq,Serialize)]
 pub enum Scope {
     /// The entry is visible in the whole module where it was defined. It can be also brought to
     /// other modules by import declarations.
@@ -71,7 +78,7 @@ pub enum Scope {
 
 
 /// The Suggestion Database Entry.
-#[derive(Clone,Debug,Eq,PartialEq)]
+#[derive(Clone,Debug,Deserialize,Eq,PartialEq,Serialize)]
 pub struct Entry {
     /// A name of suggested object.
     pub name : String,
@@ -268,6 +275,33 @@ pub enum Notification {
 
 
 
+// =============
+// === Cache ===
+// =============
+
+/// The number of times the user picked each entry, keyed by [`usage_key`].
+type Usage = HashMap<String,usize>;
+
+/// The key identifying the entry in the usage statistics. Unlike the entry id, it does not change
+/// between Language Server sessions.
+fn usage_key(entry:&Entry) -> String {
+    let module    = &entry.module;
+    let self_type = entry.self_type.as_ref().map_or("",|self_type| self_type.as_str());
+    let name      = &entry.name;
+    iformat!("{module}:{self_type}:{name}")
+}
+
+/// The content of the Suggestion Database, as stored in the project's cache file.
+#[derive(Clone,Debug,Deserialize,Serialize)]
+struct Cache {
+    version : SuggestionsDatabaseVersion,
+    entries : Vec<(EntryId,Entry)>,
+    #[serde(default)]
+    usage   : Usage,
+}
+
+
+
 // ================
 // === Database ===
 // ================
@@ -278,12 +312,22 @@ pub enum Notification {
 /// often-called Language Server methods returns the list of keys of this database instead of the
 /// whole entries. Additionally the suggestions contains information about functions and their
 /// argument names and types.
+///
+/// The database may be stored in the project's cache file, so the next time the project is opened
+/// it does not have to be fetched from the Language Server.
 #[derive(Clone,Debug,Default)]
 pub struct SuggestionDatabase {
-    logger        : Logger,
-    entries       : RefCell<HashMap<EntryId,Rc<Entry>>>,
-    version       : Cell<SuggestionsDatabaseVersion>,
-    notifications : notification::Publisher<Notification>,
+    logger         : Logger,
+    entries        : RefCell<HashMap<EntryId,Rc<Entry>>>,
+    index          : RefCell<index::Index>,
+    version        : Cell<SuggestionsDatabaseVersion>,
+    /// The version of the database stored in the cache file, if known.
+    cached_version : Cell<Option<SuggestionsDatabaseVersion>>,
+    notifications  : notification::Publisher<Notification>,
+    usage          : RefCell<Usage>,
+    /// Whether the usage statistics changed since they were written to the cache file.
+    usage_changed  : Cell<bool>,
+    usage_notifier : notification::Publisher<()>,
 }
 
 impl SuggestionDatabase {
@@ -298,36 +342,131 @@ impl SuggestionDatabase {
     /// Create a database filled with entries provided by the given iterator.
     pub fn new_from_entries<'a>
     (logger:impl AnyLogger, entries:impl IntoIterator<Item=(&'a SuggestionId,&'a Entry)>) -> Self {
-        let ret     = Self::new_empty(logger);
-        let entries = entries.into_iter().map(|(id,entry)| (*id,Rc::new(entry.clone())));
-        ret.entries.borrow_mut().extend(entries);
+        let ret = Self::new_empty(logger);
+        for (id,entry) in entries {
+            ret.insert_entry(*id,Rc::new(entry.clone()));
+        }
         ret
     }
 
     /// Create a new database which will take its initial content from the Language Server.
+    ///
+    /// The content is loaded from the project's cache file if it has the same version as the
+    /// Language Server's database. Otherwise the whole database is fetched.
+    ///
+    /// The usage statistics are always restored from the cache file, even if it is outdated.
     pub async fn create_synchronized
     (language_server:&language_server::Connection) -> FallibleResult<Self> {
+        let logger    = Logger::new("SuggestionDatabase");
+        let mut usage = Usage::default();
+        match Self::read_cache(language_server).await {
+            Ok(cache) => {
+                let client  = &language_server.client;
+                let version = client.get_suggestions_database_version().await?.current_version;
+                if cache.version == version {
+                    return Ok(Self::from_cache(cache))
+                }
+                info!(logger,"The cached Suggestion Database is outdated.");
+                usage = cache.usage;
+            }
+            Err(err) => info!(logger,"Cannot load the cached Suggestion Database: {err}"),
+        }
         let response = language_server.client.get_suggestions_database().await?;
-        Ok(Self::from_ls_response(response))
+        let database = Self::from_ls_response(response);
+        *database.usage.borrow_mut() = usage;
+        Ok(database)
+    }
+
+    /// The path of the project's file where the database is cached.
+    pub fn cache_path(language_server:&language_server::Connection) -> language_server::Path {
+        let root_id  = language_server.content_root();
+        let segments = &[CACHE_DIRECTORY,SUGGESTION_DATABASE_CACHE_FILE];
+        language_server::Path::new(root_id,segments)
+    }
+
+    /// Read the project's cache file.
+    async fn read_cache(language_server:&language_server::Connection) -> FallibleResult<Cache> {
+        let path     = Self::cache_path(language_server);
+        let contents = language_server.client.read_file(&path).await?.contents;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Create a new database model from the content of the cache file.
+    fn from_cache(cache:Cache) -> Self {
+        let database = Self::new_empty(Logger::new("SuggestionDatabase"));
+        for (id,entry) in cache.entries {
+            database.insert_entry(id,Rc::new(entry));
+        }
+        database.version.set(cache.version);
+        database.cached_version.set(Some(cache.version));
+        *database.usage.borrow_mut() = cache.usage;
+        database
+    }
+
+    /// Write the database to the project's cache file, unless the file is already up to date.
+    pub async fn save_cache(&self, language_server:&language_server::Connection)
+    -> FallibleResult<()> {
+        let version = self.version.get();
+        if self.cached_version.get() != Some(version) || self.usage_changed.get() {
+            let entries  = self.entries.borrow().iter().map(|(id,entry)| {
+                (*id,entry.deref().clone())
+            }).sorted_by_key(|(id,_)| *id).collect();
+            let usage    = self.usage.borrow().clone();
+            let contents = serde_json::to_string(&Cache {version,entries,usage})?;
+            let path     = Self::cache_path(language_server);
+            self.usage_changed.set(false);
+            if let Err(err) = language_server.client.write_file(&path,&contents).await {
+                self.usage_changed.set(true);
+                return Err(err.into())
+            }
+            self.cached_version.set(Some(version));
+        }
+        Ok(())
+    }
+
+    /// Record that the user picked the entry, e.g. in the searcher.
+    pub fn record_pick(&self, entry:&Entry) {
+        *self.usage.borrow_mut().entry(usage_key(entry)).or_default() += 1;
+        self.usage_changed.set(true);
+        self.usage_notifier.notify(());
+    }
+
+    /// Get the number of times the user picked the entry in this project.
+    pub fn pick_count(&self, entry:&Entry) -> usize {
+        self.usage.borrow().get(&usage_key(entry)).copied().unwrap_or_default()
+    }
+
+    /// Subscribe for notifications about picks recorded with [`record_pick`].
+    pub fn subscribe_usage(&self) -> Subscriber<()> {
+        self.usage_notifier.subscribe()
     }
 
     /// Create a new database model from response received from the Language Server.
     fn from_ls_response(response:language_server::response::GetSuggestionDatabase) -> Self {
         let logger      = Logger::new("SuggestionDatabase");
+        let mut index   = index::Index::default();
         let mut entries = HashMap::new();
         for ls_entry in response.entries {
             let id = ls_entry.id;
             let logger_entry = Logger::new("SuggestionEntry");
             match Entry::from_ls_entry(ls_entry.suggestion, logger_entry) {
-                Ok(entry) => { entries.insert(id, Rc::new(entry)); },
+                Ok(entry) => {
+                    index.insert(id,&entry);
+                    entries.insert(id, Rc::new(entry));
+                },
                 Err(err)  => { error!(logger,"Discarded invalid entry {id}: {err}"); },
             }
         }
         Self {
             logger,
-            entries       : RefCell::new(entries),
-            version       : Cell::new(response.current_version),
-            notifications : default()
+            entries        : RefCell::new(entries),
+            index          : RefCell::new(index),
+            version        : Cell::new(response.current_version),
+            cached_version : default(),
+            notifications  : default(),
+            usage          : default(),
+            usage_changed  : default(),
+            usage_notifier : default(),
         }
     }
 
@@ -342,24 +481,30 @@ impl SuggestionDatabase {
     }
 
     /// Apply the update event to the database.
+    ///
+    /// The events not newer than the database's version are ignored, as their updates are already
+    /// included in the content fetched from the Language Server or loaded from the cache.
     pub fn apply_update_event(&self, event:SuggestionDatabaseUpdatesEvent) {
+        if event.current_version <= self.version.get() {
+            debug!(self.logger,"Skipping the already applied update {event.current_version}.");
+            return
+        }
         for update in event.updates {
-            let mut entries = self.entries.borrow_mut();
             match update {
                 Update::Add {id,suggestion} => match suggestion.try_into() {
-                    Ok(entry) => { entries.insert(id,Rc::new(entry));                       },
+                    Ok(entry) => { self.insert_entry(id,Rc::new(entry));                    },
                     Err(err)  => { error!(self.logger, "Discarding update for {id}: {err}") },
                 },
                 Update::Remove {id} => {
-                    let removed = entries.remove(&id);
+                    let removed = self.remove_entry(id);
                     if removed.is_none() {
                         error!(self.logger, "Received Remove event for nonexistent id: {id}");
                     }
                 },
                 Update::Modify {id,return_type} => {
-                    if let Some(old_entry) = entries.get(&id) {
+                    if let Some(old_entry) = self.remove_entry(id) {
                         let new_entry = Entry {return_type,..old_entry.deref().clone()};
-                        entries.insert(id,Rc::new(new_entry));
+                        self.insert_entry(id,Rc::new(new_entry));
                     } else {
                         error!(self.logger, "Received Modify event for nonexistent id: {id}");
                     }
@@ -370,6 +515,59 @@ impl SuggestionDatabase {
         self.notifications.notify(Notification::Updated);
     }
 
+    /// Put the entry to the database and its index, replacing the entry with the same id.
+    fn insert_entry(&self, id:EntryId, entry:Rc<Entry>) {
+        self.remove_entry(id);
+        self.index.borrow_mut().insert(id,&entry);
+        self.entries.borrow_mut().insert(id,entry);
+    }
+
+    /// Remove the entry from the database and its index. Returns the removed entry.
+    fn remove_entry(&self, id:EntryId) -> Option<Rc<Entry>> {
+        let removed = self.entries.borrow_mut().remove(&id);
+        if let Some(entry) = &removed {
+            self.index.borrow_mut().remove(id,entry);
+        }
+        removed
+    }
+
+    /// Rename the method's entry after the method was renamed in the code, so it can be suggested
+    /// under the new name before the Language Server sends the database update. The number of
+    /// times the method was picked is kept.
+    pub fn rename_method(&self, id:&MethodId, new_name:&str) {
+        let found = self.entries.borrow().iter().find_map(|(entry_id,entry)| {
+            entry.method_id().contains(id).as_some((*entry_id,entry.clone_ref()))
+        });
+        if let Some((entry_id,entry)) = found {
+            let renamed = Entry {name:new_name.to_owned(),..entry.deref().clone()};
+            let usage   = self.usage.borrow_mut().remove(&usage_key(&entry));
+            if let Some(usage) = usage {
+                self.usage.borrow_mut().insert(usage_key(&renamed),usage);
+                self.usage_changed.set(true);
+            }
+            self.insert_entry(entry_id,Rc::new(renamed));
+            self.notifications.notify(Notification::Updated);
+        }
+    }
+
+    /// Search the database for entries meeting the query's criteria, without asking the Language
+    /// Server. The entries best matching the query's pattern go first.
+    pub fn search(&self, query:&Query) -> Vec<Rc<Entry>> {
+        let entries    = self.entries.borrow();
+        let candidates = match self.index.borrow().candidates(query) {
+            Some(ids) => ids.into_iter().collect_vec(),
+            None      => entries.keys().cloned().collect_vec(),
+        };
+        let mut found = candidates.into_iter().filter_map(|id| {
+            let entry = entries.get(&id)?;
+            query.check(entry).map(|subsequence| (id,entry.clone_ref(),subsequence))
+        }).collect_vec();
+        found.sort_by(|(lhs_id,_,lhs),(rhs_id,_,rhs)| {
+            lhs.compare_scores(rhs).reverse().then(lhs_id.cmp(rhs_id))
+        });
+        found.into_iter().map(|(_,entry,_)| entry).collect()
+    }
+
 
     /// Look up given id in the suggestion database and if it is a known method obtain a pointer to
     /// it.
@@ -387,36 +585,57 @@ impl SuggestionDatabase {
     /// Search the database for entries with given name and visible at given location in module.
     pub fn lookup_by_name_and_location
     (&self, name:impl Str, module:&QualifiedName, location:TextLocation) -> Vec<Rc<Entry>> {
-        self.entries.borrow().values().filter(|entry| {
-            entry.matches_name(name.as_ref()) && entry.is_visible_at(module,location)
-        }).cloned().collect()
+        let query = Query::default().with_name(name).visible_at(module.clone(),location);
+        self.search(&query)
     }
 
     /// Search the database for Local or Function entries with given name and visible at given
     /// location in module.
     pub fn lookup_locals_by_name_and_location
     (&self, name:impl Str, module:&QualifiedName, location:TextLocation) -> Vec<Rc<Entry>> {
-        self.entries.borrow().values().cloned().filter(|entry| {
-            let is_local = entry.kind == EntryKind::Function || entry.kind == EntryKind::Local;
-            is_local && entry.matches_name(name.as_ref()) && entry.is_visible_at(module,location)
+        let query = Query::default().with_name(name).visible_at(module.clone(),location);
+        self.search(&query).into_iter().filter(|entry| {
+            entry.kind == EntryKind::Function || entry.kind == EntryKind::Local
         }).collect()
     }
 
     /// Search the database for Method entry with given name and defined for given module.
     pub fn lookup_module_method
     (&self, name:impl Str, module:&QualifiedName) -> Option<Rc<Entry>> {
-        self.entries.borrow().values().cloned().find(|entry| {
-            let is_method             = entry.kind == EntryKind::Method;
-            let is_defined_for_module = entry.has_self_type(module.name());
-            is_method && is_defined_for_module && entry.matches_name(name.as_ref())
-        })
+        let query = Query::default().with_name(name).with_self_type(module.name().to_string());
+        self.search(&query).into_iter().find(|entry| entry.kind == EntryKind::Method)
+    }
+
+    /// Search the database for Atom entries constructing values of given type: the atom being the
+    /// type itself (given by its name or qualified name), and the atoms returning the type.
+    ///
+    /// The type itself goes first, followed by the atoms returning it in the order of their ids.
+    pub fn lookup_atoms_of_type(&self, tp:impl Str) -> Vec<Rc<Entry>> {
+        let tp          = tp.as_ref();
+        let name        = tp.rsplit('.').next().unwrap_or(tp);
+        let is_atom     = |entry:&Rc<Entry>| entry.kind == EntryKind::Atom;
+        let is_the_type = |entry:&Rc<Entry>| {
+            let qualified_name = format!("{}.{}",entry.module,entry.name);
+            is_atom(entry) && (entry.name == tp || qualified_name == tp)
+        };
+        let the_type  = self.search(&Query::default().with_name(name));
+        let the_type  = the_type.into_iter().filter(is_the_type);
+        let returning = self.search(&Query::default().with_return_type(tp));
+        let returning = returning.into_iter().filter(is_atom);
+        let mut atoms = Vec::<Rc<Entry>>::new();
+        for atom in the_type.chain(returning) {
+            if !atoms.iter().any(|found| Rc::ptr_eq(found,&atom)) {
+                atoms.push(atom);
+            }
+        }
+        atoms
     }
 
     /// Put the entry to the database. Using this function likely break the synchronization between
     /// Language Server and IDE, and should be used only in tests.
     #[cfg(test)]
     pub fn put_entry(&self, id:EntryId, entry:Entry) {
-        self.entries.borrow_mut().insert(id,Rc::new(entry));
+        self.insert_entry(id,Rc::new(entry));
     }
 }
 
@@ -437,6 +656,7 @@ mod test {
     use super::*;
 
     use enso_protocol::language_server::SuggestionsDatabaseEntry;
+    use json_rpc::expect_call;
     use utils::test::stream::StreamTestExt;
     use wasm_bindgen_test::wasm_bindgen_test_configure;
     use wasm_bindgen_test::wasm_bindgen_test;
@@ -606,5 +826,164 @@ mod test {
         notifications.expect_pending();
         assert_eq!(db.lookup(2).unwrap().name, "NewEntry2");
         assert_eq!(db.version.get(), 3);
+
+        // Already applied update
+        let update = SuggestionDatabaseUpdatesEvent {
+            updates         : vec![Update::Remove {id:2}],
+            current_version : 3,
+        };
+        db.apply_update_event(update);
+        fixture.run_until_stalled();
+        notifications.expect_pending();
+        assert_eq!(db.lookup(2).unwrap().name, "NewEntry2");
+    }
+
+    #[test]
+    fn caching_database() {
+        let mut fixture = TestWithLocalPoolExecutor::set_up();
+        let entry       = language_server::types::SuggestionEntry::Atom {
+            name          : "Entry".to_string(),
+            module        : "TestProject.TestModule".to_string(),
+            arguments     : vec![],
+            return_type   : "TestAtom".to_string(),
+            documentation : None
+        };
+        let response = language_server::response::GetSuggestionDatabase {
+            entries         : vec![SuggestionsDatabaseEntry {id:1, suggestion:entry}],
+            current_version : 5,
+        };
+        let db         = SuggestionDatabase::from_ls_response(response);
+        let cache_path = language_server::Path::new(default(),&[CACHE_DIRECTORY,
+            SUGGESTION_DATABASE_CACHE_FILE]);
+        let entry      = db.lookup(1).unwrap();
+        db.record_pick(&entry);
+        db.record_pick(&entry);
+        fixture.run_until_stalled();
+        assert_eq!(db.pick_count(&entry),2);
+
+        // Saving
+        let client   = language_server::MockClient::default();
+        let contents = Rc::new(RefCell::new(String::new()));
+        let written  = contents.clone_ref();
+        client.expect.write_file(move |_,contents| {
+            *written.borrow_mut() = contents.clone();
+            Ok(())
+        });
+        client.require_all_calls();
+        let ls = language_server::Connection::new_mock(client);
+        fixture.expect_completion(db.save_cache(&ls)).unwrap();
+        // The cache is up to date, so it is not written again.
+        fixture.expect_completion(db.save_cache(&ls)).unwrap();
+        let contents = contents.borrow().clone();
+
+        // Loading up to date cache
+        let client  = language_server::MockClient::default();
+        let version = language_server::response::GetSuggestionDatabaseVersion {current_version:5};
+        expect_call!(client.read_file(path=cache_path.clone())
+            => Ok(language_server::response::Read {contents:contents.clone()}));
+        expect_call!(client.get_suggestions_database_version() => Ok(version));
+        client.require_all_calls();
+        let ls     = language_server::Connection::new_mock(client);
+        let loaded = SuggestionDatabase::create_synchronized(&ls);
+        let loaded = fixture.expect_completion(loaded).unwrap();
+        assert_eq!(loaded.lookup(1).unwrap(),entry);
+        assert_eq!(loaded.version.get(),5);
+        assert_eq!(loaded.pick_count(&entry),2);
+
+        // Loading outdated cache
+        let client   = language_server::MockClient::default();
+        let version  = language_server::response::GetSuggestionDatabaseVersion {current_version:6};
+        let response = language_server::response::GetSuggestionDatabase {
+            entries         : vec![],
+            current_version : 6,
+        };
+        expect_call!(client.read_file(path=cache_path)
+            => Ok(language_server::response::Read {contents}));
+        expect_call!(client.get_suggestions_database_version() => Ok(version));
+        expect_call!(client.get_suggestions_database() => Ok(response));
+        client.require_all_calls();
+        let ls     = language_server::Connection::new_mock(client);
+        let loaded = SuggestionDatabase::create_synchronized(&ls);
+        let loaded = fixture.expect_completion(loaded).unwrap();
+        assert_eq!(loaded.lookup(1),Err(NoSuchEntry(1)));
+        assert_eq!(loaded.version.get(),6);
+        // The usage statistics are kept even if the entries are outdated.
+        assert_eq!(loaded.pick_count(&entry),2);
+    }
+
+    #[test]
+    fn searching_entries() {
+        let mut fixture = TestWithLocalPoolExecutor::set_up();
+        let module      = QualifiedName::from_segments("Project",&["Main"]).unwrap();
+        let location    = |line| TextLocation {line,column:0};
+        let method   = Entry {
+            name          : "print_number".to_string(),
+            kind          : EntryKind::Method,
+            module        : module.clone(),
+            arguments     : vec![],
+            return_type   : "Number".to_string(),
+            documentation : None,
+            self_type     : Some("Number".to_string()),
+            scope         : Scope::Everywhere,
+        };
+        let print = Entry {
+            name      : "print".to_string(),
+            self_type : Some("Any".to_string()),
+            ..method.clone()
+        };
+        let visible_local = Entry {
+            name  : "point".to_string(),
+            kind  : EntryKind::Local,
+            scope : Scope::InModule {range:location(1)..=location(3)},
+            ..method.clone()
+        };
+        let hidden_local = Entry {
+            name  : "pointer".to_string(),
+            kind  : EntryKind::Local,
+            scope : Scope::InModule {range:location(3)..=location(4)},
+            ..method.clone()
+        };
+        let entries = vec![(1,method),(2,print),(3,visible_local),(4,hidden_local)];
+        let entries = entries.iter().map(|(id,entry)| (id,entry));
+        let db      = SuggestionDatabase::new_from_entries(Logger::default(),entries);
+        let found   = |query:Query| {
+            db.search(&query).iter().map(|entry| entry.name.clone()).sorted().collect_vec()
+        };
+
+        let visible = Query::new("p").visible_at(module,location(2));
+        assert_eq!(found(visible),vec!["point","print","print_number"]);
+        assert_eq!(found(Query::new("prin")),vec!["print","print_number"]);
+        assert_eq!(found(Query::new("p").with_self_type("Number")),vec!["print_number"]);
+        assert_eq!(found(Query::new("x")),Vec::<String>::new());
+
+        db.apply_update_event(SuggestionDatabaseUpdatesEvent {
+            updates         : vec![Update::Remove {id:2}],
+            current_version : 1,
+        });
+        fixture.run_until_stalled();
+        assert_eq!(found(Query::new("prin")),vec!["print_number"]);
+    }
+
+    #[test]
+    fn renaming_method() {
+        let module = QualifiedName::from_segments("Project",&["Main"]).unwrap();
+        let method = Entry {
+            name          : "foo".to_string(),
+            kind          : EntryKind::Method,
+            module        : module.clone(),
+            arguments     : vec![],
+            return_type   : "Number".to_string(),
+            documentation : None,
+            self_type     : Some("Main".to_string()),
+            scope         : Scope::Everywhere,
+        };
+        let entries = vec![(1,method.clone())];
+        let entries = entries.iter().map(|(id,entry)| (id,entry));
+        let db      = SuggestionDatabase::new_from_entries(Logger::default(),entries);
+        db.record_pick(&method);
+        db.rename_method(&method.method_id().unwrap(),"bar");
+        let renamed = db.lookup_module_method("bar",&module).unwrap();
+        assert!(db.lookup_module_method("foo",&module).is_none());
+        assert_eq!(db.pick_count(&renamed),1);
     }
 }
@@ -0,0 +1,225 @@
+//! The in-process search index of the Suggestion Database.
+//!
+//! The index allows searching the database entries locally, without asking the Language Server,
+//! which may be slow to respond. See [`Query`] for the supported search criteria.
+
+use crate::prelude::*;
+
+use crate::double_representation::module::QualifiedName;
+use crate::model::suggestion_database::Entry;
+use crate::model::suggestion_database::EntryId;
+
+use data::text::TextLocation;
+
+
+
+// =============
+// === Query ===
+// =============
+
+/// The criteria of searching the Suggestion Database. The criteria which are `None` are not
+/// checked.
+#[derive(Clone,Debug,Default)]
+pub struct Query {
+    /// The pattern which the entry's name must fuzzy-match. The results are ordered from the best
+    /// match.
+    pub pattern     : String,
+    /// The exact name of the entry. The comparison is case-insensitive.
+    pub name        : Option<String>,
+    /// The module where the entry is defined.
+    pub module      : Option<QualifiedName>,
+    /// The type of the entry's `this` argument.
+    pub self_type   : Option<String>,
+    /// The type returned by the entry.
+    pub return_type : Option<String>,
+    /// The module and location in its code where the entry must be visible.
+    pub visible_at  : Option<(QualifiedName,TextLocation)>,
+}
+
+impl Query {
+    /// Create the query for entries matching the given pattern.
+    pub fn new(pattern:impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        Query {pattern,..default()}
+    }
+
+    /// Require the entry to have the given name.
+    pub fn with_name(mut self, name:impl Str) -> Self {
+        self.name = Some(name.as_ref().to_owned());
+        self
+    }
+
+    /// Require the entry to be defined in the given module.
+    pub fn with_module(mut self, module:QualifiedName) -> Self {
+        self.module = Some(module);
+        self
+    }
+
+    /// Require the entry to have the given type of `this` argument.
+    pub fn with_self_type(mut self, self_type:impl Into<String>) -> Self {
+        self.self_type = Some(self_type.into());
+        self
+    }
+
+    /// Require the entry to return the given type.
+    pub fn with_return_type(mut self, return_type:impl Into<String>) -> Self {
+        self.return_type = Some(return_type.into());
+        self
+    }
+
+    /// Require the entry to be visible at given location in the module.
+    pub fn visible_at(mut self, module:QualifiedName, location:TextLocation) -> Self {
+        self.visible_at = Some((module,location));
+        self
+    }
+
+    /// Check if the entry meets all the query's criteria. Returns the entry's score of matching
+    /// the query's pattern, or `None` if it does not meet them.
+    pub fn check(&self, entry:&Entry) -> Option<fuzzly::Subsequence> {
+        let name_matches        = self.name.iter().all(|name| entry.matches_name(name));
+        let module_matches      = self.module.iter().all(|module| entry.module == *module);
+        let self_type_matches   = self.self_type.iter().all(|tp| entry.has_self_type(tp));
+        let return_type_matches = self.return_type.iter().all(|tp| entry.return_type == *tp);
+        let is_visible          = self.visible_at.iter().all(|(module,location)| {
+            entry.is_visible_at(module,*location)
+        });
+        let meets_criteria = name_matches && module_matches && self_type_matches
+            && return_type_matches && is_visible;
+        let pattern_matches = fuzzly::matches(&entry.name,&self.pattern);
+        (meets_criteria && pattern_matches).and_option_from(|| {
+            let metric = fuzzly::metric::default();
+            fuzzly::find_best_subsequence(&entry.name,&self.pattern,metric)
+        })
+    }
+}
+
+
+
+// =============
+// === Index ===
+// =============
+
+type IdSet = HashSet<EntryId>;
+
+/// The index of Suggestion Database entries by their name, module, self type and return type.
+///
+/// It must be updated on each change of the database entries.
+#[derive(Clone,Debug,Default)]
+pub struct Index {
+    by_name        : HashMap<String,IdSet>,
+    by_module      : HashMap<QualifiedName,IdSet>,
+    by_self_type   : HashMap<String,IdSet>,
+    by_return_type : HashMap<String,IdSet>,
+}
+
+impl Index {
+    /// Add the entry to the index.
+    pub fn insert(&mut self, id:EntryId, entry:&Entry) {
+        self.by_name.entry(entry.name.to_lowercase()).or_default().insert(id);
+        self.by_module.entry(entry.module.clone()).or_default().insert(id);
+        if let Some(self_type) = &entry.self_type {
+            self.by_self_type.entry(self_type.clone()).or_default().insert(id);
+        }
+        self.by_return_type.entry(entry.return_type.clone()).or_default().insert(id);
+    }
+
+    /// Remove the entry from the index. The `entry` must be the same as when it was inserted.
+    pub fn remove(&mut self, id:EntryId, entry:&Entry) {
+        Self::remove_from(&mut self.by_name,&entry.name.to_lowercase(),id);
+        Self::remove_from(&mut self.by_module,&entry.module,id);
+        if let Some(self_type) = &entry.self_type {
+            Self::remove_from(&mut self.by_self_type,self_type,id);
+        }
+        Self::remove_from(&mut self.by_return_type,&entry.return_type,id);
+    }
+
+    fn remove_from<K:Eq+Hash>(map:&mut HashMap<K,IdSet>, key:&K, id:EntryId) {
+        if let Some(ids) = map.get_mut(key) {
+            ids.remove(&id);
+            if ids.is_empty() {
+                map.remove(key);
+            }
+        }
+    }
+
+    /// Get the ids of entries which may meet the query's criteria. Returns `None` if the query
+    /// does not narrow down the candidates, i.e. all entries should be checked.
+    ///
+    /// The returned entries still must be checked with [`Query::check`], as the index does not
+    /// cover all the criteria.
+    pub fn candidates(&self, query:&Query) -> Option<IdSet> {
+        let empty        = IdSet::new();
+        let by_name      = query.name.as_ref().map(|name| self.by_name.get(&name.to_lowercase()));
+        let by_module    = query.module.as_ref().map(|module| self.by_module.get(module));
+        let by_self_type = query.self_type.as_ref().map(|tp| self.by_self_type.get(tp));
+        let by_return    = query.return_type.as_ref().map(|tp| self.by_return_type.get(tp));
+        let sets         = vec![by_name,by_module,by_self_type,by_return].into_iter().flatten();
+        let sets         = sets.map(|set| set.unwrap_or(&empty)).collect_vec();
+        let (first,rest) = sets.split_first()?;
+        let candidates   = first.iter().filter(|id| rest.iter().all(|set| set.contains(id)));
+        Some(candidates.cloned().collect())
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::suggestion_database::EntryKind;
+    use crate::model::suggestion_database::Scope;
+
+    fn entry(name:&str, module:&str, self_type:Option<&str>, return_type:&str) -> Entry {
+        Entry {
+            name          : name.to_owned(),
+            kind          : EntryKind::Method,
+            module        : module.to_owned().try_into().unwrap(),
+            arguments     : vec![],
+            return_type   : return_type.to_owned(),
+            documentation : None,
+            self_type     : self_type.map(ToOwned::to_owned),
+            scope         : Scope::Everywhere,
+        }
+    }
+
+    #[test]
+    fn looking_up_candidates() {
+        let mut index = Index::default();
+        let entries   = vec!
+            [ (1,entry("foo","Test.Main",Some("Main"),"Number"))
+            , (2,entry("Foo","Test.Main",Some("Text"),"Text"))
+            , (3,entry("bar","Test.Other",Some("Text"),"Number"))
+            ];
+        for (id,entry) in &entries {
+            index.insert(*id,entry);
+        }
+        let module:QualifiedName = "Test.Main".to_owned().try_into().unwrap();
+        let ids = |query:Query| {
+            index.candidates(&query).map(|ids| ids.into_iter().sorted().collect_vec())
+        };
+        assert_eq!(ids(Query::new("f")),None);
+        assert_eq!(ids(Query::default().with_name("FOO")),Some(vec![1,2]));
+        assert_eq!(ids(Query::default().with_module(module.clone())),Some(vec![1,2]));
+        assert_eq!(ids(Query::default().with_self_type("Text")),Some(vec![2,3]));
+        assert_eq!(ids(Query::default().with_return_type("Number")),Some(vec![1,3]));
+        assert_eq!(ids(Query::default().with_module(module).with_self_type("Text")),Some(vec![2]));
+        assert_eq!(ids(Query::default().with_self_type("Unknown")),Some(vec![]));
+
+        index.remove(2,&entries[1].1);
+        assert_eq!(ids(Query::default().with_name("foo")),Some(vec![1]));
+    }
+
+    #[test]
+    fn checking_entries() {
+        let foo = entry("foo_bar","Test.Main",Some("Main"),"Number");
+        assert!(Query::new("fb").check(&foo).is_some());
+        assert!(Query::new("xyz").check(&foo).is_none());
+        assert!(Query::new("").with_name("Foo_Bar").check(&foo).is_some());
+        assert!(Query::new("").with_return_type("Text").check(&foo).is_none());
+    }
+}
@@ -4,3 +4,29 @@ pub mod web;
 
 #[cfg(test)]
 pub mod test_utils;
+
+use crate::prelude::*;
+
+use mockall::automock;
+
+
+
+// =================
+// === Reconnect ===
+// =================
+
+/// A transport which is able to re-establish its connection with the peer after it was lost.
+///
+/// Reconnecting does not invalidate the clients using the transport: the events of the new
+/// connection are delivered through the same event stream.
+#[automock]
+pub trait Reconnect : Debug {
+    /// Open a new connection to the same endpoint, replacing the lost one.
+    fn reconnect(&self) -> StaticBoxFuture<FallibleResult<()>>;
+}
+
+impl Debug for MockReconnect {
+    fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f,"Mocked Reconnect")
+    }
+}
@@ -116,6 +116,9 @@ pub struct WebSocket {
     pub on_open    : OptionalFmMutClosure<Event>,
     /// Handle to a closure connected to `WebSocket.onerror`.
     pub on_error   : OptionalFmMutClosure<Event>,
+    /// The transmitter set through the `Transport` API, kept to be connected to a socket replacing
+    /// this one after reconnecting.
+    pub event_transmitter : Option<mpsc::UnboundedSender<TransportEvent>>,
 }
 
 impl WebSocket {
@@ -130,6 +133,7 @@ impl WebSocket {
             on_close   : default(),
             on_open    : default(),
             on_error   : default(),
+            event_transmitter : default(),
         }
     }
 
@@ -258,6 +262,7 @@ impl Transport for WebSocket {
 
     fn set_event_transmitter(&mut self, transmitter:mpsc::UnboundedSender<TransportEvent>) {
         info!(self.logger,"Setting event transmitter.");
+        self.event_transmitter = Some(transmitter.clone());
         let transmitter_copy = transmitter.clone();
         let logger_copy = self.logger.clone_ref();
         self.set_on_message(move |e| {
@@ -290,3 +295,73 @@ impl Transport for WebSocket {
         });
     }
 }
+
+
+
+// =============================
+// === ReconnectingWebSocket ===
+// =============================
+
+/// A shared handle to `WebSocket` which can be used both as a transport for the RPC client and
+/// for re-establishing the connection once it is lost.
+///
+/// The reconnected socket is connected to the event transmitter of the lost one, so the client
+/// does not need to be recreated.
+#[derive(Clone,CloneRef,Debug)]
+pub struct ReconnectingWebSocket {
+    logger : Logger,
+    url    : ImString,
+    socket : Rc<RefCell<WebSocket>>,
+}
+
+impl ReconnectingWebSocket {
+    /// Establish connection with endpoint defined by the given URL and wrap it.
+    /// Asynchronous, because it waits until connection is established.
+    pub async fn new_opened
+    (parent:Logger, url:impl Str) -> Result<ReconnectingWebSocket,ConnectingError> {
+        let url    = ImString::new(url.into());
+        let logger = Logger::sub(&parent,"ReconnectingWebSocket");
+        let socket = WebSocket::new_opened(parent,url.clone_ref()).await?;
+        let socket = Rc::new(RefCell::new(socket));
+        Ok(ReconnectingWebSocket {logger,url,socket})
+    }
+
+    /// Replace the underlying socket with a new one connected to the same endpoint.
+    pub async fn reopen(&self) -> Result<(),ConnectingError> {
+        info!(self.logger,"Reconnecting to {self.url}.");
+        let logger         = self.logger.clone_ref();
+        let mut new_socket = WebSocket::new_opened(logger,self.url.clone_ref()).await?;
+        let mut socket     = self.socket.borrow_mut();
+        socket.clear_callbacks();
+        if let Err(error) = socket.ws.close() {
+            warning!(self.logger,"Failed to close the old socket: {js_to_string(error)}.");
+        }
+        if let Some(transmitter) = socket.event_transmitter.take() {
+            new_socket.set_event_transmitter(transmitter);
+        }
+        *socket = new_socket;
+        info!(self.logger,"Reconnected to {self.url}.");
+        Ok(())
+    }
+}
+
+impl Transport for ReconnectingWebSocket {
+    fn send_text(&mut self, message:&str) -> Result<(), Error> {
+        self.socket.borrow_mut().send_text(message)
+    }
+
+    fn send_binary(&mut self, message:&[u8]) -> Result<(), Error> {
+        self.socket.borrow_mut().send_binary(message)
+    }
+
+    fn set_event_transmitter(&mut self, transmitter:mpsc::UnboundedSender<TransportEvent>) {
+        self.socket.borrow_mut().set_event_transmitter(transmitter)
+    }
+}
+
+impl crate::transport::Reconnect for ReconnectingWebSocket {
+    fn reconnect(&self) -> StaticBoxFuture<FallibleResult<()>> {
+        let this = self.clone_ref();
+        async move { Ok(this.reopen().await?) }.boxed_local()
+    }
+}
@@ -3,5 +3,6 @@
 pub mod node_editor;
 pub mod layout;
 pub mod project;
+pub mod project_browser;
 pub mod temporary_panel;
 pub mod text_editor;
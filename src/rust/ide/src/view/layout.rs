@@ -72,6 +72,15 @@ impl ViewLayoutData {
     }
 }
 
+impl Drop for ViewLayoutData {
+    fn drop(&mut self) {
+        // The editors are attached to the world, which outlives the layout, e.g. when switching
+        // between projects.
+        self.node_editor.unset_parent();
+        self.text_editor.display_object().unset_parent();
+    }
+}
+
 impl ViewLayout {
     /// Creates a new ViewLayout with a single TextEditor.
     #[allow(clippy::too_many_arguments)]
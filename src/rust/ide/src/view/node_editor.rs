@@ -7,6 +7,7 @@
 use crate::prelude::*;
 
 use crate::controller::graph::NodeTrees;
+use crate::controller::graph::clipboard::ClipboardContent;
 use crate::model::execution_context::ComputedValueInfo;
 use crate::model::execution_context::LocalCall;
 use crate::model::execution_context::ExpressionId;
@@ -20,14 +21,17 @@ use enso_frp::stream::EventEmitter;
 use ensogl::display;
 use ensogl::display::traits::*;
 use ensogl::application::Application;
+use ensogl::system::web::clipboard;
 use ensogl_gui_list_view as list_view;
 use ide_view::graph_editor;
+use ide_view::graph_editor::component::node::port::widget as port_widget;
 use ide_view::graph_editor::component::visualization;
 use ide_view::graph_editor::EdgeTarget;
 use ide_view::graph_editor::GraphEditor;
 use ide_view::graph_editor::SharedHashMap;
 use utils::channel::process_stream_with_handle;
 use crate::controller::searcher::suggestion::MatchInfo;
+use crate::controller::searcher::Suggestion;
 use crate::controller::searcher::Suggestions;
 
 
@@ -45,6 +49,10 @@ enum MissingMappingFor {
     ControllerNode(ast::Id),
     #[fail(display="Displayed connection {:?} is not bound to any controller connection", _0)]
     DisplayedConnection(graph_editor::EdgeId),
+    #[fail(display="Displayed note {:?} is not bound to any controller note", _0)]
+    DisplayedNote(graph_editor::NoteId),
+    #[fail(display="Displayed group {:?} is not bound to any controller group", _0)]
+    DisplayedGroup(graph_editor::GroupId),
 }
 
 /// Error raised when reached some fatal inconsistency in data provided by GraphEditor.
@@ -124,15 +132,23 @@ impl<Parameter:frp::Data> FencedAction<Parameter> {
 /// until the generated name does not collide with any known identifier.
 const COLLAPSED_FUNCTION_NAME:&str = "func";
 
-/// The gap between nodes in pixels on default node layout (when user did not set any position of
-/// node - possibly when node was added by editing text).
-const DEFAULT_GAP_BETWEEN_NODES : f32 = 40.0;
-/// The default X position of the node when user did not set any position of node - possibly when
-/// node was added by editing text.
-const DEFAULT_NODE_X_POSITION   : f32 = -100.0;
-/// The default Y position of the node when user did not set any position of node - possibly when
-/// node was added by editing text.
-const DEFAULT_NODE_Y_POSITION   : f32 =  200.0;
+/// The default X position of the automatically laid out nodes when user did not set position of
+/// any node - possibly when nodes were added by editing text.
+const DEFAULT_NODE_X_POSITION : f32 = -100.0;
+/// The default Y position of the automatically laid out nodes when user did not set position of
+/// any node - possibly when nodes were added by editing text.
+const DEFAULT_NODE_Y_POSITION : f32 =  200.0;
+
+/// The prefixes of the Graph Editor commands which are not suggested as actions in the searcher,
+/// because they are meant to be bound to pressing and releasing keys, or are used for debugging.
+const HIDDEN_COMMAND_PREFIXES:&[&str] =
+    &["debug_","press_","release_","double_press_","enable_","disable_","edit_mode_"];
+
+/// The suffix of the searcher's labels of categories, distinguishing them from the suggestions.
+const CATEGORY_LABEL_SUFFIX:&str = " >";
+
+/// The title of the newly created node groups.
+const DEFAULT_GROUP_TITLE:&str = "Group";
 
 /// A structure which handles integration between controller and graph_editor EnsoGl control.
 /// All changes made by user in view are reflected in controller, and all controller notifications
@@ -160,6 +176,7 @@ impl GraphEditorIntegratedWithController {
 #[derive(Debug)]
 struct GraphEditorIntegratedWithControllerModel {
     logger              : Logger,
+    app                 : Application,
     //TODO[ao] we display the new "Project view" because it contains documentation panel, but no
     // text editor. This should be refactored as a part of task
     // https://github.com/enso-org/ide/issues/597
@@ -170,6 +187,10 @@ struct GraphEditorIntegratedWithControllerModel {
     node_views          : RefCell<BiMap<ast::Id,graph_editor::NodeId>>,
     expression_views    : RefCell<HashMap<graph_editor::NodeId,String>>,
     connection_views    : RefCell<BiMap<controller::graph::Connection,graph_editor::EdgeId>>,
+    note_views          : RefCell<BiMap<model::module::NoteId,graph_editor::NoteId>>,
+    /// The notes added in the UI, which are not stored in the module until their text is entered.
+    pending_notes       : RefCell<HashSet<graph_editor::NoteId>>,
+    group_views         : RefCell<BiMap<model::module::GroupId,graph_editor::GroupId>>,
     visualizations      : SharedHashMap<graph_editor::NodeId,VisualizationId>,
 }
 
@@ -229,6 +250,8 @@ impl GraphEditorIntegratedWithController {
             GraphEditorIntegratedWithControllerModel::node_removed_in_ui,&invalidate.trigger);
         let nodes_collapsed = Self::ui_action(&model,
             GraphEditorIntegratedWithControllerModel::nodes_collapsed_in_ui,&invalidate.trigger);
+        let node_expanded = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::node_expanded_in_ui,&invalidate.trigger);
         let node_entered = Self::ui_action(&model,
             GraphEditorIntegratedWithControllerModel::node_entered_in_ui,&invalidate.trigger);
         let node_exited = Self::ui_action(&model,
@@ -246,6 +269,13 @@ impl GraphEditorIntegratedWithController {
             GraphEditorIntegratedWithControllerModel::node_expression_set_in_ui,&invalidate.trigger);
         let suggestion_picked = Self::ui_action(&model,
             GraphEditorIntegratedWithControllerModel::suggestion_picked_in_ui, &invalidate.trigger);
+        let suggestion_selected = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::suggestion_selected_in_ui,
+            &invalidate.trigger);
+        let category_entered = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::category_entered_in_ui,&invalidate.trigger);
+        let category_left = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::category_left_in_ui,&invalidate.trigger);
         let node_editing_committed = Self::ui_action(&model,
             GraphEditorIntegratedWithControllerModel::node_editing_committed_in_ui,
             &invalidate.trigger);
@@ -255,6 +285,40 @@ impl GraphEditorIntegratedWithController {
         let visualization_disabled = Self::ui_action(&model,
             GraphEditorIntegratedWithControllerModel::visualization_disabled_in_ui,
             &invalidate.trigger);
+        let undo_requested = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::undo_requested_in_ui,&invalidate.trigger);
+        let redo_requested = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::redo_requested_in_ui,&invalidate.trigger);
+        let nodes_copied = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::nodes_copied_in_ui,&invalidate.trigger);
+        let nodes_cut = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::nodes_cut_in_ui,&invalidate.trigger);
+        let paste_requested = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::paste_requested_in_ui,&invalidate.trigger);
+        let nodes_arranged = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::nodes_arranged_in_ui,&invalidate.trigger);
+        let node_comment_edited = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::node_comment_edited_in_ui,
+            &invalidate.trigger);
+        let note_added = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::note_added_in_ui,&invalidate.trigger);
+        let note_moved = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::note_moved_in_ui,&invalidate.trigger);
+        let note_edited = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::note_edited_in_ui,&invalidate.trigger);
+        let nodes_grouped = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::nodes_grouped_in_ui,&invalidate.trigger);
+        let group_moved = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::group_moved_in_ui,&invalidate.trigger);
+        let group_title_edited = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::group_title_edited_in_ui,&invalidate.trigger);
+        let group_collapsed_set = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::group_collapsed_set_in_ui,
+            &invalidate.trigger);
+        let group_removed = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::group_removed_in_ui,&invalidate.trigger);
+        let port_value_set = Self::ui_action(&model,
+            GraphEditorIntegratedWithControllerModel::port_value_set_in_ui,&invalidate.trigger);
         frp::extend! {network
             // Notifications from controller
             let handle_notification = FencedAction::fence(&network,
@@ -267,6 +331,7 @@ impl GraphEditorIntegratedWithController {
             is_hold <- is_handling_notification.all_with(&invalidate.is_running, |l,r| *l || *r);
             _action <- editor_outs.node_removed             .map2(&is_hold,node_removed);
             _action <- editor_outs.nodes_collapsed          .map2(&is_hold,nodes_collapsed);
+            _action <- editor_outs.node_expanded            .map2(&is_hold,node_expanded);
             _action <- editor_outs.node_entered             .map2(&is_hold,node_entered);
             _action <- editor_outs.node_exited              .map2(&is_hold,node_exited);
             _action <- editor_outs.connection_added         .map2(&is_hold,connection_created);
@@ -276,7 +341,28 @@ impl GraphEditorIntegratedWithController {
             _action <- editor_outs.node_position_set_batched.map2(&is_hold,node_moved);
             _action <- editor_outs.edited_node              .map2(&is_hold,node_editing);
             _action <- editor_outs.node_expression_set      .map2(&is_hold,node_expression_set);
+            _action <- editor_outs.undo_requested           .map2(&is_hold,undo_requested);
+            _action <- editor_outs.redo_requested           .map2(&is_hold,redo_requested);
+            _action <- editor_outs.nodes_copied             .map2(&is_hold,nodes_copied);
+            _action <- editor_outs.nodes_cut                .map2(&is_hold,nodes_cut);
+            _action <- editor_outs.paste_requested          .map2(&is_hold,paste_requested);
+            _action <- editor_outs.nodes_arranged           .map2(&is_hold,nodes_arranged);
+            _action <- editor_outs.node_comment_edited      .map2(&is_hold,node_comment_edited);
+            _action <- editor_outs.note_added               .map2(&is_hold,note_added);
+            _action <- editor_outs.note_position_set        .map2(&is_hold,note_moved);
+            _action <- editor_outs.note_edited              .map2(&is_hold,note_edited);
+            _action <- editor_outs.nodes_grouped            .map2(&is_hold,nodes_grouped);
+            _action <- editor_outs.group_moved              .map2(&is_hold,group_moved);
+            _action <- editor_outs.group_title_edited       .map2(&is_hold,group_title_edited);
+            _action <- editor_outs.group_collapsed_set      .map2(&is_hold,group_collapsed_set);
+            _action <- editor_outs.group_removed            .map2(&is_hold,group_removed);
+            _action <- editor_outs.node_port_value_set      .map2(&is_hold,port_value_set);
+            type_mismatch <- editor_outs.connection_type_mismatch.gate_not(&is_hold);
+            eval type_mismatch ((edge_id) model.connection_type_mismatch_in_ui(edge_id));
             _action <- searcher_frp.picked_entry            .map2(&is_hold,suggestion_picked);
+            _action <- searcher_frp.selected_entry          .map2(&is_hold,suggestion_selected);
+            _action <- searcher_frp.category_entered        .map2(&is_hold,category_entered);
+            _action <- searcher_frp.category_left           .map2(&is_hold,category_left);
             _action <- project_frp.editing_committed        .map2(&is_hold,node_editing_committed);
 
             eval project_frp.editing_committed ((_) invalidate.trigger.emit(()));
@@ -331,20 +417,26 @@ impl GraphEditorIntegratedWithControllerModel {
     , app        : &Application
     , controller : controller::ExecutedGraph
     , project    : model::Project) -> Self {
+        let app                 = app.clone_ref();
         let view                = app.new_view::<ide_view::project::View>();
         let node_views          = default();
         let connection_views    = default();
+        let note_views          = default();
+        let pending_notes       = default();
+        let group_views         = default();
         let expression_views    = default();
         let visualizations      = default();
         let searcher_controller = default();
         let this = GraphEditorIntegratedWithControllerModel {
-            view,controller,node_views,
-            expression_views,connection_views,logger,visualizations,project,searcher_controller
+            app,view,controller,node_views,expression_views,connection_views,note_views,group_views,
+            logger,visualizations,project,searcher_controller,pending_notes
         };
 
         if let Err(err) = this.refresh_graph_view() {
             error!(this.logger,"Error while initializing graph editor: {err}.");
         }
+        let execution_failure = this.controller.execution_failure();
+        this.view.graph().breadcrumbs.frp.set_execution_failure.emit(execution_failure);
         this
     }
 }
@@ -378,22 +470,31 @@ impl GraphEditorIntegratedWithControllerModel {
         info!(self.logger, "Refreshing the graph view.");
         use controller::graph::Connections;
         let Connections{trees,connections} = self.controller.connections()?;
-        self.refresh_node_views(trees)?;
+        self.refresh_node_views(trees,&connections)?;
         self.refresh_connection_views(connections)?;
+        self.refresh_note_views();
+        self.refresh_group_views();
         Ok(())
     }
 
     fn refresh_node_views
-    (&self, mut trees:HashMap<double_representation::node::Id,NodeTrees>) -> FallibleResult<()> {
-        let nodes = self.controller.graph().nodes()?;
-        let ids   = nodes.iter().map(|node| node.info.id() ).collect();
+    ( &self
+    , mut trees   : HashMap<double_representation::node::Id,NodeTrees>
+    , connections : &[controller::graph::Connection]
+    ) -> FallibleResult<()> {
+        use controller::graph::layout;
+        let nodes     = self.controller.graph().nodes()?;
+        let ids       = nodes.iter().map(|node| node.info.id() ).collect();
+        let x         = DEFAULT_NODE_X_POSITION;
+        let y         = DEFAULT_NODE_Y_POSITION;
+        let origin    = model::module::Position::new(x,y);
+        let config    = default();
+        let positions = layout::positions_of_unpositioned_nodes(&nodes,connections,config,origin);
         self.retain_node_views(&ids);
-        for (i,node_info) in nodes.iter().enumerate() {
+        for node_info in &nodes {
             let id          = node_info.info.id();
             let node_trees  = trees.remove(&id).unwrap_or_else(default);
-            let x           = DEFAULT_NODE_X_POSITION;
-            let y           = DEFAULT_NODE_Y_POSITION + i as f32 * -DEFAULT_GAP_BETWEEN_NODES;
-            let default_pos = Vector2(x,y);
+            let default_pos = positions.get(&id).copied().unwrap_or(origin).vector;
             let displayed   = self.node_views.borrow_mut().get_by_left(&id).cloned();
             match displayed {
                 Some(displayed) => self.refresh_node_view(displayed, node_info, node_trees),
@@ -466,6 +567,7 @@ impl GraphEditorIntegratedWithControllerModel {
             self.view.graph().frp.inputs.set_node_position.emit_event(&(id, position.vector));
         }
         let expression = node.info.expression().repr();
+        let widgets    = self.widget_definitions(&trees.inputs,node.info.expression());
 
         // TODO [MWU]
         //  Currently we cannot limit updates, as each invalidation can affect span tree generation
@@ -479,6 +581,9 @@ impl GraphEditorIntegratedWithControllerModel {
         };
         self.view.graph().frp.inputs.set_node_expression.emit_event(&(id, code_and_trees));
         self.expression_views.borrow_mut().insert(id, expression);
+        self.view.graph().frp.inputs.set_node_widgets.emit_event(&(id, widgets));
+        let comment = node.metadata.as_ref().and_then(|md| md.comment.clone());
+        self.view.graph().frp.inputs.set_node_comment.emit_event(&(id, comment));
 
         // Set initially available type information on ports (identifiable expression's sub-parts).
         for expression_part in node.info.expression().iter_recursive() {
@@ -488,6 +593,24 @@ impl GraphEditorIntegratedWithControllerModel {
         }
     }
 
+    /// Get the definitions of value-editing widgets displayed on the node's input ports.
+    fn widget_definitions
+    (&self, input_span_tree:&span_tree::SpanTree, expression:&Ast)
+    -> Vec<port_widget::Definition> {
+        use controller::graph::widget;
+        use port_widget as view;
+        let database = self.project.suggestion_db();
+        widget::widgets(input_span_tree,expression,&database).into_iter().map(|widget| {
+            let kind = match widget.kind {
+                widget::Kind::Number            => view::Kind::Number,
+                widget::Kind::Text              => view::Kind::Text,
+                widget::Kind::Checkbox          => view::Kind::Checkbox,
+                widget::Kind::Dropdown{entries} => view::Kind::Dropdown{entries:Rc::new(entries)},
+            };
+            view::Definition {crumbs:widget.port,kind}
+        }).collect()
+    }
+
     /// Like `refresh_computed_info` but for multiple expressions.
     fn refresh_computed_infos(&self, expressions_to_refresh:&[ExpressionId]) -> FallibleResult<()> {
         debug!(self.logger, "Refreshing type information for IDs: {expressions_to_refresh:?}.");
@@ -545,6 +668,69 @@ impl GraphEditorIntegratedWithControllerModel {
         Ok(())
     }
 
+    fn refresh_note_views(&self) {
+        let notes = self.controller.graph().notes();
+        self.retain_note_views(&notes.keys().copied().collect());
+        for (id,note) in notes {
+            let displayed = self.note_views.borrow().get_by_left(&id).cloned();
+            let displayed = displayed.unwrap_or_else(|| {
+                let displayed_id = self.view.graph().add_note();
+                self.note_views.borrow_mut().insert(id,displayed_id);
+                displayed_id
+            });
+            let graph = &self.view.graph().frp.inputs;
+            graph.set_note_text.emit_event(&(displayed,note.text));
+            graph.set_note_position.emit_event(&(displayed,note.position.vector));
+        }
+    }
+
+    /// Retain only given notes in displayed graph.
+    fn retain_note_views(&self, ids:&HashSet<model::module::NoteId>) {
+        let to_remove = {
+            let borrowed = self.note_views.borrow();
+            let filtered = borrowed.iter().filter(|(id,_)| !ids.contains(id));
+            filtered.map(|(k,v)| (*k,*v)).collect_vec()
+        };
+        for (id,displayed_id) in to_remove {
+            self.view.graph().frp.inputs.remove_note.emit_event(&displayed_id);
+            self.note_views.borrow_mut().remove_by_left(&id);
+        }
+    }
+
+    fn refresh_group_views(&self) {
+        let groups = self.controller.graph().groups();
+        self.retain_group_views(&groups.keys().copied().collect());
+        for (id,group) in groups {
+            let displayed = self.group_views.borrow().get_by_left(&id).cloned();
+            let displayed = displayed.unwrap_or_else(|| {
+                let displayed_id = self.view.graph().add_group();
+                self.group_views.borrow_mut().insert(id,displayed_id);
+                displayed_id
+            });
+            let nodes = group.nodes.iter().filter_map(|node| {
+                self.node_views.borrow().get_by_left(node).cloned()
+            }).collect_vec();
+            let graph = &self.view.graph().frp.inputs;
+            graph.set_group_nodes.emit_event(&(displayed,nodes));
+            graph.set_group_title.emit_event(&(displayed,group.title));
+            graph.set_group_color.emit_event(&(displayed,group.color));
+            graph.set_group_collapsed.emit_event(&(displayed,group.collapsed));
+        }
+    }
+
+    /// Retain only given groups in displayed graph.
+    fn retain_group_views(&self, ids:&HashSet<model::module::GroupId>) {
+        let to_remove = {
+            let borrowed = self.group_views.borrow();
+            let filtered = borrowed.iter().filter(|(id,_)| !ids.contains(id));
+            filtered.map(|(k,v)| (*k,*v)).collect_vec()
+        };
+        for (id,displayed_id) in to_remove {
+            self.view.graph().frp.inputs.remove_group.emit_event(&displayed_id);
+            self.group_views.borrow_mut().remove_by_left(&id);
+        }
+    }
+
     fn edge_targets_from_controller_connection
     (&self, connection:controller::graph::Connection) -> FallibleResult<(EdgeTarget,EdgeTarget)> {
         let src_node = self.get_displayed_node_id(connection.source.node)?;
@@ -599,6 +785,30 @@ impl GraphEditorIntegratedWithControllerModel {
         Ok(())
     }
 
+    /// Handle notification received from controller about the execution having failed or the
+    /// failure being cleared.
+    pub fn on_execution_status_changed(&self, failure:Option<String>) -> FallibleResult<()> {
+        self.view.graph().breadcrumbs.frp.set_execution_failure.emit(failure);
+        Ok(())
+    }
+
+    /// Handle notification received from controller about the module's file being changed by
+    /// another program. The user is warned about the conflicting modification and the removal of
+    /// the file, as in both cases the file will be overwritten with the module's content.
+    pub fn on_external_change(&self, change:model::module::ExternalChange) -> FallibleResult<()> {
+        use model::module::ExternalChange::*;
+        let module  = self.controller.graph().module.path().module_name();
+        let warning = match change {
+            Resolved => None,
+            Conflict => Some(iformat!("The file of module {module} was modified by another \
+                program. The local changes are kept and will overwrite the file.")),
+            Removed  => Some(iformat!("The file of module {module} was removed by another \
+                program. It will be recreated with the local content.")),
+        };
+        self.view.graph().breadcrumbs.frp.set_file_warning.emit(warning);
+        Ok(())
+    }
+
     /// Handle notification received from controller about values having been computed.
     pub fn on_values_computed(&self, expressions:&[ExpressionId]) -> FallibleResult<()> {
         self.refresh_computed_infos(&expressions)
@@ -622,14 +832,19 @@ impl GraphEditorIntegratedWithControllerModel {
     pub fn handle_controller_notification
     (&self, notification:&Option<controller::graph::executed::Notification>) {
         use controller::graph::executed::Notification;
+        use controller::graph::Notification::ExternalChange;
         use controller::graph::Notification::Invalidate;
 
         debug!(self.logger, "Received notification {notification:?}");
         let result = match notification {
             Some(Notification::Graph(Invalidate))         => self.on_invalidated(),
+            Some(Notification::Graph(ExternalChange(c)))  => self.on_external_change(*c),
             Some(Notification::ComputedValueInfo(update)) => self.on_values_computed(update),
             Some(Notification::SteppedOutOfNode(id))      => self.on_node_exited(*id),
             Some(Notification::EnteredNode(local_call))   => self.on_node_entered(local_call),
+            Some(Notification::ExecutionFailed(message))  =>
+                self.on_execution_status_changed(Some(message.clone())),
+            Some(Notification::ExecutionFailureCleared)   => self.on_execution_status_changed(None),
             other => {
                 warning!(self.logger,"Handling notification {other:?} is not implemented; \
                     performing full invalidation");
@@ -648,15 +863,27 @@ impl GraphEditorIntegratedWithControllerModel {
         match notification {
             Notification::NewSuggestionList => with(self.searcher_controller.borrow(), |searcher| {
                 if let Some(searcher) = &*searcher {
+                    let mut categories = Vec::new();
                     let new_entries:AnyModelProvider = match searcher.suggestions() {
                         Suggestions::Loading       => list_view::entry::EmptyProvider.into(),
-                        Suggestions::Loaded {list} => SuggestionProvider{list}.into(),
+                        Suggestions::Loaded {list} => {
+                            let matching = list.iter().take(list.matching_count()).enumerate();
+                            categories   = matching.filter_map(|(index,entry)| {
+                                matches!(entry.suggestion, Suggestion::Category(_)).as_some(index)
+                            }).collect();
+                            SuggestionProvider{list}.into()
+                        }
                         Suggestions::Error(err)    => {
                             error!(self.logger, "Error while obtaining list from searcher: {err}");
                             list_view::entry::EmptyProvider.into()
                         },
                     };
+                    let browse_path = searcher.is_browsing().as_some_from(|| {
+                        searcher.browse_path().iter().map(ToString::to_string).collect_vec()
+                    });
                     self.view.searcher().set_entries(new_entries);
+                    self.view.searcher().set_categories(Rc::new(categories));
+                    self.view.searcher().set_breadcrumbs(browse_path.unwrap_or_default());
                 }
             })
         }
@@ -699,6 +926,163 @@ impl GraphEditorIntegratedWithControllerModel {
         Ok(())
     }
 
+    fn node_expanded_in_ui(&self, node:&graph_editor::NodeId) -> FallibleResult<()> {
+        let id = self.get_controller_node_id(*node)?;
+        self.controller.graph().expand_node(id,true)?;
+        Ok(())
+    }
+
+    fn undo_requested_in_ui(&self, _:&()) -> FallibleResult<()> {
+        if let Err(err) = self.controller.graph().module.undo() {
+            info!(self.logger, "Cannot undo: {err}");
+        }
+        Ok(())
+    }
+
+    fn redo_requested_in_ui(&self, _:&()) -> FallibleResult<()> {
+        if let Err(err) = self.controller.graph().module.redo() {
+            info!(self.logger, "Cannot redo: {err}");
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::ptr_arg)]
+    fn nodes_copied_in_ui(&self, nodes:&Vec<graph_editor::NodeId>) -> FallibleResult<()> {
+        let ids     = self.get_controller_node_ids(nodes)?;
+        let content = self.controller.graph().copy_nodes(ids)?;
+        clipboard::write_text(content.to_json()?);
+        Ok(())
+    }
+
+    #[allow(clippy::ptr_arg)]
+    fn nodes_cut_in_ui(&self, nodes:&Vec<graph_editor::NodeId>) -> FallibleResult<()> {
+        let graph        = self.controller.graph();
+        let _transaction = graph.module.open_transaction("Cut nodes");
+        self.nodes_copied_in_ui(nodes)?;
+        for id in self.get_controller_node_ids(nodes)? {
+            self.node_views.borrow_mut().remove_by_left(&id);
+            graph.remove_node(id)?;
+        }
+        Ok(())
+    }
+
+    fn paste_requested_in_ui(&self, position:&Vector2) -> FallibleResult<()> {
+        let graph    = self.controller.graph();
+        let logger   = self.logger.clone_ref();
+        let position = model::module::Position{vector:*position};
+        clipboard::read_text(move |text| {
+            let content = ClipboardContent::from_json(&text);
+            let result  = content.and_then(|content| graph.paste_nodes(&content,position));
+            if let Err(err) = result {
+                info!(logger, "Cannot paste nodes: {err}");
+            }
+        });
+        Ok(())
+    }
+
+    #[allow(clippy::ptr_arg)]
+    fn nodes_arranged_in_ui(&self, nodes:&Vec<graph_editor::NodeId>) -> FallibleResult<()> {
+        let ids = self.get_controller_node_ids(nodes)?;
+        self.controller.graph().arrange_nodes(ids)
+    }
+
+    fn node_comment_edited_in_ui
+    (&self, (displayed_id,comment):&(graph_editor::NodeId,Option<String>)) -> FallibleResult<()> {
+        let id = self.get_controller_node_id(*displayed_id)?;
+        self.controller.graph().set_node_comment(id,comment.clone())
+    }
+
+    fn port_value_set_in_ui
+    (&self, (displayed_id,crumbs,value):&(graph_editor::NodeId,span_tree::Crumbs,String))
+    -> FallibleResult<()> {
+        let id       = self.get_controller_node_id(*displayed_id)?;
+        let endpoint = controller::graph::Endpoint::new(id,crumbs.clone());
+        self.controller.set_port_value(&endpoint,value)
+    }
+
+    fn note_added_in_ui(&self, displayed_id:&graph_editor::NoteId) -> FallibleResult<()> {
+        self.pending_notes.borrow_mut().insert(*displayed_id);
+        Ok(())
+    }
+
+    fn note_moved_in_ui
+    (&self, (displayed_id,pos):&(graph_editor::NoteId,Vector2)) -> FallibleResult<()> {
+        if self.pending_notes.borrow().contains(displayed_id) {
+            // The position is stored once the note's text is entered.
+            return Ok(())
+        }
+        let id       = self.get_controller_note_id(*displayed_id)?;
+        let position = model::module::Position::new(pos.x,pos.y);
+        self.controller.graph().set_note_position(id,position)
+    }
+
+    /// Store the edited text of the note. The note added in the UI is stored in the module once
+    /// its text is entered, and removed from the view if its text stays empty.
+    fn note_edited_in_ui
+    (&self, (displayed_id,text):&(graph_editor::NoteId,String)) -> FallibleResult<()> {
+        let is_pending = self.pending_notes.borrow_mut().remove(displayed_id);
+        if !is_pending {
+            let id = self.get_controller_note_id(*displayed_id)?;
+            self.controller.graph().set_note_text(id,text.clone())
+        } else if text.trim().is_empty() {
+            self.view.graph().frp.inputs.remove_note.emit_event(displayed_id);
+            Ok(())
+        } else {
+            let position = self.view.graph().note_position(*displayed_id);
+            let position = model::module::Position{vector:position};
+            let id       = self.controller.graph().add_note(text,position);
+            self.note_views.borrow_mut().insert(id,*displayed_id);
+            Ok(())
+        }
+    }
+
+    #[allow(clippy::ptr_arg)]
+    fn nodes_grouped_in_ui(&self, nodes:&Vec<graph_editor::NodeId>) -> FallibleResult<()> {
+        if !nodes.is_empty() {
+            let ids = self.get_controller_node_ids(nodes)?;
+            self.controller.graph().add_group(DEFAULT_GROUP_TITLE,ids)?;
+        }
+        Ok(())
+    }
+
+    /// Store the positions of the moved group's nodes. The nodes laid out by the view, having no
+    /// stored position, get the position displayed before the move.
+    fn group_moved_in_ui
+    (&self, (displayed_id,offset):&(graph_editor::GroupId,Vector2)) -> FallibleResult<()> {
+        let id           = self.get_controller_group_id(*displayed_id)?;
+        let graph        = self.controller.graph();
+        let _transaction = graph.module.open_transaction("Move node group");
+        for node in graph.group(id)?.nodes {
+            let metadata     = graph.module.node_metadata(node).ok();
+            let is_placed    = metadata.and_then(|md| md.position).is_some();
+            let displayed_id = self.node_views.borrow().get_by_left(&node).cloned();
+            if let (false,Some(displayed_id)) = (is_placed,displayed_id) {
+                let displayed = self.view.graph().node_position(displayed_id);
+                let position  = model::module::Position{vector:displayed - offset};
+                graph.module.with_node_metadata(node,Box::new(|md| md.position = Some(position)));
+            }
+        }
+        graph.move_group(id,*offset)
+    }
+
+    fn group_title_edited_in_ui
+    (&self, (displayed_id,title):&(graph_editor::GroupId,String)) -> FallibleResult<()> {
+        let id = self.get_controller_group_id(*displayed_id)?;
+        self.controller.graph().set_group_title(id,title.clone())
+    }
+
+    fn group_collapsed_set_in_ui
+    (&self, (displayed_id,collapsed):&(graph_editor::GroupId,bool)) -> FallibleResult<()> {
+        let id = self.get_controller_group_id(*displayed_id)?;
+        self.controller.graph().set_group_collapsed(id,*collapsed)
+    }
+
+    fn group_removed_in_ui(&self, displayed_id:&graph_editor::GroupId) -> FallibleResult<()> {
+        let id = self.get_controller_group_id(*displayed_id)?;
+        self.group_views.borrow_mut().remove_by_left(&id);
+        self.controller.graph().remove_group(id)
+    }
+
     fn node_expression_set_in_ui
     (&self, (displayed_id,expression):&(graph_editor::NodeId,String)) -> FallibleResult<()> {
         let searcher = self.searcher_controller.borrow();
@@ -720,7 +1104,16 @@ impl GraphEditorIntegratedWithControllerModel {
                         let node_view = this.view.graph().model.nodes.get_cloned_ref(&id);
                         let position  = node_view.map(|node| node.position().xy());
                         let position  = position.map(|vector| model::module::Position{vector});
-                        controller::searcher::Mode::NewNode {position}
+                        let edge       = this.view.frp.edge_of_added_node.value();
+                        let views      = this.connection_views.borrow();
+                        let connection = edge.and_then(|edge| views.get_by_right(&edge).cloned());
+                        match connection {
+                            Some(connection) => {
+                                controller::searcher::Mode::InsertIntoConnection
+                                    {connection,position}
+                            },
+                            None => controller::searcher::Mode::NewNode {position},
+                        }
                     },
                     Err(other) => return Err(other.into()),
                 };
@@ -730,6 +1123,7 @@ impl GraphEditorIntegratedWithControllerModel {
                 let controller = this.controller.clone_ref();
                 let searcher = controller::Searcher::new_from_graph_controller
                     (&this.logger,&this.project,controller,mode,selected_nodes)?;
+                searcher.set_actions(this.searcher_actions());
                 executor::global::spawn(searcher.subscribe().for_each(f!([weak_self](notification) {
                     if let Some(this) = weak_self.upgrade() {
                         this.handle_searcher_notification(notification);
@@ -744,6 +1138,29 @@ impl GraphEditorIntegratedWithControllerModel {
         }
     }
 
+    /// Get the Graph Editor's commands, which are suggested in the searcher as actions.
+    fn searcher_actions(&self) -> Vec<controller::searcher::suggestion::Action> {
+        let target    = <GraphEditor as ensogl::application::command::Provider>::label();
+        let instances = self.app.commands.instances.borrow();
+        let instance  = instances.get(target).and_then(|instances| {
+            instances.iter().rev().find(|instance| instance.check_alive())
+        });
+        let commands = instance.iter().flat_map(|instance| &instance.command_map);
+        let commands = commands.filter(|(name,_)| {
+            !HIDDEN_COMMAND_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+        });
+        commands.sorted_by_key(|(name,_)| *name).map(|(name,command)| {
+            let caption  = name.replace('_'," ");
+            let actions  = self.app.shortcuts.actions_of(target,name);
+            let shortcut = actions.iter().filter_map(|action| action.keyboard_description()).min();
+            let endpoint = command.frp.clone_ref();
+            let shortcut = shortcut.map(ImString::new);
+            controller::searcher::suggestion::Action::new(caption,shortcut,move || {
+                endpoint.emit(())
+            })
+        }).collect()
+    }
+
     fn suggestion_picked_in_ui
     (&self, entry:&Option<ide_view::searcher::entry::Id>) -> FallibleResult<()> {
         if let Some(entry) = entry {
@@ -752,7 +1169,20 @@ impl GraphEditorIntegratedWithControllerModel {
             let searcher       = self.searcher_controller.borrow().clone().ok_or_else(error)?;
             let error          = || GraphEditorInconsistency;
             let edited_node    = graph_frp.outputs.edited_node.value().ok_or_else(error)?;
-            let new_code       = searcher.pick_completion_by_index(*entry)?;
+            let new_code       = match searcher.suggestion_by_index(*entry)? {
+                Suggestion::Completion(completion) => searcher.pick_completion(completion)?,
+                Suggestion::Literal(literal)       => searcher.pick_literal(&literal)?,
+                Suggestion::Action(action)         => {
+                    // The action is executed instead of adding or editing the node.
+                    self.view.frp.abort_node_editing.emit(());
+                    action.execute();
+                    return Ok(())
+                }
+                Suggestion::Category(category)     => {
+                    searcher.enter_category(&category);
+                    return Ok(())
+                }
+            };
             let code_and_trees = graph_editor::component::node::port::Expression {
                 code             : new_code,
                 input_span_tree  : default(),
@@ -763,6 +1193,32 @@ impl GraphEditorIntegratedWithControllerModel {
         Ok(())
     }
 
+    /// Show the documentation of the suggestion highlighted in the searcher.
+    fn suggestion_selected_in_ui
+    (&self, entry:&Option<ide_view::searcher::entry::Id>) -> FallibleResult<()> {
+        let searcher = self.searcher_controller.borrow().clone();
+        if let (Some(searcher),Some(entry)) = (searcher,entry) {
+            // The selection may refer to an already replaced list, what is not an error.
+            if let Ok(suggestion) = searcher.suggestion_by_index(*entry) {
+                self.view.frp.set_documentation_html.emit(suggestion.documentation_html());
+            }
+        }
+        Ok(())
+    }
+
+    fn category_entered_in_ui(&self, entry:&ide_view::searcher::entry::Id) -> FallibleResult<()> {
+        let error    = || MissingSearcherController;
+        let searcher = self.searcher_controller.borrow().clone().ok_or_else(error)?;
+        searcher.enter_category_by_index(*entry)
+    }
+
+    fn category_left_in_ui(&self, _:&()) -> FallibleResult<()> {
+        let error    = || MissingSearcherController;
+        let searcher = self.searcher_controller.borrow().clone().ok_or_else(error)?;
+        searcher.leave_category();
+        Ok(())
+    }
+
     fn node_editing_committed_in_ui
     (&self, displayed_id:&graph_editor::NodeId) -> FallibleResult<()> {
         let error = || MissingSearcherController;
@@ -792,6 +1248,14 @@ impl GraphEditorIntegratedWithControllerModel {
         Ok(())
     }
 
+    /// Log the connection made between ports of incompatible types. The warning is displayed by
+    /// the graph editor on the target node.
+    fn connection_type_mismatch_in_ui(&self, edge_id:&graph_editor::EdgeId) {
+        let warning = self.view.graph().model.edge_type_warning(*edge_id).unwrap_or_default();
+        warning!(self.logger,"Connection {edge_id} was made between ports of incompatible types. \
+            {warning}");
+    }
+
     fn connection_removed_in_ui(&self, edge_id:&graph_editor::EdgeId) -> FallibleResult<()> {
         let connection = self.get_controller_connection(*edge_id)?;
         self.connection_views.borrow_mut().remove_by_left(&connection);
@@ -946,6 +1410,19 @@ impl GraphEditorIntegratedWithControllerModel {
         }))
     }
 
+    fn get_controller_note_id
+    (&self, displayed_id:graph_editor::NoteId) -> Result<model::module::NoteId, MissingMappingFor> {
+        let err = MissingMappingFor::DisplayedNote(displayed_id);
+        self.note_views.borrow().get_by_right(&displayed_id).cloned().ok_or(err)
+    }
+
+    fn get_controller_group_id
+    (&self, displayed_id:graph_editor::GroupId)
+    -> Result<model::module::GroupId, MissingMappingFor> {
+        let err = MissingMappingFor::DisplayedGroup(displayed_id);
+        self.group_views.borrow().get_by_right(&displayed_id).cloned().ok_or(err)
+    }
+
     fn get_displayed_node_id
     (&self, node_id:ast::Id) -> Result<graph_editor::NodeId, MissingMappingFor> {
         let err = MissingMappingFor::ControllerNode(node_id);
@@ -996,7 +1473,7 @@ pub struct NodeEditor {
     display_object : display::object::Instance,
     #[allow(missing_docs)]
     pub graph     : Rc<GraphEditorIntegratedWithController>,
-    visualization : controller::Visualization
+    visualization : Rc<controller::Visualization>,
 }
 
 impl NodeEditor {
@@ -1013,27 +1490,71 @@ impl NodeEditor {
             controller,project);
         let graph = Rc::new(graph);
         display_object.add_child(&graph.model.view);
+        let visualization  = Rc::new(visualization);
         info!(logger, "Created.");
         Ok(NodeEditor {logger,display_object,graph,visualization}.init().await?)
     }
 
     async fn init(self) -> FallibleResult<Self> {
         let graph_editor = self.graph.graph_editor();
-        let identifiers  = self.visualization.list_visualizations().await;
-        let identifiers  = identifiers.unwrap_or_default();
         let project_name = self.graph.model.project.name().to_string();
         graph_editor.breadcrumbs.frp.project_name.emit(project_name);
-        for identifier in identifiers {
-            let visualization = self.visualization.load_visualization(&identifier).await;
-            let visualization = visualization.map(|visualization| {
-                graph_editor.frp.register_visualization.emit(Some(visualization));
-            });
-            visualization?;
-        }
+        let registered = Self::register_visualizations(&graph_editor,&self.visualization,&[]);
+        let registered = registered.await?;
+        self.setup_visualizations_refreshing(registered);
         info!(self.logger, "Initialized.");
         Ok(self)
     }
 
+    /// Load all available visualizations and register them in the graph editor. The visualizations
+    /// from `previously_registered` which are no longer available are unregistered. Returns the
+    /// paths of the registered visualizations.
+    async fn register_visualizations
+    ( graph_editor          : &GraphEditor
+    , controller            : &controller::Visualization
+    , previously_registered : &[visualization::Path]
+    ) -> FallibleResult<Vec<visualization::Path>> {
+        let identifiers    = controller.list_visualizations().await.unwrap_or_default();
+        let mut registered = Vec::new();
+        for identifier in identifiers {
+            let visualization = controller.load_visualization(&identifier).await?;
+            registered.push(visualization.signature.path.clone_ref());
+            graph_editor.frp.register_visualization.emit(Some(visualization));
+        }
+        for path in previously_registered.iter().filter(|path| !registered.contains(path)) {
+            graph_editor.frp.unregister_visualization.emit(path);
+        }
+        Ok(registered)
+    }
+
+    /// Register the visualizations again each time the files in the project's visualization
+    /// directory change.
+    fn setup_visualizations_refreshing(&self, registered:Vec<visualization::Path>) {
+        let logger            = self.logger.clone_ref();
+        let weak_graph        = Rc::downgrade(&self.graph);
+        let weak_controller   = Rc::downgrade(&self.visualization);
+        let mut notifications = self.visualization.subscribe();
+        executor::global::spawn(async move {
+            let mut registered = registered;
+            while notifications.next().await.is_some() {
+                let graph      = weak_graph.upgrade();
+                let controller = weak_controller.upgrade();
+                let (graph,controller) = match (graph,controller) {
+                    (Some(graph),Some(controller)) => (graph,controller),
+                    _                              => break,
+                };
+                info!(logger,"Visualization files changed, reloading visualizations.");
+                let graph_editor = graph.graph_editor();
+                let result       = Self::register_visualizations(&graph_editor,&controller,
+                    &registered).await;
+                match result {
+                    Ok(paths) => registered = paths,
+                    Err(err)  => error!(logger,"Failed to reload visualizations: {err}"),
+                }
+            }
+        });
+    }
+
     /// Get ids of the nodes selected in the editor.
     ///
     /// They shall be ordered by the order of the selecting. Node selected as first shall be at
@@ -1064,23 +1585,23 @@ impl list_view::entry::ModelProvider for SuggestionProvider {
 
     fn get(&self, id: usize) -> Option<list_view::entry::Model> {
         let suggestion = self.list.get_cloned(id)?;
-        if let MatchInfo::Matches {subsequence} = suggestion.match_info {
-            let caption          = suggestion.suggestion.caption();
-            let model            = list_view::entry::Model::new(caption.clone());
-            let mut char_iter    = caption.char_indices().enumerate();
-            let highlighted_iter = subsequence.indices.iter().filter_map(|idx| loop {
-                if let Some(char) = char_iter.next() {
-                    let (char_idx,(byte_id,char)) = char;
-                    if char_idx == *idx {
-                        let start = ensogl_text::Bytes(byte_id as i32);
-                        let end   = ensogl_text::Bytes((byte_id + char.len_utf8()) as i32);
-                        break Some(ensogl_text::Range::new(start,end))
-                    }
-                } else {
-                    break None;
-                }
+        if let MatchInfo::Matches {..} = suggestion.match_info {
+            let caption     = suggestion.suggestion.caption();
+            let label       = match &suggestion.suggestion {
+                Suggestion::Action(action) => match action.shortcut.deref() {
+                    Some(shortcut) => iformat!("{caption} ({shortcut})"),
+                    None           => caption.clone(),
+                },
+                Suggestion::Category(_)   => iformat!("{caption}{CATEGORY_LABEL_SUFFIX}"),
+                Suggestion::Completion(_) => caption.clone(),
+                Suggestion::Literal(_)    => caption.clone(),
+            };
+            let highlighted = suggestion.matched_byte_ranges().into_iter().map(|range| {
+                let start = ensogl_text::Bytes(range.start as i32);
+                let end   = ensogl_text::Bytes(range.end as i32);
+                ensogl_text::Range::new(start,end)
             });
-            let model = model.highlight(highlighted_iter);
+            let model = list_view::entry::Model::new(label).highlight(highlighted);
             Some(model)
         } else {
             None
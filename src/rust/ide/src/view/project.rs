@@ -18,6 +18,7 @@ use enso_frp::io::keyboard;
 use enso_shapely::shared;
 use ensogl_theme;
 use ide_view::graph_editor;
+use ide_view::project_browser;
 use nalgebra::Vector2;
 
 
@@ -73,6 +74,11 @@ shared! { ProjectView
         pub fn set_size(&mut self, size:Vector2<f32>) {
             self.layout.set_size(size);
         }
+
+        /// Get the model of the project displayed in this view.
+        pub fn model(&self) -> model::Project {
+            self.model.clone_ref()
+        }
     }
 }
 
@@ -92,8 +98,17 @@ pub async fn recreate_if_missing(project:&model::Project, path:&FilePath, defaul
 }
 
 impl ProjectView {
-    /// Create a new ProjectView.
-    pub async fn new(logger:impl AnyLogger, model:model::Project)
+    /// Create the application in which the project views are displayed.
+    pub fn setup_application() -> Application {
+        let application = Application::new(&web::get_html_element_by_id("root").unwrap());
+        Self::setup_components(&application);
+        ensogl_theme::dark::setup(&application);
+        ensogl_theme::light::setup(&application);
+        application
+    }
+
+    /// Create a new ProjectView displayed in the given application.
+    pub async fn new(logger:impl AnyLogger, model:model::Project, application:&Application)
     -> FallibleResult<Self> {
         let logger      = Logger::sub(logger,"ProjectView");
         let module_path = initial_module_path(&model)?;
@@ -105,14 +120,10 @@ impl ProjectView {
         let method            = module_path.method_pointer(model.name(),MAIN_DEFINITION_NAME);
         let graph_controller  = controller::ExecutedGraph::new(&logger,model.clone_ref(),method);
         let graph_controller  = graph_controller.await?;
-        ensogl::system::web::sleep(std::time::Duration::from_secs(10)).await;
-        let application       = Application::new(&web::get_html_element_by_id("root").unwrap());
+        let application       = application.clone_ref();
         let scene             = application.display.scene();
         let camera            = scene.camera();
         let navigator         = Navigator::new(&scene,&camera);
-        Self::setup_components(&application);
-        ensogl_theme::dark::setup(&application);
-        ensogl_theme::light::setup(&application);
         let _world = &application.display;
         // graph::register_shapes(&world);
         let keyboard                 = Keyboard::default();
@@ -144,6 +155,7 @@ impl ProjectView {
 
     fn setup_components(app:&Application) {
         app.views.register::<graph_editor::GraphEditor>();
+        app.views.register::<project_browser::View>();
     }
 
     /// Forgets ProjectView, so it won't get dropped when it goes out of scope.
@@ -0,0 +1,175 @@
+//! This module contains ProjectBrowserView, which integrates the Project Browser component with
+//! its controller.
+
+use crate::prelude::*;
+
+use crate::controller::ProjectBrowser;
+
+use enso_frp as frp;
+use enso_protocol::project_manager::ProjectMetadata;
+use ensogl::application::Application;
+use ensogl::display::traits::*;
+use ide_view::project_browser;
+use ide_view::project_browser::entry;
+
+
+
+// =============
+// === Entry ===
+// =============
+
+/// A single entry listed in the project browser.
+#[derive(Clone,Debug)]
+pub enum Entry {
+    /// The user's project, which can be opened or deleted.
+    Project(ProjectMetadata),
+    /// The sample, from which a new project can be created.
+    Sample(ProjectMetadata),
+}
+
+impl Entry {
+    /// The label displayed in the project browser.
+    pub fn label(&self) -> String {
+        match self {
+            Entry::Project(project) => project.name.to_string(),
+            Entry::Sample(sample)   => iformat!("New project from sample {sample.name}"),
+        }
+    }
+}
+
+#[derive(Clone,CloneRef,Debug)]
+struct EntryProvider {
+    entries : Rc<Vec<Entry>>,
+}
+
+impl entry::ModelProvider for EntryProvider {
+    fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn get(&self, id:entry::Id) -> Option<entry::Model> {
+        self.entries.get(id).map(|entry| entry::Model::new(entry.label()))
+    }
+}
+
+
+
+// ==========================
+// === ProjectBrowserView ===
+// ==========================
+
+/// The function called with the project which was chosen to be opened.
+pub type OpenProjectCallback = Rc<dyn Fn(ProjectMetadata)>;
+
+#[derive(Clone,CloneRef,Derivative)]
+#[derivative(Debug)]
+struct Model {
+    logger         : Logger,
+    view           : project_browser::View,
+    controller     : ProjectBrowser,
+    entries        : Rc<RefCell<Rc<Vec<Entry>>>>,
+    opened_project : Rc<Cell<Option<Uuid>>>,
+    #[derivative(Debug="ignore")]
+    open_project   : OpenProjectCallback,
+}
+
+impl Model {
+    fn entry(&self, id:entry::Id) -> Option<Entry> {
+        self.entries.borrow().get(id).cloned()
+    }
+
+    /// Fetch the current list of projects and samples and display them.
+    fn refresh_entries(&self) {
+        let this = self.clone_ref();
+        executor::global::spawn(async move {
+            let result = async {
+                let projects = this.controller.recent_projects().await?;
+                let samples  = this.controller.samples().await?;
+                let projects = projects.into_iter().map(Entry::Project);
+                let samples  = samples.into_iter().map(Entry::Sample);
+                FallibleResult::Ok(Rc::new(projects.chain(samples).collect_vec()))
+            }.await;
+            match result {
+                Ok(entries) => {
+                    *this.entries.borrow_mut() = entries.clone_ref();
+                    this.view.set_entries(entry::AnyModelProvider::from(EntryProvider {entries}));
+                }
+                Err(err) => error!(this.logger,"Failed to list the projects: {err}"),
+            }
+        });
+    }
+
+    fn open_entry(&self, id:entry::Id) {
+        let this  = self.clone_ref();
+        let entry = self.entry(id);
+        executor::global::spawn(async move {
+            let project = match entry {
+                Some(Entry::Project(project)) => Ok(project),
+                Some(Entry::Sample(sample))   => {
+                    this.controller.create_project_from_sample(&sample).await
+                }
+                None => return,
+            };
+            match project {
+                Ok(project) => (this.open_project)(project),
+                Err(err)    => error!(this.logger,"Failed to create the project: {err}"),
+            }
+        });
+    }
+
+    fn delete_entry(&self, id:entry::Id) {
+        if let Some(Entry::Project(project)) = self.entry(id) {
+            if self.opened_project.get() == Some(project.id) {
+                error!(self.logger,"Cannot delete the currently opened project {project.name}.");
+            } else {
+                let this = self.clone_ref();
+                executor::global::spawn(async move {
+                    if let Err(err) = this.controller.delete_project(project.id).await {
+                        error!(this.logger,"Failed to delete the project {project.name}: {err}");
+                    }
+                    this.refresh_entries();
+                });
+            }
+        }
+    }
+}
+
+/// The Project Browser integrated with its controller.
+///
+/// The browser lists the user's projects and samples each time it is shown. Choosing a project or
+/// a sample calls the `OpenProjectCallback`; it is the caller's responsibility to actually switch
+/// the project.
+#[derive(Debug)]
+pub struct ProjectBrowserView {
+    model   : Model,
+    network : frp::Network,
+}
+
+impl ProjectBrowserView {
+    /// Create a new project browser, initially hidden, displayed in the application's world.
+    pub fn new
+    ( logger       : impl AnyLogger
+    , app          : &Application
+    , controller   : ProjectBrowser
+    , open_project : OpenProjectCallback
+    ) -> Self {
+        let logger         = Logger::sub(logger,"ProjectBrowserView");
+        let view           = app.new_view::<project_browser::View>();
+        let entries        = default();
+        let opened_project = default();
+        app.display.add_child(&view);
+        let model = Model {logger,view,controller,entries,opened_project,open_project};
+        let frp   = &model.view.frp;
+        frp::new_network! { network
+            eval frp.is_visible ((is_visible) if *is_visible { model.refresh_entries() });
+            eval frp.chosen_entry ((id) model.open_entry(*id));
+            eval frp.deleted_entry ((id) model.delete_entry(*id));
+        }
+        Self {model,network}
+    }
+
+    /// Set the id of the currently opened project, which cannot be deleted.
+    pub fn set_opened_project(&self, id:Option<Uuid>) {
+        self.model.opened_project.set(id);
+    }
+}
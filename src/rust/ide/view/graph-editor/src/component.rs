@@ -3,9 +3,13 @@
 pub mod breadcrumbs;
 pub mod type_coloring;
 pub mod edge;
+pub mod group;
 pub mod node;
+pub mod note;
 pub mod visualization;
 
 pub use breadcrumbs::Breadcrumbs;
 pub use edge::Edge;
+pub use group::Group;
 pub use node::Node;
+pub use note::Note;
@@ -19,8 +19,11 @@ use ensogl::display::camera::Camera2d;
 use ensogl::display::object::ObjectOps;
 use ensogl::display::scene::Scene;
 use ensogl::display::shape::*;
+use ensogl::display::shape::text::glyph::system::GlyphSystem;
+use ensogl::display::shape::text::glyph::system::Line;
 use ensogl::display::shape::text::text_field::FocusManager;
 use ensogl::gui::component;
+use ensogl_theme as theme;
 use logger::AnyLogger;
 use logger::enabled::Logger;
 use std::cmp::Ordering;
@@ -92,7 +95,13 @@ pub struct FrpInputs {
     /// Sets the project name.
     pub project_name                : frp::Source<String>,
     /// Select the breadcrumb by its index.
-    pub select_breadcrumb           : frp::Source<usize>
+    pub select_breadcrumb           : frp::Source<usize>,
+    /// Sets the error message of the failed execution of the displayed graph. The message is shown
+    /// under the breadcrumbs panel; `None` hides it.
+    pub set_execution_failure       : frp::Source<Option<String>>,
+    /// Sets the warning about the module's file being changed by another program. The warning is
+    /// shown under the execution failure message; `None` hides it.
+    pub set_file_warning            : frp::Source<Option<String>>,
 }
 
 impl FrpInputs {
@@ -105,9 +114,11 @@ impl FrpInputs {
             cancel_project_name_editing <- source();
             project_name                <- source();
             select_breadcrumb           <- source();
+            set_execution_failure       <- source();
+            set_file_warning            <- source();
         }
         Self{push_breadcrumb,pop_breadcrumb,outside_press,cancel_project_name_editing,project_name,
-            select_breadcrumb}
+            select_breadcrumb,set_execution_failure,set_file_warning}
     }
 }
 
@@ -233,6 +244,12 @@ pub struct BreadcrumbsModel {
     frp_debug             : DebugFrpInputs,
     current_index         : Rc<Cell<usize>>,
     camera                : Camera2d,
+    glyph_system          : GlyphSystem,
+    /// The label displaying the execution failure message under the panel.
+    execution_failure     : Line,
+    /// The label displaying the warning about external changes of the module's file.
+    file_warning          : Line,
+    style                 : StyleWatch,
 }
 
 impl BreadcrumbsModel {
@@ -250,15 +267,40 @@ impl BreadcrumbsModel {
         let current_index         = default();
         let camera                = scene.camera().clone_ref();
         let background            = component::ShapeView::<background::Shape>::new(&logger,&scene);
+        let font                  = scene.fonts.get_or_load_embedded_font("DejaVuSansMono").unwrap();
+        let glyph_system          = GlyphSystem::new(&scene,font);
+        let execution_failure     = glyph_system.new_line();
+        let file_warning          = glyph_system.new_line();
 
+        let symbol = glyph_system.sprite_system().symbol();
+        scene.views.main.remove(&symbol);
+        scene.views.breadcrumbs.add(&symbol);
+
+        // FIXME : StyleWatch is unsuitable here, as it was designed as an internal tool for shape system (#795)
+        let style = StyleWatch::new(&scene.style_sheet);
         Self{logger,display_object,scene,breadcrumbs,project_name,breadcrumbs_container,
-            frp_inputs,current_index,frp_debug,camera,background}.init()
+            frp_inputs,current_index,frp_debug,camera,background,glyph_system,execution_failure,
+            file_warning,style}.init()
     }
 
     fn init(self) -> Self {
         self.add_child(&self.project_name);
         self.add_child(&self.breadcrumbs_container);
+        self.add_child(&self.execution_failure);
+        self.add_child(&self.file_warning);
         self.project_name.set_position_x(HORIZONTAL_MARGIN);
+        let color = theme::vars::graph_editor::breadcrumbs::execution_failure::color;
+        let color = self.style.get_color(color);
+        let y     = -(TEXT_SIZE + VERTICAL_MARGIN * 3.0) - TEXT_SIZE;
+        self.execution_failure.set_font_size(TEXT_SIZE);
+        self.execution_failure.set_font_color(color::Rgba::from(color));
+        self.execution_failure.set_position(Vector3(HORIZONTAL_MARGIN,y.round(),0.0));
+        let color = theme::vars::graph_editor::breadcrumbs::file_warning::color;
+        let color = self.style.get_color(color);
+        let y     = y - TEXT_SIZE - VERTICAL_MARGIN;
+        self.file_warning.set_font_size(TEXT_SIZE);
+        self.file_warning.set_font_color(color::Rgba::from(color));
+        self.file_warning.set_position(Vector3(HORIZONTAL_MARGIN,y.round(),0.0));
         self.relayout_for_project_name_width(self.project_name.width());
         self.project_name.frp.select.emit(());
         self
@@ -272,6 +314,17 @@ impl BreadcrumbsModel {
         self.set_position(Vector3(x_position.round(),y_position.round(),0.0));
     }
 
+    /// Show the message about execution failure under the panel, or hide it if `None` is given.
+    fn set_execution_failure(&self, failure:&Option<String>) {
+        let text = failure.as_ref().map(|message| format!("Execution failed: {}",message));
+        self.execution_failure.set_text(text.unwrap_or_default());
+    }
+
+    /// Show the warning about the module's file under the panel, or hide it if `None` is given.
+    fn set_file_warning(&self, warning:&Option<String>) {
+        self.file_warning.set_text(warning.clone().unwrap_or_default());
+    }
+
     fn width(&self) -> f32 {
         self.breadcrumbs.borrow().iter().map(|breadcrumb| breadcrumb.width()).sum()
     }
@@ -503,6 +556,12 @@ impl Breadcrumbs {
             frp.outputs.project_name <+ model.project_name.frp.outputs.name;
 
 
+            // === Execution Failure ===
+
+            eval frp.set_execution_failure((failure) model.set_execution_failure(failure));
+            eval frp.set_file_warning((warning) model.set_file_warning(warning));
+
+
             // === GUI Update ===
 
             eval model.project_name.frp.outputs.width((width) {
@@ -0,0 +1,286 @@
+//! Definition of the Group component: a titled frame drawn around a set of nodes.
+
+use crate::prelude::*;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::data::color;
+use ensogl::display::shape::*;
+use ensogl::display::traits::*;
+use ensogl::display;
+use ensogl::gui::component;
+use ensogl_text as text;
+use ensogl_theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The distance between the frame's border and the nodes inside.
+pub const PADDING : f32 = 20.0;
+/// The height of the frame's part containing the title.
+pub const TITLE_HEIGHT : f32 = 30.0;
+/// The size of the title's text.
+pub const TITLE_SIZE : f32 = 14.0;
+/// The corner radius of the frame.
+pub const CORNER_RADIUS : f32 = 12.0;
+/// The minimal width of the frame, used when the title is (almost) empty.
+pub const MIN_WIDTH : f32 = 80.0;
+/// The opacity of the frame's background.
+pub const FRAME_ALPHA : f32 = 0.2;
+
+
+
+// ===============
+// === Palette ===
+// ===============
+
+/// The hues of the group color palette.
+const PALETTE : &[fn() -> f32] = &
+    [ color::Lch::blue_hue
+    , color::Lch::green_hue
+    , color::Lch::orange_hue
+    , color::Lch::violet_hue
+    , color::Lch::blue_green_hue
+    , color::Lch::red_hue
+    , color::Lch::yellow_hue
+    , color::Lch::pink_hue
+    ];
+
+/// The frame color of the given index in the group color palette. The palette is cyclic, so every
+/// index denotes some color.
+pub fn color(index:usize) -> color::Lcha {
+    let hue = PALETTE[index % PALETTE.len()]();
+    color::Lcha::new(0.6,0.6,hue,FRAME_ALPHA)
+}
+
+
+
+// =============
+// === Shape ===
+// =============
+
+/// Group frame shape definition.
+pub mod shape {
+    use super::*;
+
+    ensogl::define_shape_system! {
+        (color_rgba:Vector4<f32>) {
+            let width  : Var<Pixels> = "input_size.x".into();
+            let height : Var<Pixels> = "input_size.y".into();
+            let color  = Var::<color::Rgba>::from(color_rgba);
+            let shape  = Rect((&width,&height)).corners_radius(CORNER_RADIUS.px());
+            let shape  = shape.fill(color);
+            shape.into()
+        }
+    }
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl_text::define_endpoints! {
+    Input {
+        set_title           (String),
+        set_color           (usize),
+        set_bounds          ((Vector2,Vector2)),
+        set_collapsed       (bool),
+        start_title_editing (),
+        stop_title_editing  (),
+    }
+    Output {
+        title_edited (String),
+    }
+}
+
+
+
+// =============
+// === Group ===
+// =============
+
+/// Internal data of `Group`.
+#[derive(Clone,CloneRef,Debug)]
+#[allow(missing_docs)]
+pub struct GroupModel {
+    pub display_object : display::object::Instance,
+    pub logger         : Logger,
+    pub frp            : FrpEndpoints,
+    pub frame          : component::ShapeView<shape::Shape>,
+    pub title          : text::Area,
+    title_text         : Rc<RefCell<String>>,
+    title_width        : Rc<Cell<f32>>,
+    bounds             : Rc<Cell<(Vector2,Vector2)>>,
+    collapsed          : Rc<Cell<bool>>,
+    editing            : Rc<Cell<bool>>,
+}
+
+impl GroupModel {
+    /// Constructor.
+    pub fn new(app:&Application, network:&frp::Network) -> Self {
+        let scene          = app.display.scene();
+        let logger         = Logger::new("group");
+        let display_object = display::object::Instance::new(&logger);
+        let frame          = component::ShapeView::<shape::Shape>::new(&logger,scene);
+        let title          = app.new_view::<text::Area>();
+        let input          = FrpInputs::new(&network);
+        let frp            = FrpEndpoints::new(&network,input);
+        let title_text     = default();
+        let title_width    = default();
+        let bounds         = default();
+        let collapsed      = default();
+        let editing        = default();
+        display_object.add_child(&frame);
+        display_object.add_child(&title);
+        title.mod_position(|t| {
+            t.x = PADDING;
+            t.y = -(TITLE_HEIGHT - TITLE_SIZE) / 2.0;
+        });
+
+        // FIXME : StyleWatch is unsuitable here, as it was designed as an internal tool for shape system (#795)
+        let styles      = StyleWatch::new(&scene.style_sheet);
+        let title_color = styles.get_color(ensogl_theme::vars::graph_editor::group::title::color);
+        title.set_default_color(color::Rgba::from(title_color));
+        title.set_default_text_size(text::Size(TITLE_SIZE));
+        title.remove_all_cursors();
+        Self {display_object,logger,frp,frame,title,title_text,title_width,bounds,collapsed
+             ,editing}
+    }
+
+    /// The size of the frame. The collapsed frame contains only the title.
+    fn size(&self) -> Vector2<f32> {
+        let title_width = (self.title_width.get() + PADDING * 2.0).max(MIN_WIDTH);
+        if self.collapsed.get() {
+            Vector2::new(title_width,TITLE_HEIGHT)
+        } else {
+            let (min,max) = self.bounds.get();
+            let width     = (max.x - min.x + PADDING * 2.0).max(title_width);
+            let height    = max.y - min.y + PADDING * 2.0 + TITLE_HEIGHT;
+            Vector2::new(width,height)
+        }
+    }
+
+    /// Place the frame around the bounds of the grouped nodes. The group is positioned by the top
+    /// left corner of its frame.
+    fn update_layout(&self) {
+        let (min,max) = self.bounds.get();
+        let size      = self.size();
+        self.mod_position(|t| {
+            t.x = min.x - PADDING;
+            t.y = max.y + PADDING + TITLE_HEIGHT;
+        });
+        self.frame.shape.sprite.size.set(size);
+        self.frame.mod_position(|t| {
+            t.x = size.x / 2.0;
+            t.y = -size.y / 2.0;
+        });
+    }
+
+    fn set_title(&self, title:&str) {
+        if *self.title_text.borrow() != title {
+            self.title.set_content(title.to_string());
+        }
+    }
+
+    fn set_color(&self, index:usize) {
+        let color = color::Rgba::from(color(index));
+        self.frame.shape.color_rgba.set(Vector4::new(color.red,color.green,color.blue,color.alpha));
+    }
+
+    fn set_title_width(&self, width:f32) {
+        self.title_width.set(width);
+        self.update_layout();
+    }
+
+    fn set_bounds(&self, bounds:(Vector2,Vector2)) {
+        self.bounds.set(bounds);
+        self.update_layout();
+    }
+
+    fn set_collapsed(&self, collapsed:bool) {
+        self.collapsed.set(collapsed);
+        self.update_layout();
+    }
+
+    /// Check if the title is being edited.
+    pub fn is_title_editing(&self) -> bool {
+        self.editing.get()
+    }
+
+    fn start_title_editing(&self) {
+        if !self.editing.replace(true) {
+            self.title.set_active_on();
+            self.title.set_cursor_at_end();
+        }
+    }
+
+    /// Stop editing the title. Returns the edited title if it was being edited.
+    fn stop_title_editing(&self) -> Option<String> {
+        self.editing.replace(false).as_some_from(|| {
+            self.title.set_active_off();
+            self.title.remove_all_cursors();
+            self.title_text.borrow().clone()
+        })
+    }
+}
+
+impl display::Object for GroupModel {
+    fn display_object(&self) -> &display::object::Instance {
+        &self.display_object
+    }
+}
+
+/// A group of nodes displayed in the graph: a titled frame drawn around them.
+///
+/// The group does not track its nodes; the graph editor sets the bounds of the grouped nodes with
+/// the `set_bounds` input. The collapsed group displays its title only.
+#[derive(Clone,CloneRef,Debug)]
+#[allow(missing_docs)]
+pub struct Group {
+    pub model       : Rc<GroupModel>,
+    pub frp_network : frp::Network,
+}
+
+impl Deref for Group {
+    type Target = GroupModel;
+    fn deref(&self) -> &Self::Target {
+        &self.model
+    }
+}
+
+impl Group {
+    /// Constructor.
+    pub fn new(app:&Application) -> Self {
+        let frp_network = frp::Network::new();
+        let model       = Rc::new(GroupModel::new(app,&frp_network));
+        let inputs      = &model.frp.input;
+        let title       = &model.title;
+
+        frp::extend! { frp_network
+            eval title.changed ((text) *model.title_text.borrow_mut() = text.into());
+            eval title.width   ((width) model.set_title_width(*width));
+
+            eval  inputs.set_title           ((text) model.set_title(text));
+            eval  inputs.set_color           ((index) model.set_color(*index));
+            eval  inputs.set_bounds          ((bounds) model.set_bounds(*bounds));
+            eval  inputs.set_collapsed       ((collapsed) model.set_collapsed(*collapsed));
+            eval_ inputs.start_title_editing (model.start_title_editing());
+            title_edited <= inputs.stop_title_editing.map(f_!(model.stop_title_editing()));
+            model.frp.source.title_edited <+ title_edited;
+        }
+        model.set_color(0);
+        model.update_layout();
+        Self {model,frp_network}
+    }
+}
+
+impl display::Object for Group {
+    fn display_object(&self) -> &display::object::Instance {
+        &self.display_object
+    }
+}
@@ -18,6 +18,7 @@ use ensogl::display;
 use ensogl::gui::component::Animation;
 use ensogl::gui::component;
 use ensogl::application::Application;
+use ensogl_text as text;
 use ensogl_text::Text;
 use ensogl_theme;
 
@@ -38,6 +39,9 @@ pub const CORNER_RADIUS      : f32 = 14.0;
 pub const NODE_HEIGHT        : f32 = 28.0;
 pub const TEXT_OFF           : f32 = 10.0;
 pub const SHADOW_SIZE        : f32 = 10.0;
+pub const COMMENT_OFFSET     : f32 = 20.0;
+pub const GLYPH_WIDTH        : f32 = 7.224_609_4;
+pub const WARNING_OFFSET     : f32 = 50.0;
 
 
 
@@ -130,14 +134,22 @@ pub mod drag_area {
 
 ensogl_text::define_endpoints! {
     Input {
-        select              (),
-        deselect            (),
-        set_expression      (Expression),
-        set_expression_type ((ast::Id,Option<Type>)),
-        set_visualization   (Option<visualization::Instance>),
+        select                (),
+        deselect              (),
+        set_expression        (Expression),
+        set_expression_type   ((ast::Id,Option<Type>)),
+        set_visualization     (Option<visualization::Instance>),
+        set_comment           (Option<String>),
+        set_warning           (Option<String>),
+        start_comment_editing (),
+        stop_comment_editing  (),
+        set_widgets           (Vec<port::widget::Definition>),
+        stop_widget_editing   (),
     }
     Output {
-        expression (Text)
+        expression     (Text),
+        comment_edited (Option<String>),
+        port_value_set ((span_tree::Crumbs,String)),
     }
 }
 
@@ -183,6 +195,10 @@ pub struct NodeModel {
     pub ports          : port::Manager,
     pub visualization  : visualization::Container,
     pub output_ports   : OutputPorts,
+    pub comment        : text::Area,
+    comment_text       : Rc<RefCell<String>>,
+    comment_editing    : Rc<Cell<bool>>,
+    pub warning        : text::Area,
 }
 
 
@@ -234,9 +250,37 @@ impl NodeModel {
         let output_ports = OutputPorts::new(&scene);
         display_object.add_child(&output_ports);
 
+        let comment = app.new_view::<text::Area>();
+        comment.mod_position(|t| {
+            t.x = TEXT_OFF;
+            t.y = NODE_HEIGHT + COMMENT_OFFSET;
+        });
+        // FIXME : StyleWatch is unsuitable here, as it was designed as an internal tool for shape system (#795)
+        let styles        = StyleWatch::new(&scene.style_sheet);
+        let comment_color = ensogl_theme::vars::graph_editor::node::comment::color;
+        let comment_color = styles.get_color(comment_color);
+        comment.set_default_color(color::Rgba::from(comment_color));
+        comment.set_default_text_size(text::Size(12.0));
+        comment.remove_all_cursors();
+        display_object.add_child(&comment);
+        let comment_text    = default();
+        let comment_editing = default();
+
+        let warning = app.new_view::<text::Area>();
+        warning.mod_position(|t| {
+            t.x = TEXT_OFF;
+            t.y = -WARNING_OFFSET;
+        });
+        let warning_color = ensogl_theme::vars::graph_editor::edge::_type::mismatch::color;
+        let warning_color = styles.get_color(warning_color);
+        warning.set_default_color(color::Rgba::from(warning_color));
+        warning.set_default_text_size(text::Size(12.0));
+        warning.remove_all_cursors();
+        display_object.add_child(&warning);
+
         let app = app.clone_ref();
         Self {app,display_object,logger,frp,main_area,drag_area,output_ports,ports
-             ,visualization} . init()
+             ,visualization,comment,comment_text,comment_editing,warning} . init()
     }
 
     fn init(self) -> Self {
@@ -277,6 +321,41 @@ impl NodeModel {
     pub fn visualization(&self) -> &visualization::Container {
         &self.visualization
     }
+
+    fn set_comment(&self, comment:&Option<String>) {
+        let comment = comment.clone().unwrap_or_default();
+        if *self.comment_text.borrow() != comment {
+            self.comment.set_content(comment);
+        }
+    }
+
+    /// Show the warning below the node, e.g. about the incompatible type of connected value.
+    fn set_warning(&self, warning:&Option<String>) {
+        self.warning.set_content(warning.clone().unwrap_or_default());
+    }
+
+    /// Check if any text of the node is being edited: the expression, the comment, or the value
+    /// in one of the widgets.
+    pub fn is_editing(&self) -> bool {
+        self.comment_editing.get() || self.ports.is_editing()
+    }
+
+    fn start_comment_editing(&self) {
+        if !self.comment_editing.replace(true) {
+            self.comment.set_active_on();
+            self.comment.set_cursor_at_end();
+        }
+    }
+
+    /// Stop editing the comment. Returns the edited comment if it was being edited.
+    fn stop_comment_editing(&self) -> Option<Option<String>> {
+        self.comment_editing.replace(false).as_some_from(|| {
+            self.comment.set_active_off();
+            self.comment.remove_all_cursors();
+            let comment = self.comment_text.borrow().clone();
+            (!comment.trim().is_empty()).as_some(comment)
+        })
+    }
 }
 
 impl Node {
@@ -304,6 +383,27 @@ impl Node {
             eval model.ports.frp.width ((w) model.set_width(*w));
 
             model.frp.source.expression <+ model.ports.frp.expression.map(|t|t.clone_ref());
+
+
+            // === Comment ===
+
+            eval model.comment.changed ((text) *model.comment_text.borrow_mut() = text.into());
+            eval  inputs.set_comment           ((comment) model.set_comment(comment));
+            eval_ inputs.start_comment_editing (model.start_comment_editing());
+            comment_edited <= inputs.stop_comment_editing.map(f_!(model.stop_comment_editing()));
+            model.frp.source.comment_edited <+ comment_edited;
+
+
+            // === Warning ===
+
+            eval inputs.set_warning ((warning) model.set_warning(warning));
+
+
+            // === Widgets ===
+
+            eval  inputs.set_widgets         ((widgets) model.ports.set_widgets(widgets.clone()));
+            eval_ inputs.stop_widget_editing (model.ports.stop_widget_editing());
+            model.frp.source.port_value_set <+ model.ports.frp.value_set;
         }
 
         Self {frp_network,model}
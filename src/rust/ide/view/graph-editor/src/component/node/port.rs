@@ -2,6 +2,8 @@
 
 #[warn(missing_docs)]
 pub mod output;
+#[warn(missing_docs)]
+pub mod widget;
 
 use crate::prelude::*;
 
@@ -36,18 +38,29 @@ pub mod shape {
     use super::*;
 
     ensogl::define_shape_system! {
-        (style:Style, hover:f32) {
+        (style:Style, hover:f32, highlight_rgba:Vector4<f32>) {
             let width  : Var<Pixels> = "input_size.x".into();
             let height : Var<Pixels> = "input_size.y".into();
             let radius = 6.px();
             let shape  = Rect((&width,&height)).corners_radius(radius);
-            let color  : Var<color::Rgba> = "srgba(1.0,1.0,1.0,0.00001)".into();
+            let color  = Var::<color::Rgba>::from(highlight_rgba);
             let shape  = shape.fill(color);
             shape.into()
         }
     }
 }
 
+/// The highlight of the port which is neither compatible nor incompatible with the dragged edge.
+/// It is not fully transparent, so the port still receives the mouse events.
+fn neutral_highlight() -> Vector4<f32> {
+    Vector4::new(1.0,1.0,1.0,0.00001)
+}
+
+fn highlight(color:color::Lcha) -> Vector4<f32> {
+    let color = color::Rgba::from(color);
+    Vector4::new(color.red,color.green,color.blue,color.alpha)
+}
+
 pub fn sort_hack(scene:&Scene) {
     let logger = Logger::new("hack");
     component::ShapeView::<shape::Shape>::new(&logger,scene);
@@ -64,8 +77,10 @@ pub struct Events {
     pub stop_edit_mode  : frp::Source,
     pub width           : frp::Stream<f32>,
     pub expression      : frp::Stream<Text>,
+    pub value_set       : frp::Stream<(span_tree::Crumbs,String)>,
     editing             : frp::nodes::Sampler<bool>,
     press_source        : frp::Source<span_tree::Crumbs>,
+    value_set_source    : frp::Source<(span_tree::Crumbs,String)>,
     hover_source        : frp::Source<Option<span_tree::Crumbs>>,
     cursor_style_source : frp::Any<cursor::Style>,
 }
@@ -122,16 +137,19 @@ impl From<&Expression> for Expression {
 
 #[derive(Clone,CloneRef,Debug)]
 pub struct Manager {
-    logger         : Logger,
-    display_object : display::object::Instance,
-    app            : Application,
-    expression     : Rc<RefCell<Expression>>,
-    label          : text::Area,
-    ports          : Rc<RefCell<Vec<component::ShapeView<shape::Shape>>>>,
-    width          : Rc<Cell<f32>>,
-    port_networks  : Rc<RefCell<Vec<frp::Network>>>,
-    type_color_map : TypeColorMap,
-    pub frp        : Events,
+    logger             : Logger,
+    display_object     : display::object::Instance,
+    app                : Application,
+    expression         : Rc<RefCell<Expression>>,
+    label              : text::Area,
+    ports              : Rc<RefCell<Vec<component::ShapeView<shape::Shape>>>>,
+    port_crumbs        : Rc<RefCell<Vec<span_tree::Crumbs>>>,
+    width              : Rc<Cell<f32>>,
+    port_networks      : Rc<RefCell<Vec<frp::Network>>>,
+    widget_definitions : Rc<RefCell<Vec<widget::Definition>>>,
+    widgets            : Rc<RefCell<Vec<widget::Widget>>>,
+    type_color_map     : TypeColorMap,
+    pub frp            : Events,
 }
 
 impl Manager {
@@ -143,11 +161,13 @@ impl Manager {
         let type_color_map = default();
         let label          = app.new_view::<text::Area>();
         let ports          = default();
+        let port_crumbs    = default();
 
         frp::new_network! { network
             cursor_style_source <- any_mut::<cursor::Style>();
             press_source        <- source::<span_tree::Crumbs>();
             hover_source        <- source::<Option<span_tree::Crumbs>>();
+            value_set_source    <- source::<(span_tree::Crumbs,String)>();
             start_edit_mode     <- source();
             stop_edit_mode      <- source();
             editing             <- label.active.sampler();
@@ -170,9 +190,10 @@ impl Manager {
         let cursor_style   = (&cursor_style_source).into();
         let press          = (&press_source).into();
         let hover          = (&hover_source).into();
+        let value_set      = (&value_set_source).into();
         let frp            = Events
             {network,cursor_style,press,hover,cursor_style_source,press_source,hover_source
-            ,start_edit_mode,stop_edit_mode,width,expression,editing};
+            ,start_edit_mode,stop_edit_mode,width,expression,editing,value_set,value_set_source};
 
         label.mod_position(|t| t.y += 6.0);
 
@@ -188,10 +209,13 @@ impl Manager {
         label.set_default_text_size(text::Size(12.0));
         label.remove_all_cursors();
 
-        let expression = default();
-        let width      = default();
+        let expression         = default();
+        let width              = default();
+        let widget_definitions = default();
+        let widgets            = default();
 
-        Self {logger,display_object,frp,label,ports,width,app,expression,port_networks,type_color_map}
+        Self {logger,display_object,frp,label,ports,port_crumbs,width,app,expression,port_networks
+             ,widget_definitions,widgets,type_color_map}
     }
 
     fn scene(&self) -> &Scene {
@@ -210,13 +234,14 @@ impl Manager {
         }
 
 
-        let glyph_width = 7.224_609_4; // FIXME hardcoded literal
+        let glyph_width = node::GLYPH_WIDTH;
         let width       = expression.code.len() as f32 * glyph_width;
         self.width.set(width);
 
 
         let mut to_visit      = vec![expression.input_span_tree.root_ref()];
         let mut ports         = vec![];
+        let mut port_crumbs   = vec![];
         let mut port_networks = vec![];
 
         loop {
@@ -231,12 +256,13 @@ impl Manager {
                         let port     = component::ShapeView::<shape::Shape>::new(&logger,self.scene());
                         let type_map = &self.type_color_map;
 
-                        let unit        = 7.224_609_4;
+                        let unit        = node::GLYPH_WIDTH;
                         let width       = unit * span.size.value as f32;
                         let width2      = width + 8.0;
                         let node_height = 28.0;
                         let height      = 18.0;
                         port.shape.sprite.size.set(Vector2::new(width2,node_height));
+                        port.shape.highlight_rgba.set(neutral_highlight());
                         let x = width/2.0 + unit * span.index.value as f32;
                         port.mod_position(|t| t.x = x);
                         self.add_child(&port);
@@ -277,6 +303,7 @@ impl Manager {
                             eval_ port.events.mouse_out  (hover_source.emit(&None));
                         }
                         ports.push(port);
+                        port_crumbs.push(crumbs);
                         port_networks.push(port_network);
                     }
 
@@ -287,14 +314,80 @@ impl Manager {
 
         *self.expression.borrow_mut()    = expression;
         *self.ports.borrow_mut()         = ports;
+        *self.port_crumbs.borrow_mut()   = port_crumbs;
         *self.port_networks.borrow_mut() = port_networks;
+        self.update_widgets();
+    }
+
+    /// Set the widgets displayed on the input ports. The widgets are kept when the expression
+    /// changes, until the new definitions are given.
+    pub(crate) fn set_widgets(&self, definitions:Vec<widget::Definition>) {
+        *self.widget_definitions.borrow_mut() = definitions;
+        self.update_widgets();
+    }
+
+    /// Recreate the widgets for the current expression. The widgets are placed below their ports,
+    /// but never overlap each other.
+    fn update_widgets(&self) {
+        let expression  = self.expression.borrow();
+        let definitions = self.widget_definitions.borrow();
+        let unit        = node::GLYPH_WIDTH;
+        let y           = -node::NODE_HEIGHT/2.0 - widget::OFFSET - widget::HEIGHT/2.0;
+        let mut next_x  = 0.0;
+        let mut widgets = vec![];
+        for definition in definitions.iter() {
+            let span_tree = &expression.input_span_tree;
+            let port      = span_tree.root_ref().get_descendant(&definition.crumbs).ok();
+            let code      = port.and_then(|port| {
+                let span  = port.span();
+                let start = span.index.value;
+                let code  = expression.code.get(start..start + span.size.value)?;
+                Some((code,span))
+            });
+            if let Some((code,span)) = code {
+                let width  = unit * span.size.value as f32;
+                let x      = (unit * span.index.value as f32).max(next_x);
+                let widget = widget::Widget::new(&self.app,&definition.kind,code,width);
+                widget.mod_position(|t| {
+                    t.x = x;
+                    t.y = y;
+                });
+                self.add_child(&widget);
+                next_x = x + widget.width() + widget::GAP;
+
+                let network          = &widget.frp_network;
+                let crumbs           = definition.crumbs.clone();
+                let value_set_source = &self.frp.value_set_source;
+                frp::extend! { network
+                    eval widget.frp.value_set ((value)
+                        value_set_source.emit(&(crumbs.clone(),value.clone()))
+                    );
+                }
+                widgets.push(widget);
+            }
+        }
+        *self.widgets.borrow_mut() = widgets;
+    }
+
+    /// Check if the expression or any of the widgets is being edited.
+    pub fn is_editing(&self) -> bool {
+        let widget_edited = self.widgets.borrow().iter().any(|widget| widget.is_editing());
+        self.frp.editing.value() || widget_edited
+    }
+
+    /// Stop editing the values in widgets, committing the edited ones.
+    pub(crate) fn stop_widget_editing(&self) {
+        let widgets = self.widgets.borrow().clone();
+        for widget in widgets {
+            widget.frp.input.stop_editing.emit(());
+        }
     }
 
     pub fn get_port_offset(&self, crumbs:&[span_tree::Crumb]) -> Option<Vector2<f32>> {
         let span_tree = &self.expression.borrow().input_span_tree;
         span_tree.root_ref().get_descendant(crumbs).map(|node|{
             let span  = node.span();
-            let unit  = 7.224_609_4;
+            let unit  = node::GLYPH_WIDTH;
             let width = unit * span.size.value as f32;
             let x     = width/2.0 + unit * span.index.value as f32;
             Vector2::new(x + node::TEXT_OFF,node::NODE_HEIGHT/2.0) // FIXME
@@ -308,6 +401,42 @@ impl Manager {
         self.type_color_map.type_color(ast_id, styles)
     }
 
+    /// Return the computed type of the port's value, if known.
+    pub fn get_port_type(&self, crumbs:&[span_tree::Crumb]) -> Option<Type> {
+        let ast_id = get_id_for_crumbs(&self.expression.borrow().input_span_tree,&crumbs)?;
+        self.type_color_map.type_of(ast_id)
+    }
+
+    /// Return the type expected by the port, if known.
+    pub fn get_port_expected_type(&self, crumbs:&[span_tree::Crumb]) -> Option<Type> {
+        let span_tree = &self.expression.borrow().input_span_tree;
+        let port      = span_tree.root_ref().get_descendant(crumbs).ok()?;
+        let typename  = port.parameter_info.as_ref()?.typename.clone()?;
+        Some(Type::from(typename))
+    }
+
+    /// Highlight the ports accepting the values of given type, and dim the ones whose expected
+    /// type is incompatible with it. The ports of unknown expected type are left intact, as are
+    /// all ports when `None` is given.
+    pub fn set_compatibility_highlight(&self, source_type:Option<&Type>) {
+        // FIXME : StyleWatch is unsuitable here, as it was designed as an internal tool for shape system (#795)
+        let styles       = StyleWatch::new(&self.app.display.scene().style_sheet);
+        let compatible   = theme::vars::graph_editor::node::port::compatible::color;
+        let compatible   = styles.get_color(compatible);
+        let incompatible = theme::vars::graph_editor::node::port::incompatible::color;
+        let incompatible = styles.get_color(incompatible);
+        let ports        = self.ports.borrow();
+        let port_crumbs  = self.port_crumbs.borrow();
+        for (port,crumbs) in ports.iter().zip(port_crumbs.iter()) {
+            let color = source_type.and_then(|source| {
+                let expected      = self.get_port_expected_type(crumbs)?;
+                let is_compatible = source.is_compatible_with(&expected);
+                Some(if is_compatible { compatible } else { incompatible })
+            });
+            port.shape.highlight_rgba.set(color.map_or_else(neutral_highlight,highlight));
+        }
+    }
+
     pub fn width(&self) -> f32 {
         self.width.get()
     }
@@ -774,6 +774,12 @@ impl OutputPorts {
         self.type_color_map.type_color(ast_id, styles)
     }
 
+    /// Return the type of the port indicated by the given `Crumb`.
+    pub fn get_port_type(&self, crumbs:&[span_tree::Crumb]) -> Option<Type> {
+        let ast_id = get_id_for_crumbs(&self.pattern_span_tree.borrow(),&crumbs)?;
+        self.type_color_map.type_of(ast_id)
+    }
+
     /// Set the type information for the given `ast::Id`.
     pub fn set_pattern_type(&self, id:ast::Id, maybe_type:Option<Type>) {
         self.type_color_map.update_entry(id,maybe_type);
@@ -0,0 +1,414 @@
+//! Definition of the value-editing widgets displayed below the node's input ports.
+//!
+//! The widget shows the current value of the port and lets user change it: numbers are changed by
+//! dragging the mouse horizontally, texts are edited in a text field, booleans are toggled with a
+//! click, and the values of other types are chosen from a dropdown list. The widget does not
+//! change the expression by itself, it only emits the code of the new value.
+
+use crate::prelude::*;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::data::color;
+use ensogl::display::shape::*;
+use ensogl::display::traits::*;
+use ensogl::display;
+use ensogl::gui::component;
+use ensogl_gui_list_view as list_view;
+use ensogl_gui_list_view::ListView;
+use ensogl_text as text;
+use ensogl_theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The height of the widget.
+pub const HEIGHT : f32 = 18.0;
+/// The distance between the node's bottom edge and its widgets.
+pub const OFFSET : f32 = 6.0;
+/// The minimal width of the widget.
+pub const MIN_WIDTH : f32 = 30.0;
+/// The horizontal padding between the widget's border and its label.
+pub const PADDING : f32 = 5.0;
+/// The horizontal gap between two neighbouring widgets.
+pub const GAP : f32 = 4.0;
+/// The size of the widget's label text.
+pub const TEXT_SIZE : f32 = 12.0;
+/// The distance the mouse must be dragged to change the number by a single step.
+pub const DRAG_STEP : f32 = 4.0;
+/// The size of the dropdown list.
+pub const DROPDOWN_SIZE : (f32,f32) = (160.0,120.0);
+
+const TRUE_CODE  : &str = "True";
+const FALSE_CODE : &str = "False";
+
+
+
+// ============
+// === Kind ===
+// ============
+
+/// The kind of widget displayed on the port.
+#[derive(Clone,Debug)]
+pub enum Kind {
+    /// The number changed by dragging.
+    Number,
+    /// The text field.
+    Text,
+    /// The checkbox toggling between `True` and `False`.
+    Checkbox,
+    /// The list of values to choose from, each given as a code to put into the port.
+    Dropdown {
+        #[allow(missing_docs)]
+        entries : Rc<Vec<String>>
+    },
+}
+
+/// The widget to be displayed on the port identified by the crumbs.
+#[derive(Clone,Debug)]
+#[allow(missing_docs)]
+pub struct Definition {
+    pub crumbs : span_tree::Crumbs,
+    pub kind   : Kind,
+}
+
+
+
+// ==================
+// === Value Code ===
+// ==================
+
+/// Remove the given delimiters surrounding the code, if present.
+fn strip_delimiters(code:&str, open:char, close:char) -> &str {
+    let is_delimited = code.len() >= 2 && code.starts_with(open) && code.ends_with(close);
+    if is_delimited { &code[1..code.len()-1] } else { code }
+}
+
+/// Read the number from the port's code. Negative numbers may be put in parentheses.
+fn parse_number(code:&str) -> Option<f64> {
+    strip_delimiters(code.trim(),'(',')').trim().parse().ok()
+}
+
+/// The number of decimal places in the number's code.
+fn decimal_places(code:&str) -> usize {
+    code.split('.').nth(1).map_or(0, |fraction| {
+        fraction.chars().take_while(|c| c.is_ascii_digit()).count()
+    })
+}
+
+/// The code of the number with the given decimal places. Negative numbers are put in parentheses,
+/// so they are not taken for subtraction in the node's expression.
+fn number_code(value:f64, decimal_places:usize) -> String {
+    let code = format!("{:.*}",decimal_places,value);
+    if value < 0.0 { format!("({})",code) } else { code }
+}
+
+/// Read the text from the text literal code. This is an inverse of `text_code`.
+fn text_value(code:&str) -> String {
+    let mut value = String::new();
+    let mut chars = strip_delimiters(code.trim(),'"','"').chars().peekable();
+    while let Some(character) = chars.next() {
+        let is_escaped = |next:&char| *next == '\\' || *next == '"';
+        let is_escape  = character == '\\' && chars.peek().map_or(false,is_escaped);
+        if is_escape {
+            value.extend(chars.next())
+        } else {
+            value.push(character)
+        }
+    }
+    value
+}
+
+/// The code of the text literal with the given content. The quotes and backslashes are escaped.
+fn text_code(text:&str) -> String {
+    format!("\"{}\"",text.replace('\\',"\\\\").replace('"',"\\\""))
+}
+
+
+
+// =============
+// === Shape ===
+// =============
+
+/// Widget background shape definition.
+pub mod shape {
+    use super::*;
+
+    ensogl::define_shape_system! {
+        (style:Style) {
+            let bg_color = ensogl_theme::vars::graph_editor::node::widget::background::color;
+            let bg_color = style.get_color(bg_color);
+            let width    : Var<Pixels> = "input_size.x".into();
+            let height   : Var<Pixels> = "input_size.y".into();
+            let radius   = HEIGHT / 2.0;
+            let shape    = Rect((&width,&height)).corners_radius(radius.px());
+            let shape    = shape.fill(color::Rgba::from(bg_color));
+            shape.into()
+        }
+    }
+}
+
+
+
+// ======================
+// === Dropdown Entry ===
+// ======================
+
+#[derive(Clone,Debug)]
+struct EntryProvider(Rc<Vec<String>>);
+
+impl list_view::entry::ModelProvider for EntryProvider {
+    fn entry_count(&self) -> usize {
+        self.0.len()
+    }
+
+    fn get(&self, id:list_view::entry::Id) -> Option<list_view::entry::Model> {
+        self.0.get(id).map(|entry| list_view::entry::Model::new(entry.as_str()))
+    }
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl_text::define_endpoints! {
+    Input {
+        stop_editing (),
+    }
+    Output {
+        value_set (String),
+    }
+}
+
+
+
+// ==============
+// === Widget ===
+// ==============
+
+/// Internal data of `Widget`.
+#[derive(Clone,CloneRef,Debug)]
+#[allow(missing_docs)]
+pub struct WidgetModel {
+    pub display_object : display::object::Instance,
+    pub logger         : Logger,
+    pub frp            : FrpEndpoints,
+    pub background     : component::ShapeView<shape::Shape>,
+    pub label          : text::Area,
+    pub dropdown       : ListView,
+    code               : Rc<RefCell<String>>,
+    text               : Rc<RefCell<String>>,
+    min_width          : Rc<Cell<f32>>,
+    width              : Rc<Cell<f32>>,
+    editing            : Rc<Cell<bool>>,
+}
+
+impl WidgetModel {
+    /// Constructor.
+    pub fn new(app:&Application, network:&frp::Network, code:&str, width:f32) -> Self {
+        let scene          = app.display.scene();
+        let logger         = Logger::new("widget");
+        let display_object = display::object::Instance::new(&logger);
+        let background     = component::ShapeView::<shape::Shape>::new(&logger,scene);
+        let label          = app.new_view::<text::Area>();
+        let dropdown       = app.new_view::<ListView>();
+        let input          = FrpInputs::new(&network);
+        let frp            = FrpEndpoints::new(&network,input);
+        let code           = Rc::new(RefCell::new(code.to_string()));
+        let text           = default();
+        let min_width      = Rc::new(Cell::new(width.max(MIN_WIDTH)));
+        let width          = Rc::new(Cell::new(min_width.get()));
+        let editing        = default();
+        display_object.add_child(&background);
+        display_object.add_child(&label);
+        label.mod_position(|t| {
+            t.x = PADDING;
+            t.y = TEXT_SIZE / 2.0;
+        });
+        dropdown.resize(Vector2(DROPDOWN_SIZE.0,DROPDOWN_SIZE.1));
+        dropdown.mod_position(|t| {
+            t.x = DROPDOWN_SIZE.0 / 2.0;
+            t.y = -(HEIGHT + DROPDOWN_SIZE.1) / 2.0;
+        });
+
+        // FIXME : StyleWatch is unsuitable here, as it was designed as an internal tool for shape system (#795)
+        let styles     = StyleWatch::new(&scene.style_sheet);
+        let text_color = styles.get_color(ensogl_theme::vars::graph_editor::node::text::color);
+        label.set_default_color(color::Rgba::from(text_color));
+        label.set_default_text_size(text::Size(TEXT_SIZE));
+        label.remove_all_cursors();
+        Self {display_object,logger,frp,background,label,dropdown,code,text,min_width,width
+             ,editing}
+    }
+
+    /// The width of the widget.
+    pub fn width(&self) -> f32 {
+        self.width.get()
+    }
+
+    fn set_label_width(&self, label_width:f32) {
+        let width = self.min_width.get().max(label_width + PADDING * 2.0);
+        self.width.set(width);
+        self.background.shape.sprite.size.set(Vector2::new(width,HEIGHT));
+        self.background.mod_position(|t| t.x = width / 2.0);
+    }
+
+    fn set_label(&self, label:impl Into<String>) {
+        self.label.set_content(label.into());
+    }
+
+    /// The code of the number dragged by the given distance. The step depends on the number of
+    /// decimal places of the original code, which are kept.
+    fn dragged_number(&self, distance:f32) -> Option<String> {
+        let code   = self.code.borrow();
+        let value  = parse_number(&code)?;
+        let places = decimal_places(&code);
+        let steps  = (distance / DRAG_STEP).round() as f64;
+        let step   = 10.0_f64.powi(-(places as i32));
+        Some(number_code(value + steps * step,places))
+    }
+
+    fn toggled_boolean(&self) -> String {
+        let value = self.code.borrow().trim() == TRUE_CODE;
+        if value { FALSE_CODE.to_string() } else { TRUE_CODE.to_string() }
+    }
+
+    /// Check if the widget is being edited: its text field is active or its dropdown is shown.
+    pub fn is_editing(&self) -> bool {
+        self.editing.get()
+    }
+
+    fn start_text_editing(&self) {
+        if !self.editing.replace(true) {
+            self.label.set_active_on();
+            self.label.set_cursor_at_end();
+        }
+    }
+
+    /// Stop editing the text. Returns the code of the edited text if it was being edited.
+    fn stop_text_editing(&self) -> Option<String> {
+        self.editing.replace(false).as_some_from(|| {
+            self.label.set_active_off();
+            self.label.remove_all_cursors();
+            text_code(&self.text.borrow())
+        })
+    }
+
+    fn toggle_dropdown(&self) {
+        if self.editing.replace(!self.editing.get()) {
+            self.display_object.remove_child(&self.dropdown);
+        } else {
+            self.display_object.add_child(&self.dropdown);
+        }
+    }
+
+    fn hide_dropdown(&self) {
+        if self.editing.replace(false) {
+            self.display_object.remove_child(&self.dropdown);
+        }
+    }
+
+    fn dropdown_entry(entries:&[String], id:Option<list_view::entry::Id>) -> Option<String> {
+        id.and_then(|id| entries.get(id).cloned())
+    }
+}
+
+impl display::Object for WidgetModel {
+    fn display_object(&self) -> &display::object::Instance {
+        &self.display_object
+    }
+}
+
+/// A widget editing the value of a single input port.
+///
+/// The widget is positioned by the left edge of its background. It is created for the current code
+/// of the port, and emits the new code with `value_set` output; the widget is expected to be
+/// recreated once the node's expression is updated.
+#[derive(Clone,CloneRef,Debug)]
+#[allow(missing_docs)]
+pub struct Widget {
+    pub model       : Rc<WidgetModel>,
+    pub frp_network : frp::Network,
+}
+
+impl Deref for Widget {
+    type Target = WidgetModel;
+    fn deref(&self) -> &Self::Target {
+        &self.model
+    }
+}
+
+impl Widget {
+    /// Constructor.
+    pub fn new(app:&Application, kind:&Kind, code:&str, width:f32) -> Self {
+        let frp_network = frp::Network::new();
+        let model       = Rc::new(WidgetModel::new(app,&frp_network,code,width));
+        let mouse       = &app.display.scene().mouse.frp;
+        let inputs      = &model.frp.input;
+        let output      = &model.frp.source;
+        let label       = &model.label;
+        let press       = &model.background.events.mouse_down;
+
+        frp::extend! { frp_network
+            eval label.changed ((text) *model.text.borrow_mut() = text.into());
+            eval label.width   ((width) model.set_label_width(*width));
+        }
+
+        match kind {
+            Kind::Number => { frp::extend! { frp_network
+                is_dragging   <- bool(&mouse.up,press);
+                was_dragging  <- is_dragging.previous();
+                drag_end      <- mouse.up.gate(&was_dragging);
+                drag_start    <- mouse.position.sample(press);
+                drag_position <- mouse.position.gate(&is_dragging);
+                distance      <- drag_position.map2(&drag_start,|pos,start| pos.x - start.x);
+                dragged       <= distance.map(f!((distance) model.dragged_number(*distance)));
+                eval dragged ((code) model.set_label(code));
+                output.value_set <+ dragged.sample(&drag_end);
+            }},
+            Kind::Text => { frp::extend! { frp_network
+                eval_ press (model.start_text_editing());
+                edited <= inputs.stop_editing.map(f_!(model.stop_text_editing()));
+                output.value_set <+ edited;
+            }},
+            Kind::Checkbox => { frp::extend! { frp_network
+                output.value_set <+ press.map(f_!(model.toggled_boolean()));
+            }},
+            Kind::Dropdown {entries} => {
+                let entries  = entries.clone_ref();
+                let dropdown = &model.dropdown;
+                dropdown.set_entries(list_view::entry::AnyModelProvider::from(
+                    EntryProvider(entries.clone_ref())
+                ));
+                frp::extend! { frp_network
+                    eval_ press               (model.toggle_dropdown());
+                    eval_ inputs.stop_editing (model.hide_dropdown());
+                    chosen <= dropdown.chosen_entry.map(move |id| {
+                        WidgetModel::dropdown_entry(&entries,*id)
+                    });
+                    eval_ chosen (model.hide_dropdown());
+                    output.value_set <+ chosen;
+                }
+            }
+        }
+
+        let label = match kind {
+            Kind::Text => text_value(code),
+            _          => code.trim().to_string(),
+        };
+        model.set_label(label);
+        model.set_label_width(0.0);
+        Self {model,frp_network}
+    }
+}
+
+impl display::Object for Widget {
+    fn display_object(&self) -> &display::object::Instance {
+        &self.display_object
+    }
+}
@@ -0,0 +1,206 @@
+//! Definition of the Note component: a free-floating annotation placed in the graph.
+
+use crate::prelude::*;
+
+use enso_frp as frp;
+use ensogl::application::Application;
+use ensogl::data::color;
+use ensogl::display::shape::*;
+use ensogl::display::traits::*;
+use ensogl::display;
+use ensogl::gui::component;
+use ensogl_text as text;
+use ensogl_text::component::area::LINE_HEIGHT;
+use ensogl_theme;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+/// The distance between the note's border and its text.
+pub const PADDING : f32 = 10.0;
+/// The corner radius of note's background.
+pub const CORNER_RADIUS : f32 = 6.0;
+/// The minimal width of the note's background, used when the note is (almost) empty.
+pub const MIN_WIDTH : f32 = 80.0;
+/// The size of note's text.
+pub const TEXT_SIZE : f32 = 12.0;
+
+
+
+// =============
+// === Shape ===
+// =============
+
+/// Note background shape definition.
+pub mod shape {
+    use super::*;
+
+    ensogl::define_shape_system! {
+        (style:Style) {
+            let bg_color = style.get_color(ensogl_theme::vars::graph_editor::note::background::color);
+            let width    : Var<Pixels> = "input_size.x".into();
+            let height   : Var<Pixels> = "input_size.y".into();
+            let shape    = Rect((&width,&height)).corners_radius(CORNER_RADIUS.px());
+            let shape    = shape.fill(color::Rgba::from(bg_color));
+            shape.into()
+        }
+    }
+}
+
+
+
+// ===========
+// === Frp ===
+// ===========
+
+ensogl_text::define_endpoints! {
+    Input {
+        set_text      (String),
+        start_editing (),
+        stop_editing  (),
+    }
+    Output {
+        edited (String),
+    }
+}
+
+
+
+// ============
+// === Note ===
+// ============
+
+/// Internal data of `Note`.
+#[derive(Clone,CloneRef,Debug)]
+#[allow(missing_docs)]
+pub struct NoteModel {
+    pub display_object : display::object::Instance,
+    pub logger         : Logger,
+    pub frp            : FrpEndpoints,
+    pub background     : component::ShapeView<shape::Shape>,
+    pub label          : text::Area,
+    text               : Rc<RefCell<String>>,
+    editing            : Rc<Cell<bool>>,
+}
+
+impl NoteModel {
+    /// Constructor.
+    pub fn new(app:&Application, network:&frp::Network) -> Self {
+        let scene          = app.display.scene();
+        let logger         = Logger::new("note");
+        let display_object = display::object::Instance::new(&logger);
+        let background     = component::ShapeView::<shape::Shape>::new(&logger,scene);
+        let label          = app.new_view::<text::Area>();
+        let input          = FrpInputs::new(&network);
+        let frp            = FrpEndpoints::new(&network,input);
+        let text           = default();
+        let editing        = default();
+        display_object.add_child(&background);
+        display_object.add_child(&label);
+        label.mod_position(|t| {
+            t.x = PADDING;
+            t.y = -PADDING;
+        });
+
+        // FIXME : StyleWatch is unsuitable here, as it was designed as an internal tool for shape system (#795)
+        let styles     = StyleWatch::new(&scene.style_sheet);
+        let text_color = styles.get_color(ensogl_theme::vars::graph_editor::note::text::color);
+        label.set_default_color(color::Rgba::from(text_color));
+        label.set_default_text_size(text::Size(TEXT_SIZE));
+        label.remove_all_cursors();
+        Self {display_object,logger,frp,background,label,text,editing}
+    }
+
+    /// The size of note's background for the given width of text.
+    fn size(&self, text_width:f32) -> Vector2<f32> {
+        let lines  = self.text.borrow().lines().count().max(1);
+        let width  = (text_width + PADDING * 2.0).max(MIN_WIDTH);
+        let height = lines as f32 * LINE_HEIGHT + PADDING * 2.0;
+        Vector2::new(width,height)
+    }
+
+    fn set_text_width(&self, text_width:f32) {
+        let size = self.size(text_width);
+        self.background.shape.sprite.size.set(size);
+        self.background.mod_position(|t| {
+            t.x = size.x / 2.0;
+            t.y = -size.y / 2.0;
+        });
+    }
+
+    fn set_text(&self, text:&str) {
+        if *self.text.borrow() != text {
+            self.label.set_content(text.to_string());
+        }
+    }
+
+    /// Check if the note's text is being edited.
+    pub fn is_editing(&self) -> bool {
+        self.editing.get()
+    }
+
+    fn start_editing(&self) {
+        if !self.editing.replace(true) {
+            self.label.set_active_on();
+            self.label.set_cursor_at_end();
+        }
+    }
+
+    /// Stop editing the note. Returns the edited text if the note was being edited.
+    fn stop_editing(&self) -> Option<String> {
+        self.editing.replace(false).as_some_from(|| {
+            self.label.set_active_off();
+            self.label.remove_all_cursors();
+            self.text.borrow().clone()
+        })
+    }
+}
+
+/// A note displayed in the graph: a text on a colored background.
+///
+/// The note is positioned by its top left corner. Its text can be edited between `start_editing`
+/// and `stop_editing` inputs, and the edited text is emitted once the editing stops.
+#[derive(Clone,CloneRef,Debug)]
+#[allow(missing_docs)]
+pub struct Note {
+    pub model       : Rc<NoteModel>,
+    pub frp_network : frp::Network,
+}
+
+impl Deref for Note {
+    type Target = NoteModel;
+    fn deref(&self) -> &Self::Target {
+        &self.model
+    }
+}
+
+impl Note {
+    /// Constructor.
+    pub fn new(app:&Application) -> Self {
+        let frp_network = frp::Network::new();
+        let model       = Rc::new(NoteModel::new(app,&frp_network));
+        let inputs      = &model.frp.input;
+        let label       = &model.label;
+
+        frp::extend! { frp_network
+            eval label.changed ((text) *model.text.borrow_mut() = text.into());
+            eval label.width   ((width) model.set_text_width(*width));
+
+            eval  inputs.set_text      ((text) model.set_text(text));
+            eval_ inputs.start_editing (model.start_editing());
+            edited <= inputs.stop_editing.map(f_!(model.stop_editing()));
+            model.frp.source.edited <+ edited;
+        }
+        model.set_text_width(0.0);
+        Self {model,frp_network}
+    }
+}
+
+impl display::Object for Note {
+    fn display_object(&self) -> &display::object::Instance {
+        &self.display_object
+    }
+}
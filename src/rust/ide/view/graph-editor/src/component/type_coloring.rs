@@ -58,6 +58,11 @@ impl TypeColorMap {
         })
     }
 
+    /// Return the type of the `ast_id`. If no type information is available, returns `None`.
+    pub fn type_of(&self, ast_id:ast::Id) -> Option<Type> {
+        self.data.get_cloned(&ast_id)
+    }
+
     /// Set the type for the given `ast::Id`. Discards the type, if `None` is given as value.
     pub fn update_entry(&self, key:ast::Id, value:Option<Type>) {
         match value {
@@ -44,14 +44,26 @@ impl Registry {
         registry
     }
 
-    /// Register a new `visualization::Definition`.
+    /// Register a new `visualization::Definition`. The definition previously registered under the
+    /// same path is replaced.
     pub fn add(&self, class:impl Into<visualization::Definition>) {
         let class = class.into();
         let sig   = &class.signature;
+        self.remove(&sig.path);
         self.type_map.borrow_mut().entry(sig.input_type.clone()).or_default().push(class.clone_ref());
         self.path_map.borrow_mut().entry(sig.path.clone()).insert(class);
     }
 
+    /// Remove the `visualization::Definition` registered under the given path.
+    pub fn remove(&self, path:&visualization::Path) {
+        if let Some(class) = self.path_map.borrow_mut().remove(path) {
+            let input_type = &class.signature.input_type;
+            if let Some(classes) = self.type_map.borrow_mut().get_mut(input_type) {
+                classes.retain(|class| &class.signature.path != path);
+            }
+        }
+    }
+
     /// Register a new `visualization::java_script::Definition`. If creating the class fails, it
     /// will not be added an warning is emitted.
     pub fn try_add_java_script(&self, class:impl Into<visualization::java_script::FallibleDefinition>) {
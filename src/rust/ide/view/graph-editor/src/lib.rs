@@ -314,6 +314,30 @@ ensogl::def_command_api! { Commands
     remove_all_nodes,
     /// Remove all nodes from the graph.
     collapse_selected_nodes,
+    /// Replace the last selected node with the body of the method it calls.
+    expand_selected_node,
+    /// Copy all selected nodes to the clipboard.
+    copy_selected_nodes,
+    /// Copy all selected nodes to the clipboard and remove them from the graph.
+    cut_selected_nodes,
+    /// Paste the nodes from the clipboard at the mouse cursor position.
+    paste_nodes,
+    /// Add a new note and place it in the origin of the workspace.
+    add_note,
+    /// Add a new note at the mouse cursor position and start editing it.
+    add_note_at_cursor,
+    /// Start editing the comment of the last selected node.
+    edit_selected_node_comment,
+    /// Group the selected nodes, drawing a titled frame around them.
+    group_selected_nodes,
+    /// Collapse the selected group, hiding its nodes, or expand it if it is collapsed.
+    toggle_selected_group_collapsed,
+    /// Remove the selected group. The grouped nodes are not affected.
+    remove_selected_group,
+    /// Arrange the selected nodes with the automatic layout.
+    arrange_selected_nodes,
+    /// Arrange all nodes in the graph with the automatic layout.
+    arrange_all_nodes,
     /// Toggle the visibility of the selected visualizations.
     toggle_visualization_visibility,
     /// Simulates a visualization open press event. In case the event will be shortly followed by `release_visualization_visibility`, the visualization will be shown permanently. In other case, it will be disabled as soon as the `release_visualization_visibility` is emitted.
@@ -374,6 +398,11 @@ ensogl::def_command_api! { Commands
 
     /// Cancel the operation being currently performed. Often mapped to the escape key.
     cancel,
+
+    /// Revert the last change made in the graph.
+    undo,
+    /// Reapply the last change reverted by `undo`.
+    redo,
 }
 
 
@@ -411,7 +440,19 @@ pub struct FrpInputs {
     pub cycle_visualization          : frp::Source<NodeId>,
     pub set_visualization            : frp::Source<(NodeId,Option<visualization::Path>)>,
     pub register_visualization       : frp::Source<Option<visualization::Definition>>,
+    pub unregister_visualization     : frp::Source<visualization::Path>,
     pub set_visualization_data       : frp::Source<(NodeId,visualization::Data)>,
+    pub set_node_comment             : frp::Source<(NodeId,Option<String>)>,
+    pub set_node_widgets             : frp::Source<(NodeId,Vec<node::port::widget::Definition>)>,
+    pub set_note_text                : frp::Source<(NoteId,String)>,
+    pub set_note_position            : frp::Source<(NoteId,Vector2)>,
+    pub remove_note                  : frp::Source<NoteId>,
+    pub add_group                    : frp::Source,
+    pub set_group_nodes              : frp::Source<(GroupId,Vec<NodeId>)>,
+    pub set_group_title              : frp::Source<(GroupId,String)>,
+    pub set_group_color              : frp::Source<(GroupId,usize)>,
+    pub set_group_collapsed          : frp::Source<(GroupId,bool)>,
+    pub remove_group                 : frp::Source<GroupId>,
 
     hover_node_input           : frp::Source<Option<EdgeTarget>>,
     hover_node_output          : frp::Source<Option<EdgeTarget>>,
@@ -452,6 +493,18 @@ impl FrpInputs {
             cycle_visualization          <- source();
             set_visualization            <- source();
             register_visualization       <- source();
+            unregister_visualization     <- source();
+            set_node_comment             <- source();
+            set_node_widgets             <- source();
+            set_note_text                <- source();
+            set_note_position            <- source();
+            remove_note                  <- source();
+            add_group                    <- source();
+            set_group_nodes              <- source();
+            set_group_title              <- source();
+            set_group_color              <- source();
+            set_group_collapsed          <- source();
+            remove_group                 <- source();
 
             hover_node_input             <- source();
             hover_node_output            <- source();
@@ -468,10 +521,13 @@ impl FrpInputs {
              ,unset_edge_source,unset_edge_target
              ,set_node_position,set_expression_type,set_method_pointer,select_node,remove_node
              ,edit_node,collapse_nodes,set_node_expression,connect_nodes,deselect_all_nodes
-             ,cycle_visualization,set_visualization,register_visualization
+             ,cycle_visualization,set_visualization,register_visualization,unregister_visualization
              ,some_edge_targets_detached,some_edge_sources_detached,all_edge_targets_attached
              ,hover_node_input,all_edge_sources_attached,hover_node_output,press_node_output
-             ,set_detached_edge_sources,all_edges_attached
+             ,set_detached_edge_sources,all_edges_attached,set_node_comment,set_node_widgets
+             ,set_note_text
+             ,set_note_position,remove_note,add_group,set_group_nodes,set_group_title
+             ,set_group_color,set_group_collapsed,remove_group
         }
     }
 }
@@ -547,6 +603,7 @@ generate_frp_outputs! {
     node_added                : NodeId,
     node_removed              : NodeId,
     nodes_collapsed           : (Vec<NodeId>,NodeId),
+    node_expanded             : NodeId,
     node_selected             : NodeId,
     node_deselected           : NodeId,
     node_position_set         : (NodeId,Vector2),
@@ -556,6 +613,8 @@ generate_frp_outputs! {
     node_exited               : (),
     node_editing_started      : NodeId,
     node_editing_finished     : NodeId,
+    node_comment_edited       : (NodeId,Option<String>),
+    node_port_value_set       : (NodeId,span_tree::Crumbs,String),
 
     edge_added        : EdgeId,
     edge_removed      : EdgeId,
@@ -563,6 +622,7 @@ generate_frp_outputs! {
     edge_target_set   : (EdgeId,EdgeTarget),
     edge_source_unset : EdgeId,
     edge_target_unset : EdgeId,
+    hovered_edge      : Option<EdgeId>,
 
     some_edge_targets_detached : (),
     some_edge_sources_detached : (),
@@ -570,8 +630,9 @@ generate_frp_outputs! {
     all_edge_sources_attached  : (),
     all_edges_attached         : (),
 
-    connection_added   : EdgeId,
-    connection_removed : EdgeId,
+    connection_added         : EdgeId,
+    connection_removed       : EdgeId,
+    connection_type_mismatch : EdgeId,
 
     visualization_enabled           : NodeId,
     visualization_disabled          : NodeId,
@@ -579,6 +640,25 @@ generate_frp_outputs! {
     visualization_set_preprocessor  : (NodeId,data::EnsoCode),
 
     edited_node : Option<NodeId>,
+
+    undo_requested : (),
+    redo_requested : (),
+
+    nodes_copied    : Vec<NodeId>,
+    nodes_cut       : Vec<NodeId>,
+    paste_requested : Vector2,
+    nodes_arranged  : Vec<NodeId>,
+
+    note_added        : NoteId,
+    note_position_set : (NoteId,Vector2),
+    note_edited       : (NoteId,String),
+
+    group_added         : GroupId,
+    nodes_grouped       : Vec<NodeId>,
+    group_moved         : (GroupId,Vector2),
+    group_title_edited  : (GroupId,String),
+    group_collapsed_set : (GroupId,bool),
+    group_removed       : GroupId,
 }
 
 
@@ -598,6 +678,9 @@ pub struct Node {
 #[derive(Clone,CloneRef,Copy,Debug,Default,Display,Eq,From,Hash,Into,PartialEq)]
 pub struct NodeId(pub Id);
 
+#[derive(Clone,CloneRef,Copy,Debug,Default,Display,Eq,From,Hash,Into,PartialEq)]
+pub struct NoteId(pub Id);
+
 impl Node {
     pub fn new(view:component::Node) -> Self {
         let in_edges  = default();
@@ -619,6 +702,41 @@ impl display::Object for Node {
 
 
 
+// =============
+// === Group ===
+// =============
+
+#[derive(Clone,CloneRef,Debug,Shrinkwrap)]
+pub struct Group {
+    #[shrinkwrap(main_field)]
+    pub view      : component::Group,
+    pub nodes     : SharedVec<NodeId>,
+    pub collapsed : Rc<Cell<bool>>,
+}
+
+#[derive(Clone,CloneRef,Copy,Debug,Default,Display,Eq,From,Hash,Into,PartialEq)]
+pub struct GroupId(pub Id);
+
+impl Group {
+    pub fn new(view:component::Group) -> Self {
+        let nodes     = default();
+        let collapsed = default();
+        Self {view,nodes,collapsed}
+    }
+
+    pub fn id(&self) -> GroupId {
+        self.view.id().into()
+    }
+}
+
+impl display::Object for Group {
+    fn display_object(&self) -> &display::object::Instance {
+        &self.view.display_object()
+    }
+}
+
+
+
 // ============
 // === Edge ===
 // ============
@@ -703,6 +821,43 @@ impl From<String> for Type {
     }
 }
 
+/// The type accepting values of every other type.
+const ANY_TYPE : &str = "Any";
+/// The general type of numbers, accepting the values of specific number types.
+const NUMBER_TYPE : &str = "Number";
+/// The specific number types, accepted where the `Number` is expected.
+const SPECIFIC_NUMBER_TYPES : &[&str] = &["Integer","Decimal"];
+
+impl Type {
+    /// The name of the type without the module path.
+    pub fn short_name(&self) -> &str {
+        self.0.rsplit('.').next().unwrap_or(&self.0)
+    }
+
+    /// Check if the values of this type may be passed where the `expected` type is required.
+    ///
+    /// The types are compared by their fully qualified names when both are qualified, and by names
+    /// without the module paths otherwise. The `Any` type is compatible with every type, and the
+    /// specific number types are compatible with `Number`.
+    pub fn is_compatible_with(&self, expected:&Type) -> bool {
+        let name          = self.short_name();
+        let expected_name = expected.short_name();
+        let is_any        = name == ANY_TYPE || expected_name == ANY_TYPE;
+        let is_number     = expected_name == NUMBER_TYPE && SPECIFIC_NUMBER_TYPES.contains(&name);
+        let is_same       = if self.is_qualified() && expected.is_qualified() {
+            self.0 == expected.0
+        } else {
+            name == expected_name
+        };
+        is_any || is_number || is_same
+    }
+
+    /// Check if the type name contains the module path.
+    fn is_qualified(&self) -> bool {
+        self.0.contains('.')
+    }
+}
+
 // =============================
 // === OptionalMethodPointer ===
 // =============================
@@ -942,14 +1097,18 @@ impl<T:frp::Data> TouchNetwork<T> {
 #[derive(Debug,Clone,CloneRef)]
 pub struct TouchState {
     pub nodes      : TouchNetwork::<NodeId>,
+    pub notes      : TouchNetwork::<NoteId>,
+    pub groups     : TouchNetwork::<GroupId>,
     pub background : TouchNetwork::<()>,
 }
 
 impl TouchState {
     pub fn new(network:&frp::Network, mouse:&frp::io::Mouse) -> Self {
         let nodes      = TouchNetwork::<NodeId>::new(&network,mouse);
+        let notes      = TouchNetwork::<NoteId>::new(&network,mouse);
+        let groups     = TouchNetwork::<GroupId>::new(&network,mouse);
         let background = TouchNetwork::<()>::new(&network,mouse);
-        Self {nodes,background}
+        Self {nodes,notes,groups,background}
     }
 }
 
@@ -1000,6 +1159,8 @@ impl GraphEditorModelWithNetwork {
     , output_press   : &frp::Source<EdgeTarget>
     , input_press    : &frp::Source<EdgeTarget>
     , expression_set : &frp::Source<(NodeId,String)>
+    , comment_edited : &frp::Source<(NodeId,Option<String>)>
+    , port_value_set : &frp::Source<(NodeId,span_tree::Crumbs,String)>
     ) -> NodeId {
         let view    = component::Node::new(&self.app);
         let node    = Node::new(view);
@@ -1037,6 +1198,10 @@ impl GraphEditorModelWithNetwork {
             );
 
             eval node.frp.expression((t) expression_set.emit((node_id,t.into())));
+            eval node.frp.comment_edited((c) comment_edited.emit((node_id,c.clone())));
+            eval node.frp.port_value_set(((crumbs,value))
+                port_value_set.emit((node_id,crumbs.clone(),value.clone()))
+            );
         }
 
         self.nodes.insert(node_id,node);
@@ -1044,6 +1209,41 @@ impl GraphEditorModelWithNetwork {
         node_id
     }
 
+    fn new_note(&self, note_edited:&frp::Source<(NoteId,String)>) -> NoteId {
+        let note    = component::Note::new(&self.app);
+        let note_id = NoteId(note.id());
+        self.add_child(&note);
+
+        let touch = &self.touch_state;
+
+        frp::new_bridge_network! { [self.network, note.background.events.network]
+            eval_ note.background.events.mouse_down(touch.notes.down.emit(note_id));
+            eval note.frp.edited((text) note_edited.emit((note_id,text.clone())));
+        }
+
+        self.notes.insert(note_id,note);
+
+        note_id
+    }
+
+    fn new_group(&self, title_edited:&frp::Source<(GroupId,String)>) -> GroupId {
+        let view     = component::Group::new(&self.app);
+        let group    = Group::new(view);
+        let group_id = group.id();
+        self.add_child(&group);
+
+        let touch = &self.touch_state;
+
+        frp::new_bridge_network! { [self.network, group.frame.events.network]
+            eval_ group.frame.events.mouse_down(touch.groups.down.emit(group_id));
+            eval group.frp.title_edited((title) title_edited.emit((group_id,title.clone())));
+        }
+
+        self.groups.insert(group_id,group);
+
+        group_id
+    }
+
     fn is_node_connected_at_input(&self, node_id:NodeId, crumbs:span_tree::Crumbs) -> bool {
         if let Some(node) = self.nodes.get_cloned(&node_id) {
             for in_edge_id in node.in_edges.raw.borrow().iter() {
@@ -1132,6 +1332,9 @@ pub struct GraphEditorModel {
     pub cursor             : cursor::Cursor,
     pub nodes              : Nodes,
     pub edges              : Edges,
+    pub notes              : SharedHashMap<NoteId,component::Note>,
+    pub groups             : SharedHashMap<GroupId,Group>,
+    selected_group         : Rc<Cell<Option<GroupId>>>,
     touch_state            : TouchState,
     frp                    : FrpInputs,
 }
@@ -1151,11 +1354,15 @@ impl GraphEditorModel {
         let display_object     = display::object::Instance::new(&logger);
         let nodes              = Nodes::new(&logger);
         let edges              = default();
+        let notes              = default();
+        let groups             = default();
+        let selected_group     = default();
         let frp                = FrpInputs::new(network);
         let touch_state        = TouchState::new(network,&scene.mouse.frp);
         let breadcrumbs        = component::Breadcrumbs::new(scene,focus_manager);
         let app                = app.clone_ref();
-        Self {logger,display_object,app,cursor,nodes,edges,touch_state,frp,breadcrumbs}.init()
+        Self {logger,display_object,app,cursor,nodes,edges,notes,groups,selected_group,touch_state
+             ,frp,breadcrumbs}.init()
     }
 
     fn init(self) -> Self {
@@ -1167,6 +1374,19 @@ impl GraphEditorModel {
         self.nodes.all.keys()
     }
 
+    /// Check if any text in the graph is being edited: a node's expression, comment or widget
+    /// value, a note, or a group title.
+    pub fn is_editing_text(&self) -> bool {
+        let nodes  = self.all_nodes().into_iter().filter_map(|id| self.nodes.get_cloned_ref(&id));
+        let notes  = self.notes.keys().into_iter().filter_map(|id| self.notes.get_cloned_ref(&id));
+        let groups = self.groups.keys().into_iter();
+        let groups = groups.filter_map(|id| self.groups.get_cloned_ref(&id));
+        let node_edited  = nodes.map(|node| node.is_editing());
+        let note_edited  = notes.map(|note| note.is_editing());
+        let group_edited = groups.map(|group| group.is_title_editing());
+        node_edited.chain(note_edited).chain(group_edited).any(|edited| edited)
+    }
+
     fn scene(&self) -> &Scene {
         self.app.display.scene()
     }
@@ -1203,6 +1423,228 @@ impl GraphEditorModel {
 }
 
 
+// === Notes and Comments ===
+
+impl GraphEditorModel {
+    pub fn set_note_text(&self, note_id:impl Into<NoteId>, text:impl Into<String>) {
+        let note_id = note_id.into();
+        if let Some(note) = self.notes.get_cloned_ref(&note_id) {
+            note.frp.set_text(text.into());
+        }
+    }
+
+    pub fn set_note_position(&self, note_id:impl Into<NoteId>, position:Vector2) {
+        let note_id = note_id.into();
+        if let Some(note) = self.notes.get_cloned_ref(&note_id) {
+            note.mod_position(|t| {
+                t.x = position.x;
+                t.y = position.y;
+            });
+        }
+    }
+
+    pub fn note_position(&self, note_id:impl Into<NoteId>) -> Vector2<f32> {
+        let note_id = note_id.into();
+        self.notes.get_cloned_ref(&note_id).map(|note| note.position().xy()).unwrap_or_default()
+    }
+
+    fn remove_note(&self, note_id:impl Into<NoteId>) {
+        self.notes.remove(&note_id.into());
+    }
+
+    fn start_note_editing(&self, note_id:impl Into<NoteId>) {
+        let note_id = note_id.into();
+        self.stop_editing_notes();
+        if let Some(note) = self.notes.get_cloned_ref(&note_id) {
+            note.frp.start_editing();
+        }
+    }
+
+    fn stop_editing_notes(&self) {
+        for note_id in self.notes.keys() {
+            if let Some(note) = self.notes.get_cloned_ref(&note_id) {
+                note.frp.stop_editing();
+            }
+        }
+    }
+
+    pub fn set_node_comment(&self, node_id:impl Into<NodeId>, comment:Option<String>) {
+        let node_id = node_id.into();
+        if let Some(node) = self.nodes.get_cloned_ref(&node_id) {
+            node.frp.set_comment(comment);
+        }
+    }
+
+    fn start_node_comment_editing(&self, node_id:impl Into<NodeId>) {
+        let node_id = node_id.into();
+        self.stop_editing_node_comments();
+        if let Some(node) = self.nodes.get_cloned_ref(&node_id) {
+            node.frp.start_comment_editing();
+        }
+    }
+
+    fn stop_editing_node_comments(&self) {
+        for node_id in self.all_nodes() {
+            if let Some(node) = self.nodes.get_cloned_ref(&node_id) {
+                node.frp.stop_comment_editing();
+            }
+        }
+    }
+}
+
+
+// === Node Widgets ===
+
+impl GraphEditorModel {
+    pub fn set_node_widgets
+    (&self, node_id:impl Into<NodeId>, widgets:Vec<node::port::widget::Definition>) {
+        let node_id = node_id.into();
+        if let Some(node) = self.nodes.get_cloned_ref(&node_id) {
+            node.frp.set_widgets(widgets);
+        }
+    }
+
+    fn stop_editing_node_widgets(&self) {
+        for node_id in self.all_nodes() {
+            if let Some(node) = self.nodes.get_cloned_ref(&node_id) {
+                node.frp.stop_widget_editing();
+            }
+        }
+    }
+}
+
+
+// === Groups ===
+
+impl GraphEditorModel {
+    pub fn groups_of_node(&self, node_id:impl Into<NodeId>) -> Vec<GroupId> {
+        let node_id = node_id.into();
+        let groups  = self.groups.raw.borrow();
+        let groups  = groups.iter().filter(|(_,group)| group.nodes.contains(&node_id));
+        groups.map(|(id,_)| *id).collect()
+    }
+
+    pub fn set_group_nodes(&self, group_id:impl Into<GroupId>, node_ids:&[NodeId]) {
+        let group_id = group_id.into();
+        if let Some(group) = self.groups.get_cloned_ref(&group_id) {
+            let old_nodes = group.nodes.mem_take();
+            for node_id in node_ids {
+                group.nodes.push(*node_id);
+            }
+            for node_id in old_nodes.iter().chain(node_ids) {
+                self.refresh_node_visibility(*node_id);
+            }
+            self.refresh_group_bounds(group_id);
+        }
+    }
+
+    pub fn set_group_title(&self, group_id:impl Into<GroupId>, title:impl Into<String>) {
+        let group_id = group_id.into();
+        if let Some(group) = self.groups.get_cloned_ref(&group_id) {
+            group.frp.set_title(title.into());
+        }
+    }
+
+    pub fn set_group_color(&self, group_id:impl Into<GroupId>, color:usize) {
+        let group_id = group_id.into();
+        if let Some(group) = self.groups.get_cloned_ref(&group_id) {
+            group.frp.set_color(color);
+        }
+    }
+
+    pub fn set_group_collapsed(&self, group_id:impl Into<GroupId>, collapsed:bool) {
+        let group_id = group_id.into();
+        if let Some(group) = self.groups.get_cloned_ref(&group_id) {
+            group.collapsed.set(collapsed);
+            group.frp.set_collapsed(collapsed);
+            for node_id in group.nodes.items() {
+                self.refresh_node_visibility(node_id);
+            }
+        }
+    }
+
+    pub fn is_group_collapsed(&self, group_id:impl Into<GroupId>) -> bool {
+        let group_id = group_id.into();
+        self.groups.get_cloned_ref(&group_id).map(|group| group.collapsed.get()).unwrap_or_default()
+    }
+
+    fn remove_group(&self, group_id:impl Into<GroupId>) {
+        let group_id = group_id.into();
+        if let Some(group) = self.groups.remove(&group_id) {
+            for node_id in group.nodes.items() {
+                self.refresh_node_visibility(node_id);
+            }
+        }
+        if self.selected_group.get() == Some(group_id) {
+            self.selected_group.set(None);
+        }
+    }
+
+    /// The positions of all nodes belonging to the given group.
+    fn group_node_positions(&self, group_id:impl Into<GroupId>) -> Vec<(NodeId,Vector2)> {
+        let group_id = group_id.into();
+        let nodes    = self.groups.get_cloned_ref(&group_id).map(|group| group.nodes.items());
+        let nodes    = nodes.unwrap_or_default();
+        nodes.into_iter().map(|node_id| (node_id,self.node_position(node_id))).collect()
+    }
+
+    /// Fit the group's frame to the nodes belonging to it.
+    fn refresh_group_bounds(&self, group_id:impl Into<GroupId>) {
+        let group_id = group_id.into();
+        if let Some(group) = self.groups.get_cloned_ref(&group_id) {
+            let nodes  = group.nodes.items();
+            let nodes  = nodes.iter().filter_map(|node_id| self.nodes.get_cloned_ref(node_id));
+            let bounds = nodes.fold(None,|bounds:Option<(Vector2,Vector2)>,node| {
+                let min = node.position().xy();
+                let max = min + Vector2(node.width(),node.height());
+                Some(match bounds {
+                    None                        => (min,max),
+                    Some((bound_min,bound_max)) => (bound_min.inf(&min),bound_max.sup(&max)),
+                })
+            });
+            if let Some(bounds) = bounds {
+                group.frp.set_bounds(bounds);
+            }
+        }
+    }
+
+    /// Hide the node and its edges if it belongs to any collapsed group, show it otherwise.
+    fn refresh_node_visibility(&self, node_id:NodeId) {
+        if let Some(node) = self.nodes.get_cloned_ref(&node_id) {
+            let groups = self.groups_of_node(node_id);
+            let hidden = groups.into_iter().any(|group_id| self.is_group_collapsed(group_id));
+            if hidden { node.unset_parent() } else { self.add_child(&node) }
+            for edge_id in self.node_in_and_out_edges(node_id) {
+                if let Some(edge) = self.edges.get_cloned_ref(&edge_id) {
+                    if hidden { edge.unset_parent() } else { self.add_child(&edge) }
+                }
+            }
+        }
+    }
+
+    fn stop_editing_group_titles(&self) {
+        for group_id in self.groups.keys() {
+            if let Some(group) = self.groups.get_cloned_ref(&group_id) {
+                group.frp.stop_title_editing();
+            }
+        }
+    }
+
+    fn select_group(&self, group_id:impl Into<GroupId>) {
+        let group_id = group_id.into();
+        self.stop_editing_group_titles();
+        self.selected_group.set(Some(group_id));
+        if let Some(group) = self.groups.get_cloned_ref(&group_id) {
+            group.frp.start_title_editing();
+        }
+    }
+
+    fn selected_group(&self) -> Option<GroupId> {
+        self.selected_group.get()
+    }
+}
+
+
 // === Remove ===
 
 impl GraphEditorModel {
@@ -1219,6 +1661,7 @@ impl GraphEditorModel {
                 if let Some(target_node) = self.nodes.get_cloned_ref(&target.node_id) {
                     target_node.in_edges.remove(&edge_id);
                 }
+                self.refresh_node_type_warning(target.node_id);
             }
         }
     }
@@ -1252,6 +1695,12 @@ impl GraphEditorModel {
         let node_id = node_id.into();
         self.nodes.remove(&node_id);
         self.nodes.selected.remove_item(&node_id);
+        for group_id in self.groups_of_node(node_id) {
+            if let Some(group) = self.groups.get_cloned_ref(&group_id) {
+                group.nodes.remove_item(&node_id);
+            }
+            self.refresh_group_bounds(group_id);
+        }
     }
 
     fn node_in_edges(&self, node_id:impl Into<NodeId>) -> Vec<EdgeId> {
@@ -1284,6 +1733,10 @@ impl GraphEditorModel {
         for edge_id in self.node_out_edges(node_id) {
             self.refresh_edge_source_size(edge_id);
         }
+        // The expected types of node's ports might have changed.
+        for edge_id in self.node_in_edges(node_id) {
+            self.refresh_edge_color(edge_id);
+        }
     }
 
     fn is_connection(&self, edge_id:impl Into<EdgeId>) -> bool {
@@ -1359,6 +1812,7 @@ impl GraphEditorModel {
             if let Some(target) = edge.take_target() {
                 if let Some(node) = self.nodes.get_cloned_ref(&target.node_id) {
                     node.in_edges.remove(&edge_id);
+                    self.refresh_node_type_warning(target.node_id);
                     let first_detached = self.edges.detached_target.is_empty();
                     self.edges.detached_target.insert(edge_id);
                     edge.view.frp.target_attached.emit(false);
@@ -1436,6 +1890,9 @@ impl GraphEditorModel {
             for edge_id in self.node_in_and_out_edges(node_id) {
                 self.refresh_edge_position(edge_id);
             }
+            for group_id in self.groups_of_node(node_id) {
+                self.refresh_group_bounds(group_id);
+            }
         }
     }
 
@@ -1444,6 +1901,9 @@ impl GraphEditorModel {
         if let Some(node) = self.nodes.get_cloned_ref(&node_id) {
             node.view.frp.set_expression_type.emit((ast_id,maybe_type))
         }
+        for edge_id in self.node_in_and_out_edges(node_id) {
+            self.refresh_edge_color(edge_id);
+        }
     }
 
     fn disable_grid_snapping_for(&self, node_ids:&[NodeId]) {
@@ -1487,6 +1947,9 @@ impl GraphEditorModel {
         if let Some(edge) = self.edges.get_cloned_ref(&edge_id) {
             let color = self.get_edge_color_or_default(edge_id);
             edge.view.set_color(color);
+            if let Some(target) = edge.target() {
+                self.refresh_node_type_warning(target.node_id);
+            }
         };
     }
 
@@ -1535,11 +1998,16 @@ impl GraphEditorModel {
     }
 
     /// Return a color for the edge. Either based on the edges source/target type, or a default
-    /// color defined in Theme Manager as `type . missing . color`
+    /// color defined in Theme Manager as `type . missing . color`. The edges connecting ports of
+    /// incompatible types get the `type . mismatch . color` as a warning.
     fn get_edge_color_or_default(&self, edge_id:EdgeId) -> color::Lcha {
         // FIXME : StyleWatch is unsuitable here, as it was designed as an internal tool for shape system (#795)
         let styles             = StyleWatch::new(&self.scene().style_sheet);
         let missing_type_color = styles.get_color(ensogl_theme::vars::graph_editor::edge::_type::missing::color);
+        if self.is_edge_type_mismatched(edge_id) {
+            let mismatch_color = ensogl_theme::vars::graph_editor::edge::_type::mismatch::color;
+            return styles.get_color(mismatch_color)
+        }
         match self.try_get_edge_color(edge_id) {
            Some(color) => color,
            None        => missing_type_color,
@@ -1555,6 +2023,74 @@ impl GraphEditorModel {
     }
 }
 
+
+// === Type Compatibility ===
+
+impl GraphEditorModel {
+    /// Return the type of the value flowing through the edge, known from its source port.
+    fn try_get_edge_source_type(&self, edge_id:EdgeId) -> Option<Type> {
+        let source            = self.edges.get_cloned_ref(&edge_id)?.source()?;
+        let node              = self.nodes.get_cloned_ref(&source.node_id)?;
+        let input_port_type   = node.view.ports.get_port_type(&source.port);
+        let output_port_type  = || node.view.output_ports.get_port_type(&source.port);
+        input_port_type.or_else(output_port_type)
+    }
+
+    /// Return the type expected by the port the edge is connected to.
+    fn try_get_edge_expected_type(&self, edge_id:EdgeId) -> Option<Type> {
+        let target = self.edges.get_cloned_ref(&edge_id)?.target()?;
+        let node   = self.nodes.get_cloned_ref(&target.node_id)?;
+        node.view.ports.get_port_expected_type(&target.port)
+    }
+
+    /// Check if the edge connects ports of incompatible types. Returns `false` if any of the types
+    /// is unknown.
+    pub fn is_edge_type_mismatched(&self, edge_id:EdgeId) -> bool {
+        self.edge_type_warning(edge_id).is_some()
+    }
+
+    /// Describe the mismatch of types of the ports connected by the edge. Returns `None` if the
+    /// types are compatible or any of them is unknown.
+    pub fn edge_type_warning(&self, edge_id:EdgeId) -> Option<String> {
+        let source   = self.try_get_edge_source_type(edge_id)?;
+        let expected = self.try_get_edge_expected_type(edge_id)?;
+        (!source.is_compatible_with(&expected)).as_some_from(|| {
+            let (expected,source) = (expected.short_name(),source.short_name());
+            format!("Type mismatch: expected {}, got {}.",expected,source)
+        })
+    }
+
+    /// Show the type mismatch warning on the node if any of its incoming edges connects ports of
+    /// incompatible types, and hide it otherwise.
+    fn refresh_node_type_warning(&self, node_id:NodeId) {
+        if let Some(node) = self.nodes.get_cloned_ref(&node_id) {
+            let edges   = node.in_edges.keys();
+            let warning = edges.into_iter().find_map(|edge_id| self.edge_type_warning(edge_id));
+            node.frp.set_warning(warning);
+        }
+    }
+
+    /// Highlight the input ports compatible with the type of the edges being dragged by their
+    /// targets, and dim the incompatible ones. Does nothing if no edge is being dragged.
+    fn highlight_compatible_ports(&self) {
+        if self.edges.detached_target.is_empty() {
+            return
+        }
+        let source_type = self.edges.detached_target.keys().into_iter().find_map(|edge_id| {
+            self.try_get_edge_source_type(edge_id)
+        });
+        self.set_ports_compatibility_highlight(source_type.as_ref());
+    }
+
+    fn set_ports_compatibility_highlight(&self, source_type:Option<&Type>) {
+        for node_id in self.all_nodes() {
+            if let Some(node) = self.nodes.get_cloned_ref(&node_id) {
+                node.view.ports.set_compatibility_highlight(source_type);
+            }
+        }
+    }
+}
+
 impl display::Object for GraphEditorModel {
     fn display_object(&self) -> &display::object::Instance {
         &self.display_object
@@ -1586,6 +2122,18 @@ impl GraphEditor {
         self.frp.add_node.emit(());
         self.frp.outputs.node_added.value()
     }
+
+    /// Add a new note and returns its ID.
+    pub fn add_note(&self) -> NoteId {
+        self.frp.add_note.emit(());
+        self.frp.outputs.note_added.value()
+    }
+
+    /// Add a new node group and returns its ID.
+    pub fn add_group(&self) -> GroupId {
+        self.frp.add_group.emit(());
+        self.frp.outputs.group_added.value()
+    }
 }
 
 impl application::command::Provider for GraphEditor {
@@ -1602,6 +2150,17 @@ impl application::shortcut::DefaultShortcutProvider for GraphEditor {
              , Self::self_shortcut(shortcut::Action::press        (&[Key::Escape],&[])                              , "cancel_project_name_editing")
              , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Backspace],&[])              , "remove_selected_nodes")
              , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Character("g".into())],&[])  , "collapse_selected_nodes")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Shift,Key::Character("g".into())],&[]), "expand_selected_node")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Character("c".into())],&[])  , "copy_selected_nodes")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Character("x".into())],&[])  , "cut_selected_nodes")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Character("v".into())],&[])  , "paste_nodes")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Character("l".into())],&[])  , "arrange_selected_nodes")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Shift,Key::Character("l".into())],&[]), "arrange_all_nodes")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Character("m".into())],&[])  , "add_note_at_cursor")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Character("k".into())],&[])  , "edit_selected_node_comment")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Shift,Key::Character("j".into())],&[]), "group_selected_nodes")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Shift,Key::Character("h".into())],&[]), "toggle_selected_group_collapsed")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Shift,Key::Character("u".into())],&[]), "remove_selected_group")
              , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Character(" ".into())],&[])  , "press_visualization_visibility")
              , Self::self_shortcut(shortcut::Action::double_press (&[Key::Control,Key::Character(" ".into())],&[])  , "double_press_visualization_visibility")
              , Self::self_shortcut(shortcut::Action::release      (&[Key::Control,Key::Character(" ".into())],&[])  , "release_visualization_visibility")
@@ -1620,6 +2179,9 @@ impl application::shortcut::DefaultShortcutProvider for GraphEditor {
              , Self::self_shortcut(shortcut::Action::press        (&[Key::Control],&[])                             , "edit_mode_on")
              , Self::self_shortcut(shortcut::Action::release      (&[Key::Control],&[])                             , "edit_mode_off")
              , Self::self_shortcut(shortcut::Action::release      (&[Key::Enter],&[])                               , "stop_editing")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Character("z".into())],&[])  , "undo")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Character("y".into())],&[])  , "redo")
+             , Self::self_shortcut(shortcut::Action::press        (&[Key::Control,Key::Shift,Key::Character("z".into())],&[]), "redo")
              ]
     }
 }
@@ -1916,6 +2478,10 @@ fn new_graph_editor(app:&Application) -> GraphEditor {
     let node_output_touch = TouchNetwork::<EdgeTarget>::new(&network,&mouse);
     node_expression_set <- source();
     outputs.node_expression_set <+ node_expression_set;
+    node_comment_edited <- source();
+    outputs.node_comment_edited <+ node_comment_edited;
+    node_port_value_set <- source();
+    outputs.node_port_value_set <+ node_port_value_set;
 
     on_output_connect_drag_mode   <- node_output_touch.down.constant(true);
     on_output_connect_follow_mode <- node_output_touch.selected.constant(false);
@@ -1942,6 +2508,7 @@ fn new_graph_editor(app:&Application) -> GraphEditor {
 
     eval  edge_over((edge_id) edge_hover.emit(Some(*edge_id)));
     eval_ edge_out(edge_hover.emit(None));
+    outputs.hovered_edge <+ edge_hover;
 
     edge_over_pos <- map2(&cursor_pos_in_scene,&edge_hover,|pos, edge_id|
         edge_id.map(|id| (id, *pos))
@@ -2026,7 +2593,10 @@ fn new_graph_editor(app:&Application) -> GraphEditor {
 
     let add_node_at_cursor = inputs.add_node_at_cursor.clone_ref();
     add_node           <- any (inputs.add_node,add_node_at_cursor);
-    new_node           <- add_node.map(f_!([model,node_cursor_style] model.new_node(&node_cursor_style,&node_output_touch.down,&node_input_touch.down,&node_expression_set)));
+    new_node           <- add_node.map(f_!([model,node_cursor_style] model.new_node
+        (&node_cursor_style,&node_output_touch.down,&node_input_touch.down,&node_expression_set
+        ,&node_comment_edited,&node_port_value_set)
+    ));
     outputs.node_added <+ new_node;
 
     node_with_position <- add_node_at_cursor.map3(&new_node,&mouse.position,|_,id,pos| (*id,*pos));
@@ -2101,6 +2671,135 @@ fn new_graph_editor(app:&Application) -> GraphEditor {
     }
 
 
+    // === Expand Node ===
+    frp::extend! { network
+
+    node_to_expand        <= inputs.expand_selected_node.map(f_!(model.last_selected_node()));
+    outputs.node_expanded <+ node_to_expand;
+    }
+
+
+    // === Copy, Cut and Paste Nodes ===
+    frp::extend! { network
+
+    // The shortcuts are left to the text areas while any text is edited.
+    copy_nodes  <= inputs.copy_selected_nodes . map(f_!((!model.is_editing_text()).as_some(())));
+    cut_nodes   <= inputs.cut_selected_nodes  . map(f_!((!model.is_editing_text()).as_some(())));
+    paste_nodes <= inputs.paste_nodes         . map(f_!((!model.is_editing_text()).as_some(())));
+
+    outputs.nodes_copied    <+ copy_nodes . map(f_!(model.selected_nodes()));
+    outputs.nodes_cut       <+ cut_nodes  . map(f_!(model.selected_nodes()));
+    outputs.paste_requested <+ paste_nodes.map2(&cursor_pos_in_scene,|_,pos| *pos);
+    }
+
+
+    // === Arrange Nodes ===
+    frp::extend! { network
+
+    outputs.nodes_arranged <+ inputs.arrange_selected_nodes . map(f_!(model.selected_nodes()));
+    outputs.nodes_arranged <+ inputs.arrange_all_nodes      . map(f_!(model.all_nodes()));
+    }
+
+
+    // === Notes ===
+    frp::extend! { network
+
+    note_edited        <- source();
+    outputs.note_edited <+ note_edited;
+
+    let add_note_at_cursor = inputs.add_note_at_cursor.clone_ref();
+    add_note           <- any (inputs.add_note,add_note_at_cursor);
+    new_note           <- add_note.map(f_!(model.new_note(&note_edited)));
+    outputs.note_added <+ new_note;
+
+    note_at_cursor     <- add_note_at_cursor.map3(&new_note,&cursor_pos_in_scene,
+        |_,id,pos| (*id,*pos)
+    );
+    eval note_at_cursor (((id,pos)) model.set_note_position(id,*pos));
+    eval note_at_cursor (((id,_))   model.start_note_editing(id));
+    outputs.note_position_set <+ note_at_cursor;
+
+    eval inputs.set_note_text     (((id,text)) model.set_note_text(id,text));
+    eval inputs.set_note_position (((id,pos))  model.set_note_position(id,*pos));
+    eval inputs.remove_note       ((id)        model.remove_note(id));
+
+    note_pos_on_press  <- touch.notes.down.map(f!((id) model.note_position(id)));
+    mouse_on_note_down <- mouse.position.sample(&touch.notes.down);
+    note_mouse_diff    <- mouse.position.map2(&mouse_on_note_down,|t,s|t-s);
+    note_mouse_diff    <- note_mouse_diff.gate(&touch.notes.is_down);
+    note_new_pos       <- note_mouse_diff.map2(&note_pos_on_press,|t,s|t+s);
+    note_dragged       <- note_new_pos.map2(&touch.notes.down,|pos,id| (*id,*pos));
+    eval note_dragged (((id,pos)) model.set_note_position(id,*pos));
+    note_was_dragged   <- bool(&touch.notes.down,&note_dragged);
+    note_drag_end      <- touch.notes.up.gate(&note_was_dragged);
+    outputs.note_position_set <+ note_drag_end.map(f!((id) (*id,model.note_position(id))));
+
+    eval touch.notes.selected ((id) model.start_note_editing(id));
+    eval_ touch.background.down (model.stop_editing_notes());
+    }
+
+
+    // === Groups ===
+    frp::extend! { network
+
+    group_title_edited <- source();
+    outputs.group_title_edited <+ group_title_edited;
+
+    new_group           <- inputs.add_group.map(f_!(model.new_group(&group_title_edited)));
+    outputs.group_added <+ new_group;
+    outputs.nodes_grouped <+ inputs.group_selected_nodes.map(f_!(model.selected_nodes()));
+
+    eval inputs.set_group_nodes     (((id,nodes))     model.set_group_nodes(id,nodes));
+    eval inputs.set_group_title     (((id,title))     model.set_group_title(id,title));
+    eval inputs.set_group_color     (((id,color))     model.set_group_color(id,*color));
+    eval inputs.set_group_collapsed (((id,collapsed)) model.set_group_collapsed(id,*collapsed));
+    eval inputs.remove_group        ((id)             model.remove_group(id));
+
+    group_to_toggle <= inputs.toggle_selected_group_collapsed.map(f_!(model.selected_group()));
+    group_toggled   <- group_to_toggle.map(f!((id) (*id,!model.is_group_collapsed(id))));
+    outputs.group_collapsed_set <+ group_toggled;
+    eval outputs.group_collapsed_set (((id,collapsed)) model.set_group_collapsed(id,*collapsed));
+    outputs.group_removed <+ inputs.remove_selected_group.map(f_!(model.selected_group())).unwrap();
+    eval outputs.group_removed ((id) model.remove_group(id));
+
+
+    // === Moving Groups ===
+
+    group_positions_on_press <- touch.groups.down.map(f!((id) model.group_node_positions(id)));
+    mouse_on_group_press     <- mouse.position.sample(&touch.groups.down);
+    group_mouse_diff         <- mouse.position.map2(&mouse_on_group_press,|t,s|t-s);
+    group_mouse_diff         <- group_mouse_diff.gate(&touch.groups.is_down);
+    group_was_dragged        <- bool(&touch.groups.down,&group_mouse_diff);
+    group_node_new_pos       <= group_mouse_diff.map2(&group_positions_on_press,|diff,positions| {
+        positions.iter().map(|(id,pos)| (*id,pos+diff)).collect_vec()
+    });
+    outputs.node_position_set <+ group_node_new_pos;
+    group_drag_end            <- touch.groups.up.gate(&group_was_dragged);
+    outputs.group_moved       <+ group_drag_end.map2(&group_mouse_diff,|id,diff| (*id,*diff));
+
+    eval touch.groups.selected ((id) model.select_group(id));
+    eval_ touch.background.down (model.stop_editing_group_titles());
+    }
+
+
+    // === Node Comments ===
+    frp::extend! { network
+
+    eval inputs.set_node_comment (((id,comment)) model.set_node_comment(id,comment.clone()));
+    comment_to_edit <= inputs.edit_selected_node_comment.map(f_!(model.last_selected_node()));
+    eval comment_to_edit ((id) model.start_node_comment_editing(id));
+    eval_ touch.background.down (model.stop_editing_node_comments());
+    }
+
+
+    // === Node Widgets ===
+    frp::extend! { network
+
+    eval inputs.set_node_widgets (((id,widgets)) model.set_node_widgets(id,widgets.clone()));
+    eval_ touch.background.down (model.stop_editing_node_widgets());
+    }
+
+
     // === Set Node Expression ===
     frp::extend! { network
 
@@ -2426,6 +3125,7 @@ fn new_graph_editor(app:&Application) -> GraphEditor {
             visualizations.add(handle);
         }
     }));
+    eval inputs.unregister_visualization ((path) visualizations.remove(path));
 
 
     // === Entering and Exiting Nodes ===
@@ -2435,6 +3135,15 @@ fn new_graph_editor(app:&Application) -> GraphEditor {
     outputs.node_exited  <+ inputs.exit_node;
 
 
+    // === Undo/Redo ===
+
+    // The shortcuts typed while editing a text are handled by the text field instead.
+    undo <= inputs.undo.map(f_!((!model.is_editing_text()).as_some(())));
+    redo <= inputs.redo.map(f_!((!model.is_editing_text()).as_some(())));
+    outputs.undo_requested <+ undo;
+    outputs.redo_requested <+ redo;
+
+
     // === OUTPUTS REBIND ===
 
     outputs.some_edge_targets_detached <+ inputs.some_edge_targets_detached;
@@ -2465,6 +3174,16 @@ fn new_graph_editor(app:&Application) -> GraphEditor {
     outputs.connection_removed <+ outputs.edge_removed;
 
 
+    // === Type Compatibility ===
+
+    // The source of a new edge is set after its target is detached.
+    eval_ inputs.some_edge_targets_detached (model.highlight_compatible_ports());
+    eval_ outputs.edge_source_set           (model.highlight_compatible_ports());
+    eval_ inputs.all_edge_targets_attached  (model.set_ports_compatibility_highlight(None));
+    type_mismatch <= new_connection.map(f!((id) model.is_edge_type_mismatched(*id).as_some(*id)));
+    outputs.connection_type_mismatch <+ type_mismatch;
+
+
     // === Status ===
 
     def is_active_src = source::<bool>();
@@ -2549,3 +3268,43 @@ impl display::Object for GraphEditor {
 }
 
 
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compatible(source:&str, expected:&str) -> bool {
+        Type::from(source.to_string()).is_compatible_with(&Type::from(expected.to_string()))
+    }
+
+    #[test]
+    fn any_type_compatibility() {
+        assert!(compatible("Any","Text"));
+        assert!(compatible("Text","Any"));
+        assert!(compatible("Base.Any","Base.Data.Number"));
+    }
+
+    #[test]
+    fn number_types_compatibility() {
+        assert!(compatible("Integer","Number"));
+        assert!(compatible("Decimal","Number"));
+        assert!(compatible("Number","Number"));
+        assert!(!compatible("Number","Integer"));
+        assert!(!compatible("Integer","Decimal"));
+        assert!(!compatible("Text","Number"));
+    }
+
+    #[test]
+    fn module_qualified_types_compatibility() {
+        assert!(compatible("Base.Data.Integer","Number"));
+        assert!(!compatible("Base.Data.Text","Main.Text"));
+        assert!(compatible("Text","Base.Data.Text"));
+        assert!(!compatible("Base.Data.Text","Base.Data.Boolean"));
+    }
+}
@@ -121,10 +121,16 @@ impl ViewModel {
             _                                   => todo!(),
         };
 
-        let data_str   = ViewModel::prepare_data_string(data_inner);
-        let output     = ViewModel::gen_html_from(data_str);
-        let mut output = output.unwrap_or_else(|_| String::from(PLACEHOLDER_STR));
-        if output     == "" { output = String::from(PLACEHOLDER_STR) }
+        let data_str = ViewModel::prepare_data_string(data_inner);
+        let output   = ViewModel::gen_html_from(data_str).unwrap_or_default();
+        self.display_html(&output);
+        Ok(())
+    }
+
+    /// Present the already generated HTML documentation in the documentation view. The placeholder
+    /// is displayed if the documentation is empty.
+    pub fn display_html(&self, html:&str) {
+        let output = if html.is_empty() { PLACEHOLDER_STR } else { html };
         // FIXME [MM] : Because of how Doc Parser was implemented in Engine repo, there is need to
         //              remove stylesheet link from generated code, that would otherwise point to
         //              non-existing file, as now stylesheet is connected by include_str! macro, and
@@ -135,7 +141,6 @@ impl ViewModel {
         let output     = output.replace(import_css, "");
 
         self.push_to_dom(output);
-        Ok(())
     }
 
     /// Load an HTML file into the documentation view when user is waiting for data to be received.
@@ -31,6 +31,7 @@
 pub mod debug_scenes;
 pub mod documentation;
 pub mod project;
+pub mod project_browser;
 pub mod searcher;
 
 pub use ide_view_graph_editor as graph_editor;
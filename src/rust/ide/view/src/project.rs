@@ -6,6 +6,7 @@ use crate::documentation;
 use crate::graph_editor::component::node;
 use crate::graph_editor::component::node::Expression;
 use crate::graph_editor::component::visualization;
+use crate::graph_editor::EdgeId;
 use crate::graph_editor::GraphEditor;
 use crate::graph_editor::NodeId;
 use crate::searcher;
@@ -89,6 +90,8 @@ impl Model {
 ensogl::def_command_api! { Commands
     /// Add new node and start editing it's expression.
     add_new_node,
+    /// Add new node inserted into the hovered connection and start editing it's expression.
+    insert_node_into_hovered_edge,
     /// Abort currently node edit. If it was added node, it will be removed, if the existing node was edited, its old expression will be restored.
     abort_node_editing,
     /// Simulates a style toggle press event.
@@ -109,11 +112,13 @@ ensogl_text::define_endpoints! {
     Commands { Commands }
     Input {
         set_documentation_data (visualization::Data),
+        set_documentation_html (String),
         set_suggestions        (list_view::entry::AnyModelProvider),
     }
     Output {
         documentation_visible         (bool),
         adding_new_node               (bool),
+        edge_of_added_node            (Option<EdgeId>),
         edited_node                   (Option<NodeId>),
         old_expression_of_edited_node (Expression),
         editing_aborted               (NodeId),
@@ -152,6 +157,7 @@ impl View {
             // === Documentation Set ===
 
             eval frp.set_documentation_data ((data) model.documentation.frp.send_data.emit(data));
+            eval frp.set_documentation_html ((html) model.documentation.display_html(html));
             eval frp.set_suggestions        ((provider) model.searcher.frp.set_entries(provider));
 
 
@@ -184,6 +190,7 @@ impl View {
             editing_aborted <+ frp.abort_node_editing.constant(true);
             should_finish_editing <-
                 any(frp.abort_node_editing,searcher.editing_committed,frp.add_new_node);
+            should_finish_editing <+ frp.insert_node_into_hovered_edge;
             eval should_finish_editing ((()) graph.inputs.stop_editing.emit(()));
             _eval <- graph.outputs.edited_node.map2(&searcher.is_visible,
                 f!([model,searcher_left_top](edited_node_id,is_visible) {
@@ -197,6 +204,8 @@ impl View {
                     } else {
                         model.searcher.hide();
                         model.searcher.set_entries(list_view::entry::AnyModelProvider::default());
+                        model.searcher.set_categories(Rc::new(Vec::new()));
+                        model.searcher.set_breadcrumbs(Vec::<String>::new());
                     }
                 }
             ));
@@ -209,8 +218,16 @@ impl View {
 
             // === Adding New Node ===
 
-            frp.source.adding_new_node <+ frp.add_new_node.constant(true);
+            // The edge must be set before the node is added, as the `edit_node` event is handled
+            // immediately.
+            hovered_edge                  <- graph.outputs.hovered_edge.sample(
+                &frp.insert_node_into_hovered_edge).unwrap();
+            frp.source.edge_of_added_node <+ frp.add_new_node.constant(None);
+            frp.source.edge_of_added_node <+ hovered_edge.map(|edge| Some(*edge));
+            frp.source.adding_new_node    <+ frp.add_new_node.constant(true);
+            frp.source.adding_new_node    <+ hovered_edge.constant(true);
             eval frp.add_new_node ((()) model.add_node_and_edit());
+            eval_ hovered_edge (model.add_node_and_edit());
 
             adding_committed           <- frp.editing_committed.gate(&frp.adding_new_node);
             adding_aborted             <- frp.editing_aborted.gate(&frp.adding_new_node);
@@ -273,6 +290,7 @@ impl application::shortcut::DefaultShortcutProvider for View {
         use frp::io::keyboard::Key;
         vec!
         [ Self::self_shortcut(shortcut::Action::press  (&[Key::Shift,Key::Tab],&[])                               , "add_new_node")
+        , Self::self_shortcut(shortcut::Action::press  (&[Key::Control,Key::Character("i".into())],&[])          , "insert_node_into_hovered_edge")
         , Self::self_shortcut(shortcut::Action::press  (&[Key::Escape],&[])                                       , "abort_node_editing")
         , Self::self_shortcut(shortcut::Action::press  (&[Key::Control,Key::Shift,Key::Character("s".into())],&[]), "toggle_style")
         , Self::self_shortcut(shortcut::Action::release(&[Key::Control,Key::Shift,Key::Character("s".into())],&[]), "toggle_style")
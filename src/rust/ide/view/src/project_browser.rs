@@ -0,0 +1,215 @@
+//! A Project Browser component.
+//!
+//! This component displays the list of user's projects and samples, allowing to choose the project
+//! to be opened or deleted. The entries are provided by the controllers, the component only
+//! reports which of them were chosen.
+//!
+//! Deleting requires confirmation: the first `delete_selected_entry` only displays a hint, and the
+//! entry is reported as deleted when the command is repeated with the same entry selected.
+
+use crate::prelude::*;
+
+use enso_frp as frp;
+use enso_frp::io::keyboard::Key;
+use ensogl::application;
+use ensogl::application::Application;
+use ensogl::application::shortcut;
+use ensogl::display;
+use ensogl_gui_list_view::ListView;
+use ensogl_text as text;
+
+pub use ensogl_gui_list_view::entry;
+
+
+
+// =================
+// === Constants ===
+// =================
+
+const PROJECT_BROWSER_WIDTH:f32  = 320.0;
+const PROJECT_BROWSER_HEIGHT:f32 = 359.5;
+/// The text size of the hint displayed below the list.
+const HINT_TEXT_SIZE:f32 = 10.0;
+/// The distance between the bottom of the list and the hint's baseline.
+const HINT_OFFSET:f32 = 16.0;
+/// The hint displayed when the deletion of the selected project waits for confirmation.
+const DELETE_CONFIRMATION_HINT:&str = "Press Ctrl+Delete again to delete the selected project.";
+
+
+
+// =============
+// === Model ===
+// =============
+
+#[derive(Clone,CloneRef,Debug)]
+struct Model {
+    logger         : Logger,
+    display_object : display::object::Instance,
+    list           : ListView,
+    hint           : text::Area,
+}
+
+impl Model {
+    fn new(app:&Application) -> Self {
+        let logger         = Logger::new("ProjectBrowserView");
+        let display_object = display::object::Instance::new(&logger);
+        let list           = app.new_view::<ListView>();
+        let hint           = app.new_view::<text::Area>();
+        list.resize(Vector2(PROJECT_BROWSER_WIDTH,PROJECT_BROWSER_HEIGHT));
+        hint.set_default_text_size(text::Size(HINT_TEXT_SIZE));
+        let x = -PROJECT_BROWSER_WIDTH / 2.0;
+        let y = -PROJECT_BROWSER_HEIGHT / 2.0 - HINT_OFFSET;
+        hint.set_position_xy(Vector2(x,y));
+        Self{logger,display_object,list,hint}
+    }
+
+    fn set_deletion_pending(&self, is_pending:bool) {
+        let content = if is_pending { DELETE_CONFIRMATION_HINT } else { "" };
+        self.hint.set_content(content);
+    }
+
+    fn set_visibility(&self, is_visible:bool) {
+        if is_visible {
+            self.display_object.add_child(&self.list);
+            self.display_object.add_child(&self.hint);
+        } else {
+            self.display_object.remove_child(&self.list);
+            self.display_object.remove_child(&self.hint);
+            self.list.set_entries(entry::AnyModelProvider::default());
+        }
+    }
+}
+
+
+
+// ===========
+// === FRP ===
+// ===========
+
+ensogl::def_command_api!( Commands
+    /// Show the project browser if it is hidden, hide it otherwise.
+    toggle_visibility,
+    /// Delete the project under the selected entry. Must be repeated to confirm the deletion.
+    delete_selected_entry,
+);
+
+ensogl_text::define_endpoints! {
+    Commands { Commands }
+    Input {
+        set_entries (entry::AnyModelProvider),
+        show        (),
+        hide        (),
+    }
+    Output {
+        chosen_entry     (entry::Id),
+        deleted_entry    (entry::Id),
+        pending_deletion (Option<entry::Id>),
+        is_visible       (bool),
+    }
+}
+
+
+
+// ============
+// === View ===
+// ============
+
+/// The Project Browser Component.
+#[allow(missing_docs)]
+#[derive(Clone,CloneRef,Debug)]
+pub struct View {
+    model   : Model,
+    pub frp : Frp,
+}
+
+impl Deref for View {
+    type Target = Frp;
+    fn deref(&self) -> &Self::Target { &self.frp }
+}
+
+impl View {
+    /// Create new component.
+    pub fn new(app:&Application) -> Self {
+        let model = Model::new(app);
+        let frp   = Frp::new_network();
+        Self{model,frp}.init()
+    }
+
+    /// Initialize the FRP network.
+    fn init(self) -> Self {
+        let network = &self.frp.network;
+        let model   = &self.model;
+        let frp     = &self.frp;
+        let source  = &self.frp.source;
+
+        frp::extend! { network
+            eval frp.set_entries ((entries) model.list.set_entries(entries));
+
+            was_visible       <- frp.is_visible.sample(&frp.toggle_visibility);
+            source.is_visible <+ frp.show.constant(true);
+            source.is_visible <+ frp.hide.constant(false);
+            source.is_visible <+ was_visible.map(|was_visible| !was_visible);
+            eval frp.is_visible ((is_visible) model.set_visibility(*is_visible));
+
+            // The list reacts to shortcuts also when hidden, so the hidden browser must not report
+            // any entries.
+            chosen_entry        <- model.list.chosen_entry.gate(&frp.is_visible).unwrap();
+            source.chosen_entry <+ chosen_entry;
+            source.is_visible   <+ chosen_entry.constant(false);
+
+            entry_to_delete <- model.list.selected_entry.sample(&frp.delete_selected_entry);
+            entry_to_delete <- entry_to_delete.gate(&frp.is_visible).unwrap();
+            confirmed       <= entry_to_delete.map2(&frp.pending_deletion,
+                |id,pending| (*pending == Some(*id)).as_some(*id));
+            requested       <= entry_to_delete.map2(&frp.pending_deletion,
+                |id,pending| (*pending != Some(*id)).as_some(Some(*id)));
+            other_selected  <= model.list.selected_entry.map2(&frp.pending_deletion,
+                |id,pending| (pending.is_some() && id != pending).as_some(()));
+            source.deleted_entry    <+ confirmed;
+            source.pending_deletion <+ requested;
+            source.pending_deletion <+ confirmed.constant(None);
+            source.pending_deletion <+ other_selected.constant(None);
+            source.pending_deletion <+ frp.is_visible.constant(None);
+            eval frp.pending_deletion ((pending) model.set_deletion_pending(pending.is_some()));
+        };
+
+        self
+    }
+}
+
+impl display::Object for View {
+    fn display_object(&self) -> &display::object::Instance { &self.model.display_object }
+}
+
+impl application::command::FrpNetworkProvider for View {
+    fn network(&self) -> &frp::Network {
+        &self.frp.network
+    }
+}
+
+impl application::command::CommandApi for View {
+    fn command_api_docs() -> Vec<application::command::EndpointDocs> {
+        Commands::command_api_docs()
+    }
+
+    fn command_api(&self) -> Vec<application::command::CommandEndpoint> {
+        self.frp.input.command.command_api()
+    }
+}
+
+impl application::command::Provider for View {
+    fn label() -> &'static str { "ProjectBrowser" }
+}
+
+impl application::View for View {
+    fn new(app: &Application) -> Self { Self::new(app) }
+}
+
+impl application::shortcut::DefaultShortcutProvider for View {
+    fn default_shortcuts() -> Vec<shortcut::Shortcut> {
+        vec!
+        [ Self::self_shortcut(shortcut::Action::press (&[Key::Control,Key::Character("o".into())],&[]), "toggle_visibility")
+        , Self::self_shortcut(shortcut::Action::press (&[Key::Control,Key::Delete],&[])               , "delete_selected_entry")
+        ]
+    }
+}
@@ -1,7 +1,7 @@
 //! A Searcher component.
 //!
 //! This component wraps the plain ListView in some searcher-specific logic, like committing
-//! editing, or picking suggestion with Tab.
+//! editing, picking suggestion with Tab, or browsing the categories of suggestions.
 
 use crate::prelude::*;
 
@@ -10,6 +10,7 @@ use ensogl::application;
 use ensogl::application::{Application, shortcut};
 use ensogl::display;
 use ensogl_gui_list_view::ListView;
+use ensogl_text as text;
 
 pub use ensogl_gui_list_view::entry;
 use enso_frp::io::keyboard::Key;
@@ -24,6 +25,12 @@ const SEARCHER_WIDTH:f32 = 240.0;
 // Because we don't implement clipping yet, the best UX is when searcher height is almost multiple
 // of entry height.
 const SEARCHER_HEIGHT:f32 = 179.5;
+/// The text size of the breadcrumbs of the browsed category.
+const BREADCRUMBS_TEXT_SIZE:f32 = 10.0;
+/// The distance between the breadcrumbs' baseline and the top of the list.
+const BREADCRUMBS_OFFSET:f32 = 6.0;
+/// The separator of the breadcrumbs' segments.
+const BREADCRUMBS_SEPARATOR:&str = " > ";
 
 
 
@@ -36,6 +43,7 @@ struct Model {
     logger         : Logger,
     display_object : display::object::Instance,
     list           : ListView,
+    breadcrumbs    : text::Area,
 }
 
 impl Model {
@@ -43,9 +51,22 @@ impl Model {
         let logger         = Logger::new("SearcherView");
         let display_object = display::object::Instance::new(&logger);
         let list           = app.new_view::<ListView>();
+        let breadcrumbs    = app.new_view::<text::Area>();
         list.resize(Vector2(SEARCHER_WIDTH, 0.0));
+        breadcrumbs.set_default_text_size(text::Size(BREADCRUMBS_TEXT_SIZE));
         display_object.add_child(&list);
-        Self{logger,display_object,list}
+        display_object.add_child(&breadcrumbs);
+        Self{logger,display_object,list,breadcrumbs}
+    }
+
+    fn set_breadcrumbs(&self, path:&[String]) {
+        self.breadcrumbs.set_content(path.join(BREADCRUMBS_SEPARATOR));
+    }
+
+    fn update_breadcrumbs_position(&self, size:Vector2<f32>) {
+        let x = -SEARCHER_WIDTH / 2.0;
+        let y = size.y / 2.0 + BREADCRUMBS_OFFSET;
+        self.breadcrumbs.set_position_xy(Vector2(x,y));
     }
 }
 
@@ -56,14 +77,19 @@ impl Model {
 // ===========
 
 ensogl::def_command_api!( Commands
-    /// Pick the selected suggestion and add it to the current input.
+    /// Pick the selected suggestion and add it to the current input. If the suggestion is
+    /// a category, enter it instead.
     pick_suggestion,
+    /// Leave the browsed category, going back to its parent.
+    leave_category,
 );
 
 ensogl_text::define_endpoints! {
     Commands { Commands }
     Input {
         set_entries      (entry::AnyModelProvider),
+        set_categories   (Rc<Vec<entry::Id>>),
+        set_breadcrumbs  (Vec<String>),
         show             (),
         hide             (),
     }
@@ -71,6 +97,8 @@ ensogl_text::define_endpoints! {
         selected_entry    (Option<entry::Id>),
         picked_entry      (Option<entry::Id>),
         editing_committed (),
+        category_entered  (entry::Id),
+        category_left     (),
         size              (Vector2<f32>),
         is_visible        (bool),
     }
@@ -87,6 +115,10 @@ ensogl_text::define_endpoints! {
 /// This component covers only the list of suggestions. The Searcher input is displayed as an
 /// additional graph node in edit mode, so we could easily display e.g. connections between selected
 /// node and searcher input.
+///
+/// In the browse mode some entries are categories: picking them emits `category_entered` instead of
+/// picking the entry or committing editing. The path of the browsed category is displayed above the
+/// list.
 #[allow(missing_docs)]
 #[derive(Clone,CloneRef,Debug)]
 pub struct View {
@@ -116,6 +148,10 @@ impl View {
 
         let height = Animation::<f32>::new(&network);
 
+        let category_of = |entry:&Option<entry::Id>, categories:&Rc<Vec<entry::Id>>| {
+            entry.filter(|entry| categories.contains(entry))
+        };
+
         frp::extend! { network
             eval frp.set_entries ((entries) model.list.set_entries(entries));
             source.selected_entry <+ model.list.selected_entry;
@@ -126,13 +162,26 @@ impl View {
             eval frp.show     ((()) height.set_target_value(SEARCHER_HEIGHT));
             eval frp.hide     ((()) height.set_target_value(0.0));
 
+            eval frp.set_breadcrumbs ((path) model.set_breadcrumbs(path));
+            eval model.list.size     ((size) model.update_breadcrumbs_position(*size));
+
             is_selected         <- model.list.selected_entry.map(|e| e.is_some());
             opt_picked_entry    <- model.list.selected_entry.sample(&frp.pick_suggestion);
-            source.picked_entry <+ opt_picked_entry.gate(&is_selected);
+            picked_entry        <- opt_picked_entry.gate(&is_selected);
+            chosen_entry        <- model.list.chosen_entry.gate(&is_selected);
+            picked_category     <- picked_entry.map2(&frp.set_categories,category_of);
+            chosen_category     <- chosen_entry.map2(&frp.set_categories,category_of);
+            is_picked_category  <- picked_category.map(|category| category.is_some());
+            is_chosen_category  <- chosen_category.map(|category| category.is_some());
+            source.category_entered <+ any(picked_category,chosen_category).unwrap();
+            source.category_left    <+ frp.leave_category;
+
+            source.picked_entry <+ picked_entry.gate_not(&is_picked_category);
             // Order of the two below is important: we want pick the entry first, and then commit
             // editing.
-            source.picked_entry      <+ model.list.chosen_entry.gate(&is_selected);
-            source.editing_committed <+ model.list.chosen_entry.gate(&is_selected).constant(());
+            chosen_completion        <- chosen_entry.gate_not(&is_chosen_category);
+            source.picked_entry      <+ chosen_completion;
+            source.editing_committed <+ chosen_completion.constant(());
         };
 
         self
@@ -170,6 +219,8 @@ impl application::shortcut::DefaultShortcutProvider for View {
     fn default_shortcuts() -> Vec<shortcut::Shortcut> {
         vec!
         [ Self::self_shortcut(shortcut::Action::press   (&[Key::Tab], &[]) , "pick_suggestion"),
+          Self::self_shortcut(shortcut::Action::press   (&[Key::Control,Key::ArrowUp], &[])
+            , "leave_category"),
         ]
     }
 }
@@ -350,7 +350,7 @@ impl Sub for Index {
 // === TextLocation ===
 
 /// A position of character in a multiline text.
-#[derive(Copy,Clone,Debug,PartialEq,Eq,PartialOrd,Ord)]
+#[derive(Copy,Clone,Debug,PartialEq,Eq,PartialOrd,Ord,Serialize,Deserialize)]
 pub struct TextLocation {
     /// Line index.
     pub line: usize,
@@ -474,6 +474,22 @@ impl<Index,Content> TextChangeTemplate<Index,Content> {
     }
 }
 
+impl TextChange {
+    /// Creates the change which turns the `old` text into the `new` one. Only the fragment between
+    /// the longest common prefix and suffix of both texts is replaced.
+    pub fn from_diff(old:&str, new:&str) -> Self {
+        let old_chars  = old.chars().collect::<Vec<_>>();
+        let new_chars  = new.chars().collect::<Vec<_>>();
+        let prefix     = old_chars.iter().zip(&new_chars).take_while(|(a,b)| a == b).count();
+        let max_suffix = old_chars.len().min(new_chars.len()) - prefix;
+        let suffix     = old_chars.iter().rev().zip(new_chars.iter().rev()).take(max_suffix);
+        let suffix     = suffix.take_while(|(a,b)| a == b).count();
+        let replaced   = Index::new(prefix)..Index::new(old_chars.len() - suffix);
+        let inserted   = new_chars[prefix..new_chars.len() - suffix].iter().collect();
+        Self::replace(replaced,inserted)
+    }
+}
+
 impl<Index:Sub+Clone,Content> TextChangeTemplate<Index,Content> {
     /// Calculate the size of the replaced text.
     pub fn replaced_size(&self) -> Index::Output {
@@ -582,6 +598,21 @@ mod test {
         assert_eq!(TextLocation::at_document_end("\n"), TextLocation {line:1, column:0});
     }
 
+    #[test]
+    fn text_change_from_diff() {
+        let case = |old:&str, new:&str, replaced:Range<usize>, inserted:&str| {
+            let change = TextChange::from_diff(old,new);
+            assert_eq!(change.replaced,Index::new(replaced.start)..Index::new(replaced.end));
+            assert_eq!(change.inserted,inserted);
+        };
+        case("main = 2 + 2","main = 2 + 3",11..12,"3");
+        case("main = 2 + 2","main = 2 + 2",12..12,"");
+        case("foo","foo bar",3..3," bar");
+        case("aaa","aa",2..3,"");
+        case("zażółć","zażólć",4..5,"l");
+        case("","new",0..0,"new");
+    }
+
     #[test]
     fn indexing_utf8() {
         let str = "zazó黄ć gęślą jaźń";
@@ -74,6 +74,19 @@ impl KeyMask {
         mask
     }
 
+    /// Get the keys set in the mask, modifiers first. Only the modifiers, the common special keys,
+    /// the space and the alphanumeric characters are recognized, as the mask does not store the
+    /// keys themselves.
+    pub fn keys(&self) -> Vec<Key> {
+        let special    = vec!
+            [ Key::Control, Key::Alt, Key::Shift, Key::Meta, Key::Enter, Key::Escape, Key::Tab
+            , Key::Backspace, Key::Delete, Key::ArrowUp, Key::ArrowDown, Key::ArrowLeft
+            , Key::ArrowRight ];
+        let characters = std::iter::once(' ').chain('a'..='z').chain('0'..='9');
+        let characters = characters.map(|character| Key::Character(character.to_string()));
+        special.into_iter().chain(characters).filter(|key| self.contains(key)).collect()
+    }
+
     /// Handles new key press event and updates the key mask accordingly.
     ///
     /// **WARNING**
@@ -272,4 +285,11 @@ mod test {
         let expected_key_mask:KeyMask = std::iter::once(&key2).collect();
         assert_eq!(expected_key_mask, sampler.value());
     }
+
+    #[test]
+    fn key_mask_keys() {
+        let mask = KeyMask::control_plus('g');
+        assert_eq!(mask.keys(),vec![Key::Control,Key::Character("g".to_string())]);
+        assert_eq!(KeyMask::default().keys(),Vec::<Key>::new());
+    }
 }
\ No newline at end of file
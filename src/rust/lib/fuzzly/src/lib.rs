@@ -7,6 +7,9 @@
 //!
 //! The metrics used for scoring may be adjusted by implementing `Metric` trait, or by customizing
 //! parameters of metrics defined in `metric` module.
+//!
+//! When the pattern may contain typos, the `find_best_subsequence_with_typos` function may be used
+//! instead: it matches also the `text`'s subsequences being the `pattern` with some typos fixed.
 #![feature(option_result_contains)]
 
 #![warn(missing_docs)]
@@ -21,6 +24,7 @@
 pub mod subsequence_graph;
 pub mod metric;
 pub mod score;
+pub mod typo;
 
 pub use enso_prelude as prelude;
 pub use metric::Metric;
@@ -28,3 +32,5 @@ pub use subsequence_graph::Graph as SubsequenceGraph;
 pub use score::Subsequence;
 pub use score::matches;
 pub use score::find_best_subsequence;
+pub use score::find_best_subsequence_with_typos;
+pub use score::find_best_subsequence_with_variants;
@@ -21,6 +21,10 @@ pub trait Metric {
     /// How good is the edge on the path on the Subsequence Graph.
     fn measure_edge(&self, edge:subsequence_graph::Edge, text:&str, pattern:&str) -> f32;
 
+    /// How good is the match which required fixing given number of typos in the pattern (see
+    /// `typo` module). The `pattern` is the original one, without typos fixed.
+    fn measure_typos(&self, _typos:usize, _text:&str, _pattern:&str) -> f32 { 0.0 }
+
     /// Return a new metric being a sum of this and `rhs`.
     fn sum<Rhs:Metric>(self, rhs:Rhs) -> Sum<Self,Rhs> where Self:Sized { Sum(self, rhs) }
 }
@@ -33,7 +37,11 @@ pub trait Metric {
 
 /// The default metric, recommended by this library
 pub fn default() -> impl Metric {
-    SubsequentLettersBonus::default().sum(CaseMatchBonus::default())
+    let subsequent_letters = SubsequentLettersBonus::default();
+    let case_match         = CaseMatchBonus::default();
+    let word_initials      = WordInitialsBonus::default();
+    let typos              = TypoPenalty::default();
+    subsequent_letters.sum(case_match).sum(word_initials).sum(typos)
 }
 
 
@@ -42,6 +50,23 @@ pub fn default() -> impl Metric {
 // === Implementations ===
 // =======================
 
+// === Reference ===
+
+impl<M:Metric> Metric for &M {
+    fn measure_vertex(&self, vertex:subsequence_graph::Vertex, text:&str, pattern:&str) -> f32 {
+        M::measure_vertex(*self,vertex,text,pattern)
+    }
+
+    fn measure_edge(&self, edge:subsequence_graph::Edge, text:&str, pattern:&str) -> f32 {
+        M::measure_edge(*self,edge,text,pattern)
+    }
+
+    fn measure_typos(&self, typos:usize, text:&str, pattern:&str) -> f32 {
+        M::measure_typos(*self,typos,text,pattern)
+    }
+}
+
+
 // === Sum ===
 
 /// The structure representing the sum of two metrics
@@ -62,6 +87,13 @@ impl<M1:Metric, M2:Metric> Metric for Sum<M1,M2> {
         let right            = right.measure_edge(edge,text,pattern);
         left + right
     }
+
+    fn measure_typos(&self, typos:usize, text:&str, pattern:&str) -> f32 {
+        let Self(left,right) = self;
+        let left             = left.measure_typos(typos,text,pattern);
+        let right            = right.measure_typos(typos,text,pattern);
+        left + right
+    }
 }
 
 
@@ -137,3 +169,102 @@ impl Metric for CaseMatchBonus {
 
     fn measure_edge(&self, _:subsequence_graph::Edge, _:&str, _:&str) -> f32 { 0.0 }
 }
+
+
+// === WordInitialsBonus ===
+
+/// A metric which scores the matched characters being initials of the words in text. The words are
+/// separated by non-alphanumeric characters (like in `snake_case` or dotted `Module.Path`), or
+/// start with an uppercase letter following a lowercase one (like in `CamelCase`).
+#[derive(Copy,Clone,Debug)]
+pub struct WordInitialsBonus {
+    /// A score added for each matched word initial.
+    pub bonus_per_initial : f32,
+}
+
+impl Default for WordInitialsBonus {
+    fn default() -> Self {
+        WordInitialsBonus {
+            bonus_per_initial : 0.75,
+        }
+    }
+}
+
+impl WordInitialsBonus {
+    /// Check if the char at given position in text is a word initial.
+    pub fn is_word_initial(text:&str, position_in_text:usize) -> bool {
+        let previous = position_in_text.checked_sub(1).and_then(|i| text.chars().nth(i));
+        let current  = text.chars().nth(position_in_text);
+        match (previous,current) {
+            (_,None)                       => false,
+            (None,Some(current))           => current.is_alphanumeric(),
+            (Some(previous),Some(current)) => {
+                let after_separator = !previous.is_alphanumeric() && current.is_alphanumeric();
+                let camel_case_hump = previous.is_lowercase() && current.is_uppercase();
+                after_separator || camel_case_hump
+            }
+        }
+    }
+}
+
+impl Metric for WordInitialsBonus {
+    fn measure_vertex(&self, vertex:subsequence_graph::Vertex, text:&str, _pattern:&str) -> f32 {
+        let is_initial = Self::is_word_initial(text,vertex.position_in_text);
+        if is_initial { self.bonus_per_initial } else { 0.0 }
+    }
+
+    fn measure_edge(&self, _:subsequence_graph::Edge, _:&str, _:&str) -> f32 { 0.0 }
+}
+
+
+// === TypoPenalty ===
+
+/// A metric which lowers the score of matches which required fixing typos in the pattern.
+#[derive(Copy,Clone,Debug)]
+pub struct TypoPenalty {
+    /// A score subtracted for each fixed typo.
+    pub penalty_per_typo : f32,
+}
+
+impl Default for TypoPenalty {
+    fn default() -> Self {
+        TypoPenalty {
+            penalty_per_typo : 1.0,
+        }
+    }
+}
+
+impl Metric for TypoPenalty {
+    fn measure_vertex(&self, _:subsequence_graph::Vertex, _:&str, _:&str) -> f32 { 0.0 }
+
+    fn measure_edge(&self, _:subsequence_graph::Edge, _:&str, _:&str) -> f32 { 0.0 }
+
+    fn measure_typos(&self, typos:usize, _text:&str, _pattern:&str) -> f32 {
+        -self.penalty_per_typo * typos as f32
+    }
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn recognizing_word_initials() {
+        let initials = |text:&str| {
+            let positions = 0..text.chars().count();
+            positions.filter(|i| WordInitialsBonus::is_word_initial(text,*i)).collect_vec()
+        };
+        assert_eq!(initials("get_table_column"),vec![0,4,10]);
+        assert_eq!(initials("getTableColumn"),vec![0,3,8]);
+        assert_eq!(initials("Base.Data.Text"),vec![0,5,10]);
+        assert_eq!(initials("_private"),vec![1]);
+        assert_eq!(initials("HTTP"),vec![0]);
+        assert_eq!(initials(""),Vec::<usize>::new());
+    }
+}
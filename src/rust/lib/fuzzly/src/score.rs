@@ -4,6 +4,7 @@ use crate::prelude::*;
 
 use crate::metric::Metric;
 use crate::subsequence_graph;
+use crate::typo;
 use crate::SubsequenceGraph;
 
 use std::collections::hash_map::Entry;
@@ -172,6 +173,20 @@ impl Subsequence {
         else if self.score > rhs.score                    { std::cmp::Ordering::Greater }
         else                                              { std::cmp::Ordering::Equal   }
     }
+
+    /// The ranges of `text`'s char indices which belong to the subsequence. The adjacent chars are
+    /// merged into a single range, so the ranges may be used for highlighting the matched parts
+    /// of the text.
+    pub fn char_ranges(&self) -> Vec<Range<usize>> {
+        let mut ranges = Vec::<Range<usize>>::new();
+        for index in &self.indices {
+            match ranges.last_mut() {
+                Some(last) if last.end == *index => last.end += 1,
+                _                                => ranges.push(*index..*index + 1),
+            }
+        }
+        ranges
+    }
 }
 
 /// Find best subsequence in `text` which case-insensitively equals to `pattern` in terms of given
@@ -215,6 +230,51 @@ pub fn find_best_subsequence
 }
 
 
+/// Find best subsequence in `text` which case-insensitively equals to `pattern` with at most
+/// `max_typos` typos fixed (see `typo` module), in terms of given `metric`.
+///
+/// The exact matches are always preferred: the pattern variants with more typos fixed are checked
+/// only if none of the variants with fewer typos matches `text`. The score of a match with typos
+/// fixed is adjusted by the `metric`'s typos measure.
+///
+/// The pattern variants are generated on each call. When matching many texts against the same
+/// pattern, generate them once with `typo::variants` and use `find_best_subsequence_with_variants`.
+pub fn find_best_subsequence_with_typos
+(text:impl Str, pattern:impl Str, metric:impl Metric, max_typos:usize) -> Option<Subsequence> {
+    let text    = text.as_ref();
+    let pattern = pattern.as_ref();
+    if matches(text,pattern) {
+        find_best_subsequence(text,pattern,metric)
+    } else {
+        let variants = typo::variants(pattern,max_typos);
+        find_best_subsequence_with_variants(text,pattern,&variants,metric)
+    }
+}
+
+/// Find best subsequence in `text` which case-insensitively equals to one of the `pattern`'s
+/// `variants` with typos fixed, as generated by `typo::variants`. The `pattern` itself is not
+/// matched.
+///
+/// The variants with fewer typos fixed are preferred, and the score of the match is adjusted by
+/// the `metric`'s typos measure.
+pub fn find_best_subsequence_with_variants
+(text:impl Str, pattern:impl Str, variants:&[typo::Variant], metric:impl Metric)
+-> Option<Subsequence> {
+    let text      = text.as_ref();
+    let pattern   = pattern.as_ref();
+    let max_typos = variants.iter().map(|variant| variant.typos).max().unwrap_or(0);
+    (1..=max_typos).find_map(|typos| {
+        let with_typos = variants.iter().filter(|variant| variant.typos == typos);
+        let matching   = with_typos.filter(|variant| matches(text,variant.pattern.as_str()));
+        let found      = matching.filter_map(|variant| {
+            find_best_subsequence(text,variant.pattern.as_str(),&metric)
+        });
+        let best  = found.max_by(|lhs,rhs| lhs.compare_scores(rhs))?;
+        let score = best.score + metric.measure_typos(typos,text,pattern);
+        Some(Subsequence {score,..best})
+    })
+}
+
 
 // =============
 // === Tests ===
@@ -309,4 +369,49 @@ mod test {
         let text    = "";
         assert_eq!(find_best_subsequence(text,pattern,mock_metric::Sum::default()), None);
     }
+
+    #[test]
+    fn subsequence_char_ranges() {
+        let subsequence = |indices:Vec<usize>| Subsequence {score:0.0,indices};
+        assert_eq!(subsequence(vec![]).char_ranges(),Vec::<Range<usize>>::new());
+        assert_eq!(subsequence(vec![3]).char_ranges(),vec![3..4]);
+        assert_eq!(subsequence(vec![0,1,2,5,7,8]).char_ranges(),vec![0..3,5..6,7..9]);
+    }
+
+    #[test]
+    fn finding_best_subsequence_with_typos() {
+        let metric = crate::metric::default();
+        let found  = |text,pattern,max_typos| {
+            find_best_subsequence_with_typos(text,pattern,&metric,max_typos)
+        };
+        let swapped = found("table","tabel",1).unwrap();
+        assert_eq!(swapped.indices,vec![0,1,2,3,4]);
+        let exact = find_best_subsequence("table","table",&metric).unwrap();
+        assert!(swapped.score < exact.score);
+        assert_eq!(found("table","tabel",0),None);
+        assert_eq!(found("table","tabxel",1),None);
+        assert!(found("table","tabxel",2).is_some());
+
+        let exact_match = found("tabel_view","tabel",1).unwrap();
+        assert_eq!(exact_match,find_best_subsequence("tabel_view","tabel",&metric).unwrap());
+
+        let variants = crate::typo::variants("tabel",1);
+        let found    = |text| find_best_subsequence_with_variants(text,"tabel",&variants,&metric);
+        assert_eq!(found("table"),Some(swapped));
+        assert_eq!(found("column"),None);
+    }
+
+    #[test]
+    fn preferring_word_initials() {
+        let metric   = crate::metric::default();
+        let initials = find_best_subsequence("get_table_column","gtc",&metric).unwrap();
+        let letters  = find_best_subsequence("gotcha","gtc",&metric).unwrap();
+        assert_eq!(initials.indices,vec![0,4,10]);
+        assert!(initials.score > letters.score);
+
+        let camel_case = find_best_subsequence("getTableColumn","gtc",&metric).unwrap();
+        assert_eq!(camel_case.indices,vec![0,3,8]);
+        let module_path = find_best_subsequence("Base.Data.Text","bdt",&metric).unwrap();
+        assert_eq!(module_path.indices,vec![0,5,10]);
+    }
 }
@@ -0,0 +1,138 @@
+//! Tolerating typos in the pattern.
+//!
+//! Because the pattern is matched against any subsequence of the text, the characters missing in
+//! the pattern are never a problem. Therefore we consider only two kinds of typos: a redundant
+//! (or mistyped) character, which is fixed by removing it from the pattern, and two adjacent
+//! characters typed in wrong order, which is fixed by swapping them.
+use crate::prelude::*;
+
+
+
+// =================
+// === Tolerance ===
+// =================
+
+/// The number of pattern characters required to tolerate a single typo.
+pub const CHARS_PER_TYPO : usize = 4;
+
+/// The maximum number of typos tolerated in any pattern.
+pub const MAX_TYPOS : usize = 2;
+
+/// The number of typos tolerated in the given pattern: one per each `CHARS_PER_TYPO` characters,
+/// but no more than `MAX_TYPOS`.
+///
+/// The short patterns must match exactly, because fixing a typo in them would make them match
+/// almost anything.
+pub fn max_typos(pattern:impl Str) -> usize {
+    (pattern.as_ref().chars().count() / CHARS_PER_TYPO).min(MAX_TYPOS)
+}
+
+
+
+// ================
+// === Variants ===
+// ================
+
+/// A variant of the pattern with some typos fixed.
+#[allow(missing_docs)]
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub struct Variant {
+    pub pattern : String,
+    pub typos   : usize,
+}
+
+/// Generate all variants of the pattern with at most `max_typos` typos fixed. The pattern itself is
+/// not included.
+///
+/// The variants are ordered by the number of fixed typos, and each variant is returned only once,
+/// with the smallest number of typos it requires.
+pub fn variants(pattern:impl Str, max_typos:usize) -> Vec<Variant> {
+    let pattern     = pattern.as_ref().chars().collect_vec();
+    let mut visited = HashSet::new();
+    let mut layer   = vec![pattern.clone()];
+    let mut result  = Vec::new();
+    visited.insert(pattern);
+    for typos in 1..=max_typos {
+        let mut next_layer = Vec::new();
+        for variant in &layer {
+            for fixed in single_typo_fixes(variant) {
+                if visited.insert(fixed.clone()) {
+                    next_layer.push(fixed);
+                }
+            }
+        }
+        let variants = next_layer.iter().map(|chars| Variant {
+            pattern : chars.iter().collect(),
+            typos,
+        });
+        result.extend(variants);
+        layer = next_layer;
+    }
+    result
+}
+
+/// All the patterns created by fixing a single typo in `pattern`. A single-character pattern has
+/// no fixes, because removing its only character would give an empty pattern, matching anything.
+fn single_typo_fixes(pattern:&[char]) -> Vec<Vec<char>> {
+    let mut result = Vec::new();
+    if pattern.len() > 1 {
+        for index in 0..pattern.len() {
+            let mut removed = pattern.to_vec();
+            removed.remove(index);
+            result.push(removed);
+        }
+        for index in 0..pattern.len() - 1 {
+            if pattern[index] != pattern[index+1] {
+                let mut swapped = pattern.to_vec();
+                swapped.swap(index,index+1);
+                result.push(swapped);
+            }
+        }
+    }
+    result
+}
+
+
+
+// =============
+// === Tests ===
+// =============
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tolerated_typos() {
+        assert_eq!(max_typos(""),0);
+        assert_eq!(max_typos("gtc"),0);
+        assert_eq!(max_typos("tabel"),1);
+        assert_eq!(max_typos("ęśąćżźół"),2);
+        assert_eq!(max_typos("a_very_long_pattern"),2);
+    }
+
+    #[test]
+    fn generating_variants() {
+        let patterns = |variants:Vec<Variant>, typos:usize| {
+            variants.into_iter().filter(|v| v.typos == typos).map(|v| v.pattern).collect_vec()
+        };
+        assert!(variants("abc",0).is_empty());
+        assert!(variants("a",2).is_empty());
+
+        let abc = variants("abc",1);
+        assert_eq!(patterns(abc,1),vec!["bc","ac","ab","bac","acb"]);
+
+        let aab = variants("aab",1);
+        assert_eq!(patterns(aab,1),vec!["ab","aa","aba"]);
+
+        let abc = variants("abc",2);
+        assert!(abc.windows(2).all(|pair| pair[0].typos <= pair[1].typos));
+        let one_typo  = patterns(abc.clone(),1);
+        let two_typos = patterns(abc,2);
+        assert_eq!(one_typo.len(),5);
+        assert!(two_typos.contains(&"a".to_string()));
+        assert!(two_typos.contains(&"bca".to_string()));
+        assert!(!two_typos.contains(&"ab".to_string()));
+        assert!(!two_typos.contains(&"abc".to_string()));
+    }
+}